@@ -2,16 +2,71 @@ use proc_macro::TokenStream;
 use quote::quote;
 use syn::{parse::Parser as _, Ident};
 
-#[proc_macro_derive(Event)]
+/// Fields marked with `#[topic]` are published as event topics after the event name,
+/// the remaining fields are published as the event data map.
+#[proc_macro_derive(Event, attributes(topic))]
 pub fn derive_soroban_event(input: TokenStream) -> TokenStream {
-    let syn::DeriveInput { ident, .. } = syn::parse_macro_input! {input};
+    let syn::DeriveInput { ident, data, .. } = syn::parse_macro_input! {input};
 
     let ident_name = ident.to_string();
     let ident_name = ident_name.as_str();
 
+    let syn::Data::Struct(syn::DataStruct {
+        fields: syn::Fields::Named(fields),
+        ..
+    }) = data
+    else {
+        return quote! {
+            compile_error!("Event can only be derived for structs with named fields");
+        }
+        .into();
+    };
+
+    let (topic_fields, data_fields): (Vec<_>, Vec<_>) = fields
+        .named
+        .into_iter()
+        .partition(|field| field.attrs.iter().any(|attr| attr.path().is_ident("topic")));
+
+    if topic_fields.is_empty() {
+        return quote! {
+            impl shared::Event for #ident {
+                const EVENT_NAME: &'static str = #ident_name;
+            }
+        }
+        .into();
+    }
+
+    // The event name takes the first topic, and Soroban allows at most 4 topics
+    if topic_fields.len() > 3 {
+        return quote! {
+            compile_error!("Event can have at most 3 topic fields");
+        }
+        .into();
+    }
+
+    let topic_idents = topic_fields.iter().map(|field| &field.ident);
+    let data_idents = data_fields.iter().map(|field| &field.ident);
+    let data_keys = data_fields
+        .iter()
+        .map(|field| field.ident.as_ref().unwrap().to_string());
+
     quote! {
         impl shared::Event for #ident {
             const EVENT_NAME: &'static str = #ident_name;
+
+            fn publish(self, env: &soroban_sdk::Env) {
+                use soroban_sdk::IntoVal;
+
+                let mut data = soroban_sdk::Map::<soroban_sdk::Symbol, soroban_sdk::Val>::new(env);
+                #(
+                    data.set(soroban_sdk::Symbol::new(env, #data_keys), self.#data_idents.into_val(env));
+                )*
+
+                env.events().publish(
+                    (soroban_sdk::Symbol::new(env, Self::EVENT_NAME), #(self.#topic_idents,)*),
+                    data,
+                );
+            }
         }
     }
     .into()
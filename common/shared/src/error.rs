@@ -25,10 +25,18 @@ pub enum Error {
     InsufficientReceivedAmount = 104,
     Slippage = 105,
     InvalidFirstDeposit = 106,
+    ZeroOutput = 107,
+    InvalidRate = 108,
+    StalePrice = 109,
+    PriceDeviation = 110,
+    NotConverged = 111,
 
     // Factory
     PoolExist = 200,
     IdenticalAddresses = 201,
     MaxPoolsNumReached = 202,
     InvalidNumberOfTokens = 203,
+    ParamOutOfBounds = 204,
+    TokenNotAllowed = 205,
+    InvalidTokenDecimals = 206,
 }
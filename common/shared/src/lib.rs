@@ -3,6 +3,8 @@
 pub mod consts;
 mod error;
 mod event;
+pub mod price_feed;
+pub mod rate_provider;
 pub mod soroban_data;
 pub mod utils;
 
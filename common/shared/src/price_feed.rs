@@ -0,0 +1,52 @@
+use soroban_sdk::{contractclient, contracttype, Address, Env};
+
+use crate::{rate_provider::RATE_PRECISION, require, utils::safe_cast, Error};
+
+#[contracttype]
+#[derive(Debug, Clone)]
+pub struct PriceData {
+    /// `PriceFeed::decimals` precision
+    pub price: i128,
+    /// Ledger timestamp of the price
+    pub timestamp: u64,
+}
+
+/// Reports the price of a token in the asset the pool is pegged to, such as EUR in USD
+#[contractclient(name = "PriceFeedClient")]
+pub trait PriceFeed {
+    fn decimals(env: Env) -> u32;
+
+    /// `None` if the feed has no price yet
+    fn lastprice(env: Env) -> Option<PriceData>;
+}
+
+/// Reads the price of a feed as a `RATE_PRECISION` rate, a feed that reverts or reports a price
+/// out of range gives `Error::InvalidRate` and a price older than `max_price_age` seconds
+/// `Error::StalePrice`
+pub fn read_price_rate(env: &Env, price_feed: &Address, max_price_age: u64) -> Result<u128, Error> {
+    let price_feed = PriceFeedClient::new(env, price_feed);
+    let price_data = price_feed
+        .try_lastprice()
+        .ok()
+        .and_then(|price_data| price_data.ok())
+        .ok_or(Error::InvalidRate)?
+        .ok_or(Error::StalePrice)?;
+
+    let expires_at = price_data
+        .timestamp
+        .checked_add(max_price_age)
+        .ok_or(Error::InvalidRate)?;
+    require!(env.ledger().timestamp() <= expires_at, Error::StalePrice);
+
+    let decimals = price_feed
+        .try_decimals()
+        .ok()
+        .and_then(|decimals| decimals.ok())
+        .ok_or(Error::InvalidRate)?;
+
+    safe_cast::<_, u128>(price_data.price)?
+        .checked_mul(RATE_PRECISION)
+        .zip(10u128.checked_pow(decimals))
+        .map(|(price, denominator)| price / denominator)
+        .ok_or(Error::InvalidRate)
+}
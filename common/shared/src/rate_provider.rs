@@ -0,0 +1,27 @@
+use soroban_sdk::{contractclient, Address, Env};
+
+use crate::{require, Error};
+
+/// `RATE_PRECISION` is 1.0
+pub const RATE_PRECISION: u128 = 1_000_000_000_000_000_000;
+
+/// Reports how much of the pegged asset one unit of a yield-bearing or wrapped token is worth
+#[contractclient(name = "RateProviderClient")]
+pub trait RateProvider {
+    /// `RATE_PRECISION` precision
+    fn get_rate(env: Env) -> u128;
+}
+
+/// Reads the rate of a provider, a provider that reverts or reports a zero rate
+/// gives `Error::InvalidRate` instead of aborting the caller
+pub fn read_provider_rate(env: &Env, rate_provider: &Address) -> Result<u128, Error> {
+    let rate = RateProviderClient::new(env, rate_provider)
+        .try_get_rate()
+        .ok()
+        .and_then(|rate| rate.ok())
+        .ok_or(Error::InvalidRate)?;
+
+    require!(rate > 0, Error::InvalidRate);
+
+    Ok(rate)
+}
@@ -78,6 +78,14 @@ pub trait SorobanData:
         Self::extend_ttl_by_key(env, key);
     }
 
+    fn remove_by_key<K: IntoVal<Env, Val>>(env: &Env, key: &K) {
+        match Self::STORAGE_TYPE {
+            StorageType::Instance => env.storage().instance().remove(key),
+            StorageType::Temporary => env.storage().temporary().remove(key),
+            StorageType::Persistent => env.storage().persistent().remove(key),
+        };
+    }
+
     fn extend_ttl_by_key<K: IntoVal<Env, Val>>(env: &Env, key: &K) {
         match Self::STORAGE_TYPE {
             StorageType::Instance => env
@@ -1,6 +1,15 @@
+use core::cmp::Ordering;
+
 use ethnum::U256;
 
-use crate::Error;
+use crate::{rate_provider::RATE_PRECISION, require, utils::safe_cast, Error};
+
+/// Iteration limit of the Newton's method in `get_d` and `get_y`
+pub const MAX_NEWTON_ITERATIONS: usize = 255;
+/// Basis point precision, `BP` is 100%
+pub const BP: u128 = 10000;
+/// Swap, withdraw and deposit fee shares are fractions of it, one basis point is 100
+pub const FEE_DENOMINATOR: u128 = 1_000_000;
 
 pub fn sqrt(n: &U256) -> U256 {
     if *n == U256::ZERO {
@@ -80,3 +89,355 @@ pub fn cube(v: U256) -> Result<U256, Error> {
 pub fn square(v: U256) -> Result<U256, Error> {
     v.checked_mul(v).ok_or(Error::U256Overflow)
 }
+
+/// Converts a token amount with `decimals` to the `system_precision` the pool math works in
+pub fn to_system_precision(amount: u128, decimals: u32, system_precision: u32) -> u128 {
+    match decimals.cmp(&system_precision) {
+        Ordering::Greater => amount / (10u128.pow(decimals - system_precision)),
+        Ordering::Less => amount * (10u128.pow(system_precision - decimals)),
+        Ordering::Equal => amount,
+    }
+}
+
+/// Inverse of `to_system_precision`
+pub fn from_system_precision(amount: u128, decimals: u32, system_precision: u32) -> u128 {
+    match decimals.cmp(&system_precision) {
+        Ordering::Greater => amount * (10u128.pow(decimals - system_precision)),
+        Ordering::Less => amount / (10u128.pow(system_precision - decimals)),
+        Ordering::Equal => amount,
+    }
+}
+
+/// Values an amount in the pegged asset by a `RATE_PRECISION` rate
+#[inline]
+pub fn scale_by_rate(amount: u128, rate: u128) -> u128 {
+    amount * rate / RATE_PRECISION
+}
+
+/// Inverse of `scale_by_rate`
+#[inline]
+pub fn unscale_by_rate(amount: u128, rate: u128) -> u128 {
+    amount * RATE_PRECISION / rate
+}
+
+/// Rewards an LP amount has accrued from `acc_reward_per_share_p`, which carries `p` bits
+/// of fraction
+#[inline]
+pub fn accrued_rewards(lp_amount: u128, acc_reward_per_share_p: u128, p: u128) -> u128 {
+    (lp_amount * acc_reward_per_share_p) >> p
+}
+
+/// Splits a fee into the part left to the LPs and the admin fee
+#[inline]
+pub fn split_admin_fee(fee: u128, admin_fee_share_bp: u128) -> (u128, u128) {
+    let admin_fee = fee * admin_fee_share_bp / BP;
+    (fee - admin_fee, admin_fee)
+}
+
+/// The off-peg multiplier scales the swap fee up only above `BP` and with a fee to scale
+#[inline]
+pub fn is_offpeg_fee_enabled(swap_fee_share: u128, offpeg_fee_multiplier_bp: u128) -> bool {
+    swap_fee_share > 0 && offpeg_fee_multiplier_bp > BP
+}
+
+/// Upper bound of the swap fee share, approached as one of the balances goes to zero
+pub fn max_swap_fee_share(swap_fee_share: u128, offpeg_fee_multiplier_bp: u128) -> u128 {
+    if !is_offpeg_fee_enabled(swap_fee_share, offpeg_fee_multiplier_bp) {
+        return swap_fee_share;
+    }
+
+    swap_fee_share * offpeg_fee_multiplier_bp / BP
+}
+
+/// Swap fee share as a `(numerator, denominator)` fraction for the balances of the swapped
+/// tokens after the swap, `fee * m / ((m - 1) * 4xy / (x + y)² + 1)` with the off-peg
+/// multiplier `m`
+pub fn swap_fee_share(
+    swap_fee_share: u128,
+    offpeg_fee_multiplier_bp: u128,
+    balance: u128,
+    other_balance: u128,
+) -> (U256, U256) {
+    let fee_share = U256::new(swap_fee_share);
+    let fee_denominator = U256::new(FEE_DENOMINATOR);
+    let sum = U256::new(balance + other_balance);
+
+    if !is_offpeg_fee_enabled(swap_fee_share, offpeg_fee_multiplier_bp) || sum == U256::ZERO {
+        return (fee_share, fee_denominator);
+    }
+
+    let bp = U256::new(BP);
+    let multiplier = U256::new(offpeg_fee_multiplier_bp);
+    let sum_squared = sum * sum;
+    let xy4 = U256::new(balance) * U256::new(other_balance) * 4;
+
+    (
+        fee_share * multiplier * sum_squared,
+        fee_denominator * ((multiplier - bp) * xy4 + bp * sum_squared),
+    )
+}
+
+/// StableSwap imbalance fee of a balance that moved from `old_balance` to `new_balance` while
+/// the invariant moved from `d0` to `d1`, `n * fee / (4 * (n - 1))` of its deviation from the
+/// proportional share of `d1`
+pub fn imbalance_fee(
+    old_balance: u128,
+    new_balance: u128,
+    d0: u128,
+    d1: u128,
+    deposit_fee_share: u128,
+    tokens_count: u128,
+) -> u128 {
+    let ideal_balance = old_balance * d1 / d0;
+    let difference = ideal_balance.abs_diff(new_balance);
+
+    difference * deposit_fee_share * tokens_count / (4 * (tokens_count - 1) * FEE_DENOMINATOR)
+}
+
+/// How much less than the spot output a swap gives, in basis points
+pub fn price_impact_bp(spot_output: u128, output: u128) -> u128 {
+    if spot_output == 0 {
+        return 0;
+    }
+
+    spot_output.saturating_sub(output) * BP / spot_output
+}
+
+/// Output for `input` at the marginal price `y·p / (x·q)` of the curve, `p` and `q` are shrunk
+/// by a common factor to 128 bits so the product with the input fits, the price keeps far more
+/// precision than the output
+pub fn spot_output(input: u128, x: u128, y: u128, p: U256, q: U256) -> Result<u128, Error> {
+    let shift = (U256::BITS - p.max(q).leading_zeros()).saturating_sub(u128::BITS);
+    let (p, q) = (p >> shift, q >> shift);
+
+    let numerator = (U256::new(input) * y)
+        .checked_mul(p)
+        .ok_or(Error::U256Overflow)?;
+    let denominator = U256::new(x) * q;
+    require!(denominator > 0, Error::ZeroAmount);
+
+    safe_cast(numerator / denominator)
+}
+
+/// StableSwap invariant `D` of `balances` with the amplification coefficient `a`, solves
+/// `A·nⁿ·Σx + D = A·nⁿ·D + Dⁿ⁺¹ / (nⁿ·Πx)` by Newton's method. All the math is done in `U256`,
+/// an intermediate that does not fit fails with `Error::U256Overflow`. The invariant is not
+/// defined when only some of the balances are zero, that fails with `Error::ZeroAmount`
+pub fn get_d<I>(a: u128, balances: I) -> Result<u128, Error>
+where
+    I: IntoIterator<Item = u128>,
+    I::IntoIter: Clone,
+{
+    let balances = balances.into_iter();
+    let tokens_count = balances.clone().count() as u32;
+    let n = U256::from(tokens_count);
+    let sum = balances
+        .clone()
+        .fold(U256::ZERO, |sum, balance| sum + U256::new(balance));
+
+    if sum == U256::ZERO {
+        return Ok(0);
+    }
+    require!(
+        balances.clone().all(|balance| balance > 0),
+        Error::ZeroAmount
+    );
+
+    let ann = U256::new(a) * n.pow(tokens_count);
+    let mut d = sum;
+    let mut d_prev = U256::ZERO;
+
+    for _ in 0..MAX_NEWTON_ITERATIONS {
+        // Dⁿ⁺¹ / (nⁿ·Πx)
+        let mut d_p = d;
+        for balance in balances.clone() {
+            d_p = d_p.checked_mul(d).ok_or(Error::U256Overflow)? / (U256::new(balance) * n);
+        }
+
+        let d_before_prev = d_prev;
+        d_prev = d;
+        // (A·nⁿ·Σx + n·D_P)·D / ((A·nⁿ - 1)·D + (n + 1)·D_P)
+        d = (ann * sum + d_p * n)
+            .checked_mul(d)
+            .ok_or(Error::U256Overflow)?
+            / ((ann - 1) * d + (n + 1) * d_p);
+
+        if d.abs_diff(d_prev) <= 1 {
+            return safe_cast(d);
+        }
+        // The rounding of a very uneven pool can leave it alternating between two neighbours,
+        // the lower one mints less LP
+        if d == d_before_prev {
+            return safe_cast(d.min(d_prev));
+        }
+    }
+
+    Err(Error::NotConverged)
+}
+
+/// Balance of the token at `index` that keeps the invariant at `d` along with the other
+/// `balances`, solves `y² + (Σx' + D / A·nⁿ - D)·y = Dⁿ⁺¹ / (A·nⁿ·nⁿ·Πx')` by Newton's method,
+/// where `x'` are the balances of the other tokens. The balance at `index` itself is ignored,
+/// a zero balance among the others fails with `Error::ZeroAmount`
+pub fn get_y<I>(a: u128, balances: I, index: u32, d: u128) -> Result<u128, Error>
+where
+    I: IntoIterator<Item = u128>,
+    I::IntoIter: Clone,
+{
+    // an empty pool, the other balances are zero as well
+    if d == 0 {
+        return Ok(0);
+    }
+
+    let balances = balances.into_iter();
+    let tokens_count = balances.clone().count() as u32;
+    let n = U256::from(tokens_count);
+    let ann = U256::new(a) * n.pow(tokens_count);
+    let d = U256::new(d);
+
+    let mut sum = U256::ZERO;
+    let mut c = d;
+    for (balance_index, balance) in balances.enumerate() {
+        if balance_index as u32 == index {
+            continue;
+        }
+
+        require!(balance > 0, Error::ZeroAmount);
+        sum += balance;
+        c = c.checked_mul(d).ok_or(Error::U256Overflow)? / (U256::new(balance) * n);
+    }
+    c = c.checked_mul(d).ok_or(Error::U256Overflow)? / (ann * n);
+
+    let b = sum + d / ann;
+    let mut y = d;
+    let mut y_prev = U256::ZERO;
+
+    for _ in 0..MAX_NEWTON_ITERATIONS {
+        let y_before_prev = y_prev;
+        y_prev = y;
+        // (y² + c) / (2y + b - D)
+        y = (y * y + c) / (y * 2 + b - d);
+
+        if y.abs_diff(y_prev) <= 1 {
+            return safe_cast(y);
+        }
+        // Same as in `get_d`, the higher balance pays out less
+        if y == y_before_prev {
+            return safe_cast(y.max(y_prev));
+        }
+    }
+
+    Err(Error::NotConverged)
+}
+
+#[allow(clippy::inconsistent_digit_grouping)]
+#[cfg(test)]
+mod tests {
+    use soroban_sdk::{vec, Env};
+
+    use super::{get_d, get_y};
+    use crate::Error;
+
+    #[test]
+    fn test_get_d() {
+        let env = Env::default();
+
+        assert_eq!(get_d(20, vec![&env, 0, 0]).unwrap(), 0);
+        assert_eq!(
+            get_d(20, vec![&env, 1_000_000, 1_000_000]).unwrap(),
+            2_000_000
+        );
+        assert_eq!(
+            get_d(20, vec![&env, 2_000_000, 256_364, 5_000_000]).unwrap(),
+            7_197_880
+        );
+
+        let n = 100_000_000_000_000_000;
+        assert_eq!(
+            get_d(20, vec![&env, n, n, n, n, n, n, n, n]).unwrap(),
+            n * 8
+        );
+        assert_eq!(
+            get_d(20, vec![&env, n, n, n / 1_000]).unwrap(),
+            157_831_140_060_220_325
+        );
+    }
+
+    #[test]
+    fn test_get_d_large_balances() {
+        let env = Env::default();
+
+        let n = 2u128.pow(59);
+        assert_eq!(get_d(60, [n, n]).unwrap(), n * 2);
+        assert_eq!(get_d(60, vec![&env, n, n, n, n]).unwrap(), n * 4);
+
+        assert_eq!(get_d(20, [u128::MAX / 2, 1]), Err(Error::U256Overflow));
+    }
+
+    #[test]
+    fn test_get_d_rounding_cycle() {
+        // Newton's method alternates between two values 8 apart here
+        let balances = [
+            600_000_000_100_000_000,
+            600_000_000_100_000_000,
+            600_100_000_000,
+        ];
+        assert_eq!(get_d(20, balances).unwrap(), 234_838_801_095_467_871);
+    }
+
+    #[test]
+    fn test_zero_balance() {
+        let env = Env::default();
+
+        assert_eq!(get_d(20, [1_000_000, 0]), Err(Error::ZeroAmount));
+        assert_eq!(
+            get_d(20, vec![&env, 1_000_000, 1_000_000, 0]),
+            Err(Error::ZeroAmount)
+        );
+        assert_eq!(get_y(20, [0, 0], 1, 1_000_000), Err(Error::ZeroAmount));
+        assert_eq!(
+            get_y(20, vec![&env, 1_000_000, 0, 0], 1, 3_000_000),
+            Err(Error::ZeroAmount)
+        );
+    }
+
+    #[test]
+    fn test_get_y() {
+        let env = Env::default();
+
+        assert_eq!(get_y(20, vec![&env, 0, 0, 0], 1, 0).unwrap(), 0);
+        assert_eq!(
+            get_y(20, vec![&env, 1_000_000, 0, 1_000_000], 1, 3_000_000).unwrap(),
+            1_000_000
+        );
+
+        let n = 100_000_000_000_000_000;
+        let big_d = 157_831_140_060_220_325;
+        assert_eq!(
+            get_y(20, vec![&env, n, 0, n / 1_000], 1, big_d).unwrap(),
+            n - 2
+        );
+        assert_eq!(
+            get_y(20, vec![&env, n, n, 0], 2, big_d).unwrap(),
+            n / 1_000 - 1
+        );
+
+        // The balance at the index does not matter
+        assert_eq!(
+            get_y(20, vec![&env, n, n, n, 1], 3, n * 4).unwrap(),
+            get_y(20, vec![&env, n, n, n, n], 3, n * 4).unwrap()
+        );
+    }
+
+    #[test]
+    fn test_get_y_keeps_d() {
+        let env = Env::default();
+        let balances = vec![&env, 2_000_000, 256_364, 5_000_000, 1_300_000, 700_000];
+        let d = get_d(40, balances.clone()).unwrap();
+
+        for index in 0..balances.len() {
+            let y = get_y(40, balances.clone(), index, d).unwrap();
+            assert!(y.abs_diff(balances.get_unchecked(index)) <= 1);
+        }
+    }
+}
@@ -1,5 +1,9 @@
 #![no_std]
 
 mod admin;
+mod operation_nonce;
+mod upgrader;
 
 pub use admin::Admin;
+pub use operation_nonce::OperationNonce;
+pub use upgrader::Upgrader;
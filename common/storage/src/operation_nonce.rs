@@ -0,0 +1,18 @@
+use proc_macros::{extend_ttl_info_instance, Instance, SorobanData, SorobanSimpleData, SymbolKey};
+use shared::soroban_data::SimpleSorobanData;
+use soroban_sdk::{contracttype, Env};
+
+#[contracttype]
+#[derive(Default, SorobanData, SorobanSimpleData, SymbolKey, Instance)]
+#[extend_ttl_info_instance]
+pub struct OperationNonce(pub u64);
+
+impl OperationNonce {
+    /// Increments the per-pool operation counter and returns its new value
+    pub fn next(env: &Env) -> u64 {
+        let nonce = Self::get(env).unwrap_or_default().0 + 1;
+        OperationNonce(nonce).save(env);
+
+        nonce
+    }
+}
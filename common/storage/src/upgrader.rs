@@ -0,0 +1,23 @@
+use proc_macros::{extend_ttl_info_instance, Instance, SorobanData, SorobanSimpleData, SymbolKey};
+use shared::{soroban_data::SimpleSorobanData, Error};
+use soroban_sdk::{contracttype, Address, Env};
+
+use crate::Admin;
+
+/// Address allowed to upgrade the contract, the factory for the pools it deploys
+#[contracttype]
+#[derive(SorobanData, SorobanSimpleData, SymbolKey, Instance)]
+#[extend_ttl_info_instance]
+pub struct Upgrader(pub Address);
+
+impl Upgrader {
+    /// Contracts without an upgrader are upgraded by their admin
+    pub fn require_exist_auth(env: &Env) -> Result<(), Error> {
+        match Self::get(env) {
+            Ok(upgrader) => upgrader.0.require_auth(),
+            Err(_) => Admin::require_exist_auth(env)?,
+        }
+
+        Ok(())
+    }
+}
@@ -1,18 +1,50 @@
-use shared::{utils::extend_ttl_instance, Error};
+use shared::{utils::extend_ttl_instance, Error, Event};
 use soroban_sdk::{contract, contractimpl, Address, BytesN, Env, Map, Vec};
 use storage::Admin;
 
-use crate::methods::public::{create_three_pool, create_two_pool, get_admin, get_pool, get_pools, get_three_pool_wasm_hash, get_two_pool_wasm_hash, initialize, set_admin, update_three_pool_wasm_hash, update_two_pool_wasm_hash};
+use crate::events::Upgraded;
+use crate::methods::public::{
+    claim_pool_admin_fee, create_metapool, create_pool_permissionless, create_pool_with_liquidity,
+    create_stable_pool, create_three_pool, create_two_pool, get_admin, get_creation_fee,
+    get_metapool_wasm_hash, get_permissionless_config, get_pool, get_pool_info, get_pool_variants,
+    get_pool_wasm_hash, get_pools, get_pools_by_token, get_pools_count, get_pools_page,
+    get_stable_pool_wasm_hash, get_three_pool_wasm_hash, get_two_pool_wasm_hash, initialize,
+    is_token_allowed, is_token_allowlist_enabled, migrate, predict_pool_address, set_admin,
+    set_creation_fee, set_permissionless_config, set_pool_admin, set_pool_fee_share,
+    set_pool_status, set_token_allowed, set_token_allowlist_enabled, update_metapool_wasm_hash,
+    update_stable_pool_wasm_hash, update_three_pool_wasm_hash, update_two_pool_wasm_hash,
+    upgrade_pools, PoolFeeShare,
+};
+use crate::storage::{
+    factory_info::CreationFee,
+    permissionless_config::PermissionlessConfig,
+    pool_record::{PoolRecord, PoolStatus, PoolType},
+};
 
 #[contract]
 pub struct FactoryContract;
 
 #[contractimpl]
 impl FactoryContract {
-    pub fn initialize(env: Env, two_pool_wasm_hash: BytesN<32>, three_pool_wasm_hash: BytesN<32>, admin: Address) -> Result<(), Error> {
-        initialize(env, two_pool_wasm_hash, three_pool_wasm_hash, admin)
+    pub fn initialize(
+        env: Env,
+        two_pool_wasm_hash: BytesN<32>,
+        three_pool_wasm_hash: BytesN<32>,
+        stable_pool_wasm_hash: BytesN<32>,
+        metapool_wasm_hash: BytesN<32>,
+        admin: Address,
+    ) -> Result<(), Error> {
+        initialize(
+            env,
+            two_pool_wasm_hash,
+            three_pool_wasm_hash,
+            stable_pool_wasm_hash,
+            metapool_wasm_hash,
+            admin,
+        )
     }
 
+    /// Two or three pool of `tokens`, a token set has one pool per `tier`
     #[allow(clippy::too_many_arguments)]
     pub fn create_pool(
         env: Env,
@@ -22,6 +54,7 @@ impl FactoryContract {
         tokens: Vec<Address>,
         fee_share_bp: u128,
         admin_fee_share_bp: u128,
+        tier: u32,
     ) -> Result<Address, Error> {
         extend_ttl_instance(&env);
 
@@ -34,6 +67,7 @@ impl FactoryContract {
                 tokens,
                 fee_share_bp,
                 admin_fee_share_bp,
+                tier,
             ),
             3 => create_three_pool(
                 env,
@@ -43,10 +77,123 @@ impl FactoryContract {
                 tokens,
                 fee_share_bp,
                 admin_fee_share_bp,
+                tier,
             ),
-            _ => Err(Error::InvalidNumberOfTokens)
+            _ => Err(Error::InvalidNumberOfTokens),
         }
     }
+
+    /// Two or three pool created with the first deposit of `amounts` from the deployer,
+    /// `amounts` follow the order of `tokens`
+    #[allow(clippy::too_many_arguments)]
+    pub fn create_pool_with_liquidity(
+        env: Env,
+        deployer: Address,
+        pool_admin: Address,
+        a: u128,
+        tokens: Vec<Address>,
+        fee_share_bp: u128,
+        admin_fee_share_bp: u128,
+        tier: u32,
+        amounts: Vec<u128>,
+        min_lp_amount: u128,
+    ) -> Result<Address, Error> {
+        extend_ttl_instance(&env);
+
+        create_pool_with_liquidity(
+            env,
+            deployer,
+            pool_admin,
+            a,
+            tokens,
+            fee_share_bp,
+            admin_fee_share_bp,
+            tier,
+            amounts,
+            min_lp_amount,
+        )
+    }
+
+    /// Two or three pool anyone can create with the parameters within the configured bounds, on
+    /// one of the tiers the admin opened
+    pub fn create_pool_permissionless(
+        env: Env,
+        deployer: Address,
+        a: u128,
+        tokens: Vec<Address>,
+        fee_share_bp: u128,
+        admin_fee_share_bp: u128,
+        tier: u32,
+    ) -> Result<Address, Error> {
+        extend_ttl_instance(&env);
+
+        create_pool_permissionless(
+            env,
+            deployer,
+            a,
+            tokens,
+            fee_share_bp,
+            admin_fee_share_bp,
+            tier,
+        )
+    }
+
+    /// Generic StableSwap pool of 2 to 8 tokens, the pool takes and returns per-token amounts in
+    /// the sorted order of `tokens`
+    #[allow(clippy::too_many_arguments)]
+    pub fn create_stable_pool(
+        env: Env,
+        deployer: Address,
+        pool_admin: Address,
+        a: u128,
+        tokens: Vec<Address>,
+        fee_share_bp: u128,
+        admin_fee_share_bp: u128,
+        tier: u32,
+    ) -> Result<Address, Error> {
+        extend_ttl_instance(&env);
+
+        create_stable_pool(
+            env,
+            deployer,
+            pool_admin,
+            a,
+            tokens,
+            fee_share_bp,
+            admin_fee_share_bp,
+            tier,
+        )
+    }
+
+    /// Pairs `token` with the LP of `base_pool`, a three pool created by the factory, the pool
+    /// can also trade `token` for the base pool tokens through the base pool
+    #[allow(clippy::too_many_arguments)]
+    pub fn create_metapool(
+        env: Env,
+        deployer: Address,
+        pool_admin: Address,
+        a: u128,
+        token: Address,
+        base_pool: Address,
+        fee_share_bp: u128,
+        admin_fee_share_bp: u128,
+        tier: u32,
+    ) -> Result<Address, Error> {
+        extend_ttl_instance(&env);
+
+        create_metapool(
+            env,
+            deployer,
+            pool_admin,
+            a,
+            token,
+            base_pool,
+            fee_share_bp,
+            admin_fee_share_bp,
+            tier,
+        )
+    }
+
     // ----------- Admin -----------
 
     pub fn set_admin(env: Env, new_admin: Address) -> Result<(), Error> {
@@ -55,19 +202,133 @@ impl FactoryContract {
         set_admin(env, new_admin)
     }
 
+    /// A delisted pool is hidden from the lookups and its tokens and tier can get a new pool
+    pub fn set_pool_status(env: Env, pool: Address, status: PoolStatus) -> Result<(), Error> {
+        extend_ttl_instance(&env);
+
+        set_pool_status(env, pool, status)
+    }
+
+    /// Claims the admin fees of a pool the factory is the admin of, such as the pools created by
+    /// anyone with `PoolAdmin::Factory`, the fees go to the admin of the factory
+    pub fn claim_pool_admin_fee(env: Env, pool: Address) -> Result<(), Error> {
+        extend_ttl_instance(&env);
+
+        claim_pool_admin_fee(env, pool)
+    }
+
+    pub fn set_pool_fee_share(
+        env: Env,
+        pool: Address,
+        fee_share: PoolFeeShare,
+        value: u128,
+    ) -> Result<(), Error> {
+        extend_ttl_instance(&env);
+
+        set_pool_fee_share(env, pool, fee_share, value)
+    }
+
+    pub fn set_pool_admin(env: Env, pool: Address, new_admin: Address) -> Result<(), Error> {
+        extend_ttl_instance(&env);
+
+        set_pool_admin(env, pool, new_admin)
+    }
+
+    /// Fee the deployer pays for every new pool, the factory deploys for free
+    pub fn set_creation_fee(env: Env, fee: CreationFee) -> Result<(), Error> {
+        extend_ttl_instance(&env);
+
+        set_creation_fee(env, fee)
+    }
+
+    pub fn set_permissionless_config(env: Env, config: PermissionlessConfig) -> Result<(), Error> {
+        extend_ttl_instance(&env);
+
+        set_permissionless_config(env, config)
+    }
+
+    /// Once enabled, pools are only created from the tokens allowed by the admin
+    pub fn set_token_allowlist_enabled(env: Env, enabled: bool) -> Result<(), Error> {
+        extend_ttl_instance(&env);
+
+        set_token_allowlist_enabled(env, enabled)
+    }
+
+    pub fn set_token_allowed(env: Env, token: Address, allowed: bool) -> Result<(), Error> {
+        extend_ttl_instance(&env);
+
+        set_token_allowed(env, token, allowed)
+    }
+
     // ----------- View -----------
 
-    pub fn pool(env: Env, tokens: Vec<Address>) -> Result<Address, Error> {
+    /// Pool of the tokens in `tier`, the default tier when it is not set
+    pub fn pool(env: Env, tokens: Vec<Address>, tier: Option<u32>) -> Result<Address, Error> {
         extend_ttl_instance(&env);
-        get_pool(env, tokens)
+        get_pool(env, tokens, tier)
     }
 
+    /// Pools of the tokens in all the tiers, in the order they were created
+    pub fn pool_variants(env: Env, tokens: Vec<Address>) -> Vec<Address> {
+        extend_ttl_instance(&env);
+
+        get_pool_variants(env, tokens)
+    }
+
+    /// Deprecated, reads every pool record so it stops fitting in the budget of a call as the
+    /// registry grows, use `pools_count` with `pools_page` instead
     pub fn pools(env: Env) -> Result<Map<Address, Vec<Address>>, Error> {
         extend_ttl_instance(&env);
 
         get_pools(&env)
     }
 
+    /// Address a pool of `tokens` in `tier` gets when `deployer` creates it, the order of the
+    /// tokens does not matter
+    pub fn predict_pool_address(
+        env: Env,
+        deployer: Address,
+        tokens: Vec<Address>,
+        tier: Option<u32>,
+    ) -> Result<Address, Error> {
+        predict_pool_address(env, deployer, tokens, tier)
+    }
+
+    pub fn pools_count(env: Env) -> Result<u32, Error> {
+        extend_ttl_instance(&env);
+
+        get_pools_count(env)
+    }
+
+    /// Pools from the index `start` on, in the order they were created, a page covers at most
+    /// `MAX_PAGE_LIMIT` indices. Holds only the pools of `status` when it is set, otherwise the
+    /// ones that are not delisted, so a page can be shorter than `limit` before the end
+    pub fn pools_page(
+        env: Env,
+        start: u32,
+        limit: u32,
+        status: Option<PoolStatus>,
+    ) -> Result<Vec<Address>, Error> {
+        extend_ttl_instance(&env);
+
+        get_pools_page(env, start, limit, status)
+    }
+
+    /// Up to `limit` pools holding `token` from the position `start` of its list on, a page
+    /// covers at most `MAX_PAGE_LIMIT` positions and skips the delisted pools in them. A metapool
+    /// holds the LP of its base pool as a token
+    pub fn pools_by_token(env: Env, token: Address, start: u32, limit: u32) -> Vec<Address> {
+        extend_ttl_instance(&env);
+
+        get_pools_by_token(env, token, start, limit)
+    }
+
+    pub fn pool_info(env: Env, pool: Address) -> Result<PoolRecord, Error> {
+        extend_ttl_instance(&env);
+
+        get_pool_info(env, pool)
+    }
+
     pub fn get_two_pool_wasm_hash(env: Env) -> Result<BytesN<32>, Error> {
         get_two_pool_wasm_hash(env)
     }
@@ -75,6 +336,34 @@ impl FactoryContract {
         get_three_pool_wasm_hash(env)
     }
 
+    pub fn get_stable_pool_wasm_hash(env: Env) -> Result<BytesN<32>, Error> {
+        get_stable_pool_wasm_hash(env)
+    }
+
+    pub fn get_metapool_wasm_hash(env: Env) -> Result<BytesN<32>, Error> {
+        get_metapool_wasm_hash(env)
+    }
+
+    pub fn pool_wasm_hash(env: Env, pool: Address) -> Result<BytesN<32>, Error> {
+        get_pool_wasm_hash(env, pool)
+    }
+
+    pub fn get_permissionless_config(env: Env) -> Result<PermissionlessConfig, Error> {
+        get_permissionless_config(env)
+    }
+
+    pub fn get_creation_fee(env: Env) -> Result<CreationFee, Error> {
+        get_creation_fee(env)
+    }
+
+    pub fn is_token_allowlist_enabled(env: Env) -> bool {
+        is_token_allowlist_enabled(env)
+    }
+
+    pub fn is_token_allowed(env: Env, token: Address) -> bool {
+        is_token_allowed(env, token)
+    }
+
     pub fn get_admin(env: Env) -> Result<Address, Error> {
         get_admin(env)
     }
@@ -93,10 +382,54 @@ impl FactoryContract {
         update_three_pool_wasm_hash(env, new_wasm_hash)
     }
 
+    pub fn update_stable_pool_wasm_hash(env: Env, new_wasm_hash: BytesN<32>) -> Result<(), Error> {
+        extend_ttl_instance(&env);
+
+        update_stable_pool_wasm_hash(env, new_wasm_hash)
+    }
+
+    pub fn update_metapool_wasm_hash(env: Env, new_wasm_hash: BytesN<32>) -> Result<(), Error> {
+        extend_ttl_instance(&env);
+
+        update_metapool_wasm_hash(env, new_wasm_hash)
+    }
+
+    /// Upgrades `pools` of `pool_type` to `new_wasm_hash`, or without them the pools of the type on
+    /// the registry page from the index `start`, up to `MAX_PAGE_LIMIT` pools per call and never
+    /// a delisted one. Future pools keep the hash set by the `update_*_wasm_hash` calls. A pool
+    /// deployed before the factory became its upgrader needs its `set_upgrader` first
+    pub fn upgrade_pools(
+        env: Env,
+        pool_type: PoolType,
+        new_wasm_hash: BytesN<32>,
+        pools: Option<Vec<Address>>,
+        start: u32,
+    ) -> Result<(), Error> {
+        extend_ttl_instance(&env);
+
+        upgrade_pools(env, pool_type, new_wasm_hash, pools, start)
+    }
+
     pub fn upgrade(env: Env, new_wasm_hash: BytesN<32>) -> Result<(), Error> {
         Admin::require_exist_auth(&env)?;
 
-        env.deployer().update_current_contract_wasm(new_wasm_hash);
+        env.deployer()
+            .update_current_contract_wasm(new_wasm_hash.clone());
+
+        Upgraded { new_wasm_hash }.publish(&env);
+
         Ok(())
     }
+
+    /// Moves the pools of the previous contract version to the registry, the pool types it did
+    /// not have get the wasm hashes passed in
+    pub fn migrate(
+        env: Env,
+        stable_pool_wasm_hash: BytesN<32>,
+        metapool_wasm_hash: BytesN<32>,
+    ) -> Result<(), Error> {
+        extend_ttl_instance(&env);
+
+        migrate(env, stable_pool_wasm_hash, metapool_wasm_hash)
+    }
 }
@@ -1,11 +1,129 @@
-use soroban_sdk::{contracttype, Address};
+use soroban_sdk::{contracttype, Address, BytesN, Vec};
 
 use proc_macros::Event;
 
+use crate::storage::{
+    factory_info::CreationFee,
+    permissionless_config::PermissionlessConfig,
+    pool_record::{PoolStatus, PoolType},
+};
+
 #[derive(Event)]
 #[contracttype]
 pub struct PairCreated {
+    #[topic]
     pub token0: Address,
+    #[topic]
     pub token1: Address,
     pub pool: Address,
 }
+
+#[derive(Event)]
+#[contracttype]
+pub struct ThreePoolCreated {
+    #[topic]
+    pub token0: Address,
+    #[topic]
+    pub token1: Address,
+    #[topic]
+    pub token2: Address,
+    pub pool: Address,
+}
+
+#[derive(Event)]
+#[contracttype]
+pub struct StablePoolCreated {
+    #[topic]
+    pub tokens: Vec<Address>,
+    pub pool: Address,
+}
+
+#[derive(Event)]
+#[contracttype]
+pub struct MetapoolCreated {
+    #[topic]
+    pub token: Address,
+    #[topic]
+    pub base_pool: Address,
+    pub pool: Address,
+}
+
+#[derive(Event)]
+#[contracttype]
+pub struct TwoPoolWasmHashUpdated {
+    pub new_wasm_hash: BytesN<32>,
+}
+
+#[derive(Event)]
+#[contracttype]
+pub struct ThreePoolWasmHashUpdated {
+    pub new_wasm_hash: BytesN<32>,
+}
+
+#[derive(Event)]
+#[contracttype]
+pub struct StablePoolWasmHashUpdated {
+    pub new_wasm_hash: BytesN<32>,
+}
+
+#[derive(Event)]
+#[contracttype]
+pub struct MetapoolWasmHashUpdated {
+    pub new_wasm_hash: BytesN<32>,
+}
+
+#[derive(Event)]
+#[contracttype]
+pub struct AdminChanged {
+    #[topic]
+    pub new_admin: Address,
+}
+
+#[derive(Event)]
+#[contracttype]
+pub struct PermissionlessConfigUpdated {
+    pub config: PermissionlessConfig,
+}
+
+#[derive(Event)]
+#[contracttype]
+pub struct CreationFeeUpdated {
+    pub fee: CreationFee,
+}
+
+#[derive(Event)]
+#[contracttype]
+pub struct TokenAllowlistEnabled {
+    pub enabled: bool,
+}
+
+#[derive(Event)]
+#[contracttype]
+pub struct TokenAllowlistUpdated {
+    #[topic]
+    pub token: Address,
+    pub allowed: bool,
+}
+
+#[derive(Event)]
+#[contracttype]
+pub struct PoolsUpgraded {
+    #[topic]
+    pub pool_type: PoolType,
+    pub new_wasm_hash: BytesN<32>,
+    pub pools: Vec<Address>,
+}
+
+#[derive(Event)]
+#[contracttype]
+pub struct PoolStatusUpdated {
+    #[topic]
+    pub pool: Address,
+    pub status: PoolStatus,
+}
+
+#[derive(Event)]
+#[contracttype]
+pub struct Upgraded {
+    pub new_wasm_hash: BytesN<32>,
+}
@@ -0,0 +1,96 @@
+use shared::{require, soroban_data::SimpleSorobanData, Error, Event};
+use soroban_sdk::{vec, Address, Env, IntoVal, Symbol};
+use storage::Admin;
+
+use crate::{
+    events::MetapoolCreated,
+    storage::{
+        factory_info::FactoryInfo,
+        pool_record::{PoolRecord, PoolType},
+        token_allowlist::TokenAllowlist,
+    },
+};
+
+/// Deploys a pool of `token` against the LP of `base_pool`, a listed three pool created by the
+/// factory, the metapool is registered under `token` and `base_pool`
+#[allow(clippy::too_many_arguments)]
+pub fn create_metapool(
+    env: Env,
+    deployer: Address,
+    pool_admin: Address,
+    a: u128,
+    token: Address,
+    base_pool: Address,
+    fee_share_bp: u128,
+    admin_fee_share_bp: u128,
+    tier: u32,
+) -> Result<Address, Error> {
+    if deployer != env.current_contract_address() {
+        deployer.require_auth();
+    }
+
+    Admin::require_exist_auth(&env)?;
+
+    let mut factory_info = FactoryInfo::get(&env)?;
+
+    let base_record = PoolRecord::get_by_pool(&env, base_pool.clone())?;
+    require!(
+        base_record.pool_type == PoolType::ThreePool && !base_record.is_delisted(),
+        Error::InvalidArg
+    );
+    require!(
+        !base_record.tokens.contains(&token),
+        Error::IdenticalAddresses
+    );
+
+    let tokens = vec![&env, token.clone(), base_pool.clone()];
+    require!(
+        FactoryInfo::get_pool(&env, tokens.clone(), tier).is_err(),
+        Error::PoolExist
+    );
+    TokenAllowlist::require_valid_tokens(&env, &vec![&env, token.clone()])?;
+    factory_info.charge_creation_fee(&env, &deployer)?;
+
+    let salt = FactoryInfo::pool_salt(&env, tokens.clone(), tier)?;
+
+    let deployed_pool = env
+        .deployer()
+        .with_address(deployer.clone(), salt)
+        .deploy(factory_info.metapool_wasm_hash.clone());
+
+    factory_info.add_pool(
+        &env,
+        &PoolRecord::new(
+            &env,
+            deployed_pool.clone(),
+            FactoryInfo::sort_tokens(tokens),
+            PoolType::Metapool,
+            tier,
+            deployer,
+            factory_info.metapool_wasm_hash.clone(),
+        ),
+    );
+
+    let args = vec![
+        &env,
+        *pool_admin.as_val(),
+        a.into_val(&env),
+        *token.as_val(),
+        *base_pool.as_val(),
+        fee_share_bp.into_val(&env),
+        admin_fee_share_bp.into_val(&env),
+        env.current_contract_address().into_val(&env),
+    ];
+    env.invoke_contract::<()>(&deployed_pool, &Symbol::new(&env, "initialize"), args);
+
+    factory_info.save(&env);
+
+    MetapoolCreated {
+        token,
+        base_pool,
+        pool: deployed_pool.clone(),
+    }
+    .publish(&env);
+
+    Ok(deployed_pool)
+}
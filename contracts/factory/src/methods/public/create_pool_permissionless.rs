@@ -0,0 +1,56 @@
+use shared::{require, soroban_data::SimpleSorobanData, Error};
+use soroban_sdk::{Address, Env, Vec};
+
+use crate::{
+    methods::public::{deploy_three_pool, deploy_two_pool},
+    storage::permissionless_config::{PermissionlessConfig, PoolAdmin},
+};
+
+/// Deploys a two or three pool for anyone within the bounds set by the admin, the pool admin is
+/// the configured protocol address or the factory itself. Only the tiers the admin opened take
+/// these pools
+pub fn create_pool_permissionless(
+    env: Env,
+    deployer: Address,
+    a: u128,
+    tokens: Vec<Address>,
+    fee_share_bp: u128,
+    admin_fee_share_bp: u128,
+    tier: u32,
+) -> Result<Address, Error> {
+    deployer.require_auth();
+
+    let config = PermissionlessConfig::get(&env).map_err(|_| Error::Forbidden)?;
+    require!(config.enabled, Error::Forbidden);
+    require!(config.tiers.contains(tier), Error::Forbidden);
+    config.require_params(a, fee_share_bp, admin_fee_share_bp)?;
+
+    let pool_admin = match config.pool_admin {
+        PoolAdmin::Factory => env.current_contract_address(),
+        PoolAdmin::Protocol(pool_admin) => pool_admin,
+    };
+
+    match tokens.len() {
+        2 => deploy_two_pool(
+            env,
+            deployer,
+            pool_admin,
+            a,
+            tokens,
+            fee_share_bp,
+            admin_fee_share_bp,
+            tier,
+        ),
+        3 => deploy_three_pool(
+            env,
+            deployer,
+            pool_admin,
+            a,
+            tokens,
+            fee_share_bp,
+            admin_fee_share_bp,
+            tier,
+        ),
+        _ => Err(Error::InvalidNumberOfTokens),
+    }
+}
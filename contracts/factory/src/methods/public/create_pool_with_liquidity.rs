@@ -0,0 +1,81 @@
+use shared::{require, Error};
+use soroban_sdk::{vec, Address, Env, IntoVal, Symbol, Val, Vec};
+
+use crate::{
+    methods::public::{create_three_pool, create_two_pool},
+    storage::factory_info::FactoryInfo,
+};
+
+/// Creates a two or three pool and makes its first deposit from the deployer,
+/// `amounts` follow the order of `tokens`
+#[allow(clippy::too_many_arguments)]
+pub fn create_pool_with_liquidity(
+    env: Env,
+    deployer: Address,
+    pool_admin: Address,
+    a: u128,
+    tokens: Vec<Address>,
+    fee_share_bp: u128,
+    admin_fee_share_bp: u128,
+    tier: u32,
+    amounts: Vec<u128>,
+    min_lp_amount: u128,
+) -> Result<Address, Error> {
+    require!(amounts.len() == tokens.len(), Error::InvalidArg);
+
+    let deployed_pool = match tokens.len() {
+        2 => create_two_pool(
+            env.clone(),
+            deployer.clone(),
+            pool_admin,
+            a,
+            tokens.clone(),
+            fee_share_bp,
+            admin_fee_share_bp,
+            tier,
+        ),
+        3 => create_three_pool(
+            env.clone(),
+            deployer.clone(),
+            pool_admin,
+            a,
+            tokens.clone(),
+            fee_share_bp,
+            admin_fee_share_bp,
+            tier,
+        ),
+        _ => Err(Error::InvalidNumberOfTokens),
+    }?;
+
+    // the pool keeps its tokens sorted
+    let mut sorted_amounts = Vec::new(&env);
+    for token in FactoryInfo::sort_tokens(tokens.clone()) {
+        let index = tokens.first_index_of(token).ok_or(Error::InvalidArg)?;
+        sorted_amounts.push_back(amounts.get_unchecked(index));
+    }
+
+    let amounts: Val = if sorted_amounts.len() == 2 {
+        (
+            sorted_amounts.get_unchecked(0),
+            sorted_amounts.get_unchecked(1),
+        )
+            .into_val(&env)
+    } else {
+        (
+            sorted_amounts.get_unchecked(0),
+            sorted_amounts.get_unchecked(1),
+            sorted_amounts.get_unchecked(2),
+        )
+            .into_val(&env)
+    };
+
+    let args = vec![
+        &env,
+        *deployer.as_val(),
+        amounts,
+        min_lp_amount.into_val(&env),
+    ];
+    env.invoke_contract::<Val>(&deployed_pool, &Symbol::new(&env, "deposit"), args);
+
+    Ok(deployed_pool)
+}
@@ -0,0 +1,96 @@
+use shared::{require, soroban_data::SimpleSorobanData, Error, Event};
+use soroban_sdk::{vec, Address, Env, IntoVal, Symbol, Vec};
+use storage::Admin;
+
+use crate::{
+    events::StablePoolCreated,
+    storage::{
+        factory_info::FactoryInfo,
+        pool_record::{PoolRecord, PoolType},
+        token_allowlist::TokenAllowlist,
+    },
+};
+
+const MIN_STABLE_POOL_TOKENS: u32 = 2;
+const MAX_STABLE_POOL_TOKENS: u32 = 8;
+
+/// Deploys a generic StableSwap pool of 2 to 8 tokens, initialized with the tokens sorted
+#[allow(clippy::too_many_arguments)]
+pub fn create_stable_pool(
+    env: Env,
+    deployer: Address,
+    pool_admin: Address,
+    a: u128,
+    tokens: Vec<Address>,
+    fee_share_bp: u128,
+    admin_fee_share_bp: u128,
+    tier: u32,
+) -> Result<Address, Error> {
+    if deployer != env.current_contract_address() {
+        deployer.require_auth();
+    }
+
+    Admin::require_exist_auth(&env)?;
+
+    let mut factory_info = FactoryInfo::get(&env)?;
+
+    require!(
+        (MIN_STABLE_POOL_TOKENS..=MAX_STABLE_POOL_TOKENS).contains(&tokens.len()),
+        Error::InvalidNumberOfTokens
+    );
+
+    let sorted_tokens = FactoryInfo::sort_tokens(tokens.clone());
+    for index in 1..sorted_tokens.len() {
+        require!(
+            sorted_tokens.get_unchecked(index - 1) != sorted_tokens.get_unchecked(index),
+            Error::IdenticalAddresses
+        );
+    }
+    require!(
+        FactoryInfo::get_pool(&env, tokens.clone(), tier).is_err(),
+        Error::PoolExist
+    );
+    TokenAllowlist::require_valid_tokens(&env, &tokens)?;
+    factory_info.charge_creation_fee(&env, &deployer)?;
+
+    let salt = FactoryInfo::pool_salt(&env, tokens, tier)?;
+
+    let deployed_pool = env
+        .deployer()
+        .with_address(deployer.clone(), salt)
+        .deploy(factory_info.stable_pool_wasm_hash.clone());
+
+    factory_info.add_pool(
+        &env,
+        &PoolRecord::new(
+            &env,
+            deployed_pool.clone(),
+            sorted_tokens.clone(),
+            PoolType::StablePool,
+            tier,
+            deployer,
+            factory_info.stable_pool_wasm_hash.clone(),
+        ),
+    );
+
+    let args = vec![
+        &env,
+        *pool_admin.as_val(),
+        a.into_val(&env),
+        sorted_tokens.into_val(&env),
+        fee_share_bp.into_val(&env),
+        admin_fee_share_bp.into_val(&env),
+        env.current_contract_address().into_val(&env),
+    ];
+    env.invoke_contract::<()>(&deployed_pool, &Symbol::new(&env, "initialize"), args);
+
+    factory_info.save(&env);
+
+    StablePoolCreated {
+        tokens: sorted_tokens,
+        pool: deployed_pool.clone(),
+    }
+    .publish(&env);
+
+    Ok(deployed_pool)
+}
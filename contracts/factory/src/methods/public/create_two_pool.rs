@@ -1,8 +1,15 @@
-use shared::{require, soroban_data::SimpleSorobanData, Error};
+use shared::{require, soroban_data::SimpleSorobanData, Error, Event};
 use soroban_sdk::{vec, Address, Env, IntoVal, Symbol, Vec};
 use storage::Admin;
 
-use crate::storage::factory_info::{FactoryInfo, MAX_PAIRS_NUM};
+use crate::{
+    events::PairCreated,
+    storage::{
+        factory_info::FactoryInfo,
+        pool_record::{PoolRecord, PoolType},
+        token_allowlist::TokenAllowlist,
+    },
+};
 
 #[allow(clippy::too_many_arguments)]
 pub fn create_two_pool(
@@ -13,6 +20,7 @@ pub fn create_two_pool(
     tokens: Vec<Address>,
     fee_share_bp: u128,
     admin_fee_share_bp: u128,
+    tier: u32,
 ) -> Result<Address, Error> {
     if deployer != env.current_contract_address() {
         deployer.require_auth();
@@ -20,33 +28,63 @@ pub fn create_two_pool(
 
     Admin::require_exist_auth(&env)?;
 
+    deploy_two_pool(
+        env,
+        deployer,
+        pool_admin,
+        a,
+        tokens,
+        fee_share_bp,
+        admin_fee_share_bp,
+        tier,
+    )
+}
+
+/// Deploys and registers the pool, the caller authorizes the deployment
+#[allow(clippy::too_many_arguments)]
+pub(crate) fn deploy_two_pool(
+    env: Env,
+    deployer: Address,
+    pool_admin: Address,
+    a: u128,
+    tokens: Vec<Address>,
+    fee_share_bp: u128,
+    admin_fee_share_bp: u128,
+    tier: u32,
+) -> Result<Address, Error> {
     let mut factory_info = FactoryInfo::get(&env)?;
 
-    require!(
-        factory_info.pools.len() < MAX_PAIRS_NUM,
-        Error::MaxPoolsNumReached
-    );
-    require!(tokens.len() == 2,Error::InvalidNumberOfTokens);
+    require!(tokens.len() == 2, Error::InvalidNumberOfTokens);
     let token_a = tokens.get_unchecked(0);
     let token_b = tokens.get_unchecked(1);
     require!(token_a != token_b, Error::IdenticalAddresses);
     require!(
-        factory_info.get_pool(tokens.clone()).is_err(),
+        FactoryInfo::get_pool(&env, tokens.clone(), tier).is_err(),
         Error::PoolExist
     );
+    TokenAllowlist::require_valid_tokens(&env, &tokens)?;
+    factory_info.charge_creation_fee(&env, &deployer)?;
 
     let sorted_tokens = FactoryInfo::sort_tokens(tokens.clone());
-    let mut tokens_with_address =  tokens;
-    tokens_with_address.push_front(env.current_contract_address());
-    let bytes = FactoryInfo::merge_addresses(tokens_with_address)?;
-    let salt = env.crypto().keccak256(&bytes);
+    let salt = FactoryInfo::pool_salt(&env, tokens, tier)?;
 
     let deployed_pool = env
         .deployer()
-        .with_address(deployer, salt)
+        .with_address(deployer.clone(), salt)
         .deploy(factory_info.two_pool_wasm_hash.clone());
 
-    factory_info.add_pool(sorted_tokens.clone(), &deployed_pool);
+    factory_info.add_pool(
+        &env,
+        &PoolRecord::new(
+            &env,
+            deployed_pool.clone(),
+            sorted_tokens.clone(),
+            PoolType::TwoPool,
+            tier,
+            deployer,
+            factory_info.two_pool_wasm_hash.clone(),
+        ),
+    );
 
     let args = vec![
         &env,
@@ -56,10 +94,18 @@ pub fn create_two_pool(
         *sorted_tokens.get_unchecked(1).as_val(),
         fee_share_bp.into_val(&env),
         admin_fee_share_bp.into_val(&env),
+        env.current_contract_address().into_val(&env),
     ];
     env.invoke_contract::<()>(&deployed_pool, &Symbol::new(&env, "initialize"), args);
 
     factory_info.save(&env);
 
+    PairCreated {
+        token0: sorted_tokens.get_unchecked(0),
+        token1: sorted_tokens.get_unchecked(1),
+        pool: deployed_pool.clone(),
+    }
+    .publish(&env);
+
     Ok(deployed_pool)
 }
@@ -4,10 +4,23 @@ use storage::Admin;
 
 use crate::storage::factory_info::FactoryInfo;
 
-pub fn initialize(env: Env, two_pool_wasm_hash: BytesN<32>, three_pool_wasm_hash: BytesN<32>, admin: Address) -> Result<(), Error> {
+pub fn initialize(
+    env: Env,
+    two_pool_wasm_hash: BytesN<32>,
+    three_pool_wasm_hash: BytesN<32>,
+    stable_pool_wasm_hash: BytesN<32>,
+    metapool_wasm_hash: BytesN<32>,
+    admin: Address,
+) -> Result<(), Error> {
     require!(!FactoryInfo::has(&env), Error::Initialized);
 
-    FactoryInfo::new(&env, two_pool_wasm_hash, three_pool_wasm_hash).save(&env);
+    FactoryInfo::new(
+        two_pool_wasm_hash,
+        three_pool_wasm_hash,
+        stable_pool_wasm_hash,
+        metapool_wasm_hash,
+    )
+    .save(&env);
     Admin(admin).save(&env);
 
     Ok(())
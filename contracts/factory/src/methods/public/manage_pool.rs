@@ -0,0 +1,104 @@
+use shared::{require, soroban_data::SimpleSorobanData, Error};
+use soroban_sdk::{contracttype, token, vec, Address, Env, IntoVal, Symbol, Val, Vec};
+use storage::Admin;
+
+use crate::storage::pool_record::{PoolRecord, PoolType};
+
+/// Fee share of a pool the factory can set as its admin
+#[contracttype]
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub enum PoolFeeShare {
+    Swap,
+    Withdraw,
+    Deposit,
+    Admin,
+}
+
+impl PoolFeeShare {
+    fn setter(&self) -> &'static str {
+        match self {
+            PoolFeeShare::Swap => "set_swap_fee_share",
+            PoolFeeShare::Withdraw => "set_withdraw_fee_share",
+            PoolFeeShare::Deposit => "set_deposit_fee_share",
+            PoolFeeShare::Admin => "set_admin_fee_share",
+        }
+    }
+}
+
+/// Claims the admin fees of a pool the factory is the admin of and sends them on to the admin
+/// of the factory
+pub fn claim_pool_admin_fee(env: Env, pool: Address) -> Result<(), Error> {
+    let admin = Admin::get(&env)?;
+    admin.require_auth();
+
+    let record = get_managed_pool(&env, &pool)?;
+    // The LP of the base pool a metapool holds is not a token the factory can send on
+    require!(record.pool_type != PoolType::Metapool, Error::InvalidArg);
+
+    let current_contract = env.current_contract_address();
+    let mut balances = Vec::new(&env);
+    for token in record.tokens.iter() {
+        balances.push_back(token::Client::new(&env, &token).balance(&current_contract));
+    }
+
+    invoke_pool(&env, &pool, "claim_admin_fee", Vec::new(&env));
+
+    for (token, balance) in record.tokens.iter().zip(balances.iter()) {
+        let token = token::Client::new(&env, &token);
+        let fee = token.balance(&current_contract) - balance;
+        if fee > 0 {
+            token.transfer(&current_contract, admin.as_ref(), &fee);
+        }
+    }
+
+    Ok(())
+}
+
+pub fn set_pool_fee_share(
+    env: Env,
+    pool: Address,
+    fee_share: PoolFeeShare,
+    value: u128,
+) -> Result<(), Error> {
+    Admin::require_exist_auth(&env)?;
+
+    get_managed_pool(&env, &pool)?;
+    invoke_pool(
+        &env,
+        &pool,
+        fee_share.setter(),
+        vec![&env, value.into_val(&env)],
+    );
+
+    Ok(())
+}
+
+/// Hands a pool the factory is the admin of to `new_admin`
+pub fn set_pool_admin(env: Env, pool: Address, new_admin: Address) -> Result<(), Error> {
+    Admin::require_exist_auth(&env)?;
+
+    get_managed_pool(&env, &pool)?;
+    invoke_pool(
+        &env,
+        &pool,
+        "set_admin",
+        vec![&env, new_admin.into_val(&env)],
+    );
+
+    Ok(())
+}
+
+fn get_managed_pool(env: &Env, pool: &Address) -> Result<PoolRecord, Error> {
+    let record = PoolRecord::get_by_pool(env, pool.clone())?;
+    let pool_admin: Address = env.invoke_contract(pool, &Symbol::new(env, "get_admin"), vec![env]);
+    require!(
+        pool_admin == env.current_contract_address(),
+        Error::Forbidden
+    );
+
+    Ok(record)
+}
+
+fn invoke_pool(env: &Env, pool: &Address, function: &str, args: Vec<Val>) {
+    env.invoke_contract::<()>(pool, &Symbol::new(env, function), args);
+}
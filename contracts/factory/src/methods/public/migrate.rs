@@ -0,0 +1,52 @@
+use shared::{soroban_data::SimpleSorobanData, Error};
+use soroban_sdk::{BytesN, Env};
+use storage::Admin;
+
+use crate::storage::{
+    factory_info::{FactoryInfo, DEFAULT_TIER},
+    legacy_factory_info::LegacyFactoryInfo,
+    pool_record::{PoolRecord, PoolType},
+};
+
+/// Converts the factory info stored by the previous contract version and registers its pools in
+/// the default tier, to be called right after `upgrade`
+pub fn migrate(
+    env: Env,
+    stable_pool_wasm_hash: BytesN<32>,
+    metapool_wasm_hash: BytesN<32>,
+) -> Result<(), Error> {
+    Admin::require_exist_auth(&env)?;
+
+    let legacy_info = LegacyFactoryInfo::get(&env)?;
+    let mut factory_info = FactoryInfo::new(
+        legacy_info.two_pool_wasm_hash.clone(),
+        legacy_info.three_pool_wasm_hash.clone(),
+        stable_pool_wasm_hash,
+        metapool_wasm_hash,
+    );
+
+    for (tokens, pool) in legacy_info.pools.iter() {
+        let (pool_type, wasm_hash) = match tokens.len() {
+            2 => (PoolType::TwoPool, legacy_info.two_pool_wasm_hash.clone()),
+            3 => (
+                PoolType::ThreePool,
+                legacy_info.three_pool_wasm_hash.clone(),
+            ),
+            _ => return Err(Error::InvalidNumberOfTokens),
+        };
+        let record = PoolRecord::new(
+            &env,
+            pool,
+            FactoryInfo::sort_tokens(tokens),
+            pool_type,
+            DEFAULT_TIER,
+            env.current_contract_address(),
+            wasm_hash,
+        );
+        factory_info.add_pool(&env, &record);
+    }
+
+    factory_info.save(&env);
+
+    Ok(())
+}
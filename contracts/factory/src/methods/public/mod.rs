@@ -1,15 +1,45 @@
-mod create_two_pool;
+mod create_metapool;
+mod create_pool_permissionless;
+mod create_pool_with_liquidity;
+mod create_stable_pool;
 mod create_three_pool;
+mod create_two_pool;
 mod initialize;
+mod manage_pool;
+mod migrate;
 mod set_admin;
-mod update_two_pool_wasm_hash;
+mod set_creation_fee;
+mod set_permissionless_config;
+mod set_pool_status;
+mod set_token_allowed;
+mod set_token_allowlist_enabled;
+mod update_metapool_wasm_hash;
+mod update_stable_pool_wasm_hash;
 mod update_three_pool_wasm_hash;
+mod update_two_pool_wasm_hash;
+mod upgrade_pools;
 mod view;
 
-pub use create_two_pool::create_two_pool;
+pub use create_metapool::create_metapool;
+pub use create_pool_permissionless::create_pool_permissionless;
+pub use create_pool_with_liquidity::create_pool_with_liquidity;
+pub use create_stable_pool::create_stable_pool;
 pub use create_three_pool::create_three_pool;
+pub(crate) use create_three_pool::deploy_three_pool;
+pub use create_two_pool::create_two_pool;
+pub(crate) use create_two_pool::deploy_two_pool;
 pub use initialize::initialize;
+pub use manage_pool::{claim_pool_admin_fee, set_pool_admin, set_pool_fee_share, PoolFeeShare};
+pub use migrate::migrate;
 pub use set_admin::set_admin;
-pub use update_two_pool_wasm_hash::update_two_pool_wasm_hash;
+pub use set_creation_fee::set_creation_fee;
+pub use set_permissionless_config::set_permissionless_config;
+pub use set_pool_status::set_pool_status;
+pub use set_token_allowed::set_token_allowed;
+pub use set_token_allowlist_enabled::set_token_allowlist_enabled;
+pub use update_metapool_wasm_hash::update_metapool_wasm_hash;
+pub use update_stable_pool_wasm_hash::update_stable_pool_wasm_hash;
 pub use update_three_pool_wasm_hash::update_three_pool_wasm_hash;
+pub use update_two_pool_wasm_hash::update_two_pool_wasm_hash;
+pub use upgrade_pools::upgrade_pools;
 pub use view::*;
@@ -1,12 +1,16 @@
 use soroban_sdk::{Address, Env};
 
 use shared::soroban_data::SimpleSorobanData;
-use shared::Error;
+use shared::{Error, Event};
 use storage::Admin;
 
+use crate::events::AdminChanged;
+
 pub fn set_admin(env: Env, new_admin: Address) -> Result<(), Error> {
     Admin::require_exist_auth(&env)?;
-    Admin(new_admin).save(&env);
+    Admin(new_admin.clone()).save(&env);
+
+    AdminChanged { new_admin }.publish(&env);
 
     Ok(())
 }
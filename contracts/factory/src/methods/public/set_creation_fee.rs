@@ -0,0 +1,23 @@
+use shared::{soroban_data::SimpleSorobanData, Error, Event};
+use soroban_sdk::Env;
+use storage::Admin;
+
+use crate::{
+    events::CreationFeeUpdated,
+    storage::factory_info::{CreationFee, FactoryInfo},
+};
+
+pub fn set_creation_fee(env: Env, fee: CreationFee) -> Result<(), Error> {
+    Admin::require_exist_auth(&env)?;
+    fee.require_valid()?;
+
+    FactoryInfo::update(&env, |info| {
+        info.creation_fee = fee.clone();
+
+        Ok(())
+    })?;
+
+    CreationFeeUpdated { fee }.publish(&env);
+
+    Ok(())
+}
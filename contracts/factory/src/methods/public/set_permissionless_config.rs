@@ -0,0 +1,18 @@
+use shared::{soroban_data::SimpleSorobanData, Error, Event};
+use soroban_sdk::Env;
+use storage::Admin;
+
+use crate::{
+    events::PermissionlessConfigUpdated, storage::permissionless_config::PermissionlessConfig,
+};
+
+pub fn set_permissionless_config(env: Env, config: PermissionlessConfig) -> Result<(), Error> {
+    Admin::require_exist_auth(&env)?;
+    config.require_valid()?;
+
+    config.save(&env);
+
+    PermissionlessConfigUpdated { config }.publish(&env);
+
+    Ok(())
+}
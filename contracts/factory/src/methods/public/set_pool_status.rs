@@ -0,0 +1,20 @@
+use shared::{Error, Event};
+use soroban_sdk::{Address, Env};
+use storage::Admin;
+
+use crate::{
+    events::PoolStatusUpdated,
+    storage::pool_record::{PoolRecord, PoolStatus},
+};
+
+pub fn set_pool_status(env: Env, pool: Address, status: PoolStatus) -> Result<(), Error> {
+    Admin::require_exist_auth(&env)?;
+
+    let index = PoolRecord::get_index(&env, pool.clone())?;
+    let mut record = PoolRecord::get(&env, index)?;
+    record.set_status(&env, index, status)?;
+
+    PoolStatusUpdated { pool, status }.publish(&env);
+
+    Ok(())
+}
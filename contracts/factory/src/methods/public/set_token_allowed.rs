@@ -0,0 +1,15 @@
+use shared::{Error, Event};
+use soroban_sdk::{Address, Env};
+use storage::Admin;
+
+use crate::{events::TokenAllowlistUpdated, storage::token_allowlist::TokenAllowlist};
+
+pub fn set_token_allowed(env: Env, token: Address, allowed: bool) -> Result<(), Error> {
+    Admin::require_exist_auth(&env)?;
+
+    TokenAllowlist::set_allowed(&env, token.clone(), allowed);
+
+    TokenAllowlistUpdated { token, allowed }.publish(&env);
+
+    Ok(())
+}
@@ -0,0 +1,15 @@
+use shared::{soroban_data::SimpleSorobanData, Error, Event};
+use soroban_sdk::Env;
+use storage::Admin;
+
+use crate::{events::TokenAllowlistEnabled, storage::token_allowlist::TokenAllowlist};
+
+pub fn set_token_allowlist_enabled(env: Env, enabled: bool) -> Result<(), Error> {
+    Admin::require_exist_auth(&env)?;
+
+    TokenAllowlist { enabled }.save(&env);
+
+    TokenAllowlistEnabled { enabled }.publish(&env);
+
+    Ok(())
+}
@@ -0,0 +1,19 @@
+use shared::{soroban_data::SimpleSorobanData, Error, Event};
+use soroban_sdk::{BytesN, Env};
+use storage::Admin;
+
+use crate::{events::MetapoolWasmHashUpdated, storage::factory_info::FactoryInfo};
+
+pub fn update_metapool_wasm_hash(env: Env, new_wasm_hash: BytesN<32>) -> Result<(), Error> {
+    Admin::require_exist_auth(&env)?;
+
+    FactoryInfo::update(&env, |info| {
+        info.metapool_wasm_hash = new_wasm_hash.clone();
+
+        Ok(())
+    })?;
+
+    MetapoolWasmHashUpdated { new_wasm_hash }.publish(&env);
+
+    Ok(())
+}
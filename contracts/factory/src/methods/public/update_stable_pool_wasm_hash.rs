@@ -0,0 +1,19 @@
+use shared::{soroban_data::SimpleSorobanData, Error, Event};
+use soroban_sdk::{BytesN, Env};
+use storage::Admin;
+
+use crate::{events::StablePoolWasmHashUpdated, storage::factory_info::FactoryInfo};
+
+pub fn update_stable_pool_wasm_hash(env: Env, new_wasm_hash: BytesN<32>) -> Result<(), Error> {
+    Admin::require_exist_auth(&env)?;
+
+    FactoryInfo::update(&env, |info| {
+        info.stable_pool_wasm_hash = new_wasm_hash.clone();
+
+        Ok(())
+    })?;
+
+    StablePoolWasmHashUpdated { new_wasm_hash }.publish(&env);
+
+    Ok(())
+}
@@ -1,15 +1,19 @@
-use shared::{soroban_data::SimpleSorobanData, Error};
+use shared::{soroban_data::SimpleSorobanData, Error, Event};
 use soroban_sdk::{BytesN, Env};
 use storage::Admin;
 
-use crate::storage::factory_info::FactoryInfo;
+use crate::{events::TwoPoolWasmHashUpdated, storage::factory_info::FactoryInfo};
 
 pub fn update_two_pool_wasm_hash(env: Env, new_wasm_hash: BytesN<32>) -> Result<(), Error> {
     Admin::require_exist_auth(&env)?;
 
     FactoryInfo::update(&env, |info| {
-        info.two_pool_wasm_hash = new_wasm_hash;
+        info.two_pool_wasm_hash = new_wasm_hash.clone();
 
         Ok(())
-    })
+    })?;
+
+    TwoPoolWasmHashUpdated { new_wasm_hash }.publish(&env);
+
+    Ok(())
 }
@@ -0,0 +1,79 @@
+use shared::{require, soroban_data::SimpleSorobanData, Error, Event};
+use soroban_sdk::{vec, Address, BytesN, Env, IntoVal, Symbol, Vec};
+use storage::Admin;
+
+use crate::{
+    events::PoolsUpgraded,
+    storage::{
+        factory_info::{FactoryInfo, MAX_PAGE_LIMIT},
+        pool_record::{PoolRecord, PoolType},
+    },
+};
+
+/// Upgrades `pools`, or the pools of `pool_type` on the registry page from the index `start`
+/// when not set, at most `MAX_PAGE_LIMIT` pools per call. Delisted pools are left out of a page
+/// and rejected in `pools`, the pools trust the factory as their upgrader
+pub fn upgrade_pools(
+    env: Env,
+    pool_type: PoolType,
+    new_wasm_hash: BytesN<32>,
+    pools: Option<Vec<Address>>,
+    start: u32,
+) -> Result<(), Error> {
+    Admin::require_exist_auth(&env)?;
+
+    let mut upgraded_pools = Vec::new(&env);
+
+    match pools {
+        Some(pools) => {
+            require!(pools.len() <= MAX_PAGE_LIMIT, Error::InvalidArg);
+
+            for pool in pools.iter() {
+                let index = PoolRecord::get_index(&env, pool)?;
+                let record = PoolRecord::get(&env, index)?;
+                require!(
+                    record.pool_type == pool_type && !record.is_delisted(),
+                    Error::InvalidArg
+                );
+
+                upgraded_pools.push_back(upgrade_pool(&env, index, record, &new_wasm_hash));
+            }
+        }
+        None => {
+            let end = start
+                .saturating_add(MAX_PAGE_LIMIT)
+                .min(FactoryInfo::get(&env)?.pools_count);
+
+            for index in start..end {
+                let record = PoolRecord::get(&env, index)?;
+                if record.pool_type == pool_type && !record.is_delisted() {
+                    upgraded_pools.push_back(upgrade_pool(&env, index, record, &new_wasm_hash));
+                }
+            }
+        }
+    }
+
+    PoolsUpgraded {
+        pool_type,
+        new_wasm_hash,
+        pools: upgraded_pools,
+    }
+    .publish(&env);
+
+    Ok(())
+}
+
+fn upgrade_pool(
+    env: &Env,
+    index: u32,
+    mut record: PoolRecord,
+    new_wasm_hash: &BytesN<32>,
+) -> Address {
+    let args = vec![env, new_wasm_hash.into_val(env)];
+    env.invoke_contract::<()>(&record.pool, &Symbol::new(env, "upgrade"), args);
+
+    record.wasm_hash = new_wasm_hash.clone();
+    record.save(env, index);
+
+    record.pool
+}
@@ -2,14 +2,58 @@ use shared::{soroban_data::SimpleSorobanData, Error};
 use soroban_sdk::{Address, BytesN, Env, Map, Vec};
 use storage::Admin;
 
-use crate::storage::factory_info::FactoryInfo;
+use crate::storage::{
+    factory_info::{CreationFee, FactoryInfo, DEFAULT_TIER},
+    permissionless_config::PermissionlessConfig,
+    pool_record::{PoolRecord, PoolStatus},
+    token_allowlist::TokenAllowlist,
+};
 
-pub fn get_pool(env: Env, tokens: Vec<Address>) -> Result<Address, Error> {
-    FactoryInfo::get(&env)?.get_pool(tokens)
+pub fn get_pool(env: Env, tokens: Vec<Address>, tier: Option<u32>) -> Result<Address, Error> {
+    FactoryInfo::get_pool(&env, tokens, tier.unwrap_or(DEFAULT_TIER))
+}
+
+pub fn get_pool_variants(env: Env, tokens: Vec<Address>) -> Vec<Address> {
+    FactoryInfo::get_pool_variants(&env, tokens)
 }
 
 pub fn get_pools(env: &Env) -> Result<Map<Address, Vec<Address>>, Error> {
-    FactoryInfo::get(env)?.get_pools()
+    FactoryInfo::get(env)?.get_pools(env)
+}
+
+pub fn predict_pool_address(
+    env: Env,
+    deployer: Address,
+    tokens: Vec<Address>,
+    tier: Option<u32>,
+) -> Result<Address, Error> {
+    let salt = FactoryInfo::pool_salt(&env, tokens, tier.unwrap_or(DEFAULT_TIER))?;
+
+    Ok(env
+        .deployer()
+        .with_address(deployer, salt)
+        .deployed_address())
+}
+
+pub fn get_pools_count(env: Env) -> Result<u32, Error> {
+    Ok(FactoryInfo::get(&env)?.pools_count)
+}
+
+pub fn get_pools_page(
+    env: Env,
+    start: u32,
+    limit: u32,
+    status: Option<PoolStatus>,
+) -> Result<Vec<Address>, Error> {
+    FactoryInfo::get(&env)?.get_pools_page(&env, start, limit, status)
+}
+
+pub fn get_pools_by_token(env: Env, token: Address, start: u32, limit: u32) -> Vec<Address> {
+    PoolRecord::get_pools_by_token(&env, token, start, limit)
+}
+
+pub fn get_pool_info(env: Env, pool: Address) -> Result<PoolRecord, Error> {
+    PoolRecord::get_by_pool(&env, pool)
 }
 
 pub fn get_two_pool_wasm_hash(env: Env) -> Result<BytesN<32>, Error> {
@@ -20,6 +64,34 @@ pub fn get_three_pool_wasm_hash(env: Env) -> Result<BytesN<32>, Error> {
     Ok(FactoryInfo::get(&env)?.three_pool_wasm_hash)
 }
 
+pub fn get_stable_pool_wasm_hash(env: Env) -> Result<BytesN<32>, Error> {
+    Ok(FactoryInfo::get(&env)?.stable_pool_wasm_hash)
+}
+
+pub fn get_metapool_wasm_hash(env: Env) -> Result<BytesN<32>, Error> {
+    Ok(FactoryInfo::get(&env)?.metapool_wasm_hash)
+}
+
+pub fn get_pool_wasm_hash(env: Env, pool: Address) -> Result<BytesN<32>, Error> {
+    Ok(PoolRecord::get_by_pool(&env, pool)?.wasm_hash)
+}
+
+pub fn get_permissionless_config(env: Env) -> Result<PermissionlessConfig, Error> {
+    PermissionlessConfig::get(&env)
+}
+
+pub fn get_creation_fee(env: Env) -> Result<CreationFee, Error> {
+    Ok(FactoryInfo::get(&env)?.creation_fee)
+}
+
+pub fn is_token_allowlist_enabled(env: Env) -> bool {
+    TokenAllowlist::is_enabled(&env)
+}
+
+pub fn is_token_allowed(env: Env, token: Address) -> bool {
+    TokenAllowlist::is_allowed(&env, token)
+}
+
 pub fn get_admin(env: Env) -> Result<Address, Error> {
     Ok(Admin::get(&env)?.0)
 }
@@ -1,11 +1,43 @@
 use proc_macros::{extend_ttl_info_instance, Instance, SorobanData, SorobanSimpleData, SymbolKey};
 use shared::{
-    utils::{bytes::address_to_bytes},
+    require,
+    utils::{bytes::address_to_bytes, safe_cast},
     Error,
 };
-use soroban_sdk::{contracttype, Address, Bytes, BytesN, Env, Map, Vec};
+use soroban_sdk::{contracttype, token, Address, Bytes, BytesN, Env, Map, Vec};
 
-pub const MAX_PAIRS_NUM: u32 = 21;
+use super::pool_record::{PoolRecord, PoolStatus};
+
+/// Most pools returned by one page
+pub const MAX_PAGE_LIMIT: u32 = 30;
+/// Tier of the pools created before tiers, a token set has one pool per tier
+pub const DEFAULT_TIER: u32 = 0;
+
+/// Fee the deployer of a pool pays to the treasury
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct CreationFeeInfo {
+    pub token: Address,
+    pub amount: u128,
+    pub treasury: Address,
+}
+
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub enum CreationFee {
+    Free,
+    Charged(CreationFeeInfo),
+}
+
+impl CreationFee {
+    pub fn require_valid(&self) -> Result<(), Error> {
+        if let CreationFee::Charged(fee) = self {
+            require!(fee.amount > 0, Error::InvalidArg);
+        }
+
+        Ok(())
+    }
+}
 
 #[contracttype]
 #[derive(SorobanData, SorobanSimpleData, SymbolKey, Instance)]
@@ -13,18 +45,45 @@ pub const MAX_PAIRS_NUM: u32 = 21;
 pub struct FactoryInfo {
     pub two_pool_wasm_hash: soroban_sdk::BytesN<32>,
     pub three_pool_wasm_hash: soroban_sdk::BytesN<32>,
-    pub pools: Map<Vec<Address>, Address>,
+    pub stable_pool_wasm_hash: soroban_sdk::BytesN<32>,
+    pub metapool_wasm_hash: soroban_sdk::BytesN<32>,
+    /// Number of registered pools, each one is a `PoolRecord` under its index below it
+    pub pools_count: u32,
+    pub creation_fee: CreationFee,
 }
 
 impl FactoryInfo {
-    pub fn new(env: &Env, two_pool_wasm_hash: BytesN<32>, three_pool_wasm_hash: BytesN<32>) -> Self {
+    pub fn new(
+        two_pool_wasm_hash: BytesN<32>,
+        three_pool_wasm_hash: BytesN<32>,
+        stable_pool_wasm_hash: BytesN<32>,
+        metapool_wasm_hash: BytesN<32>,
+    ) -> Self {
         FactoryInfo {
             two_pool_wasm_hash,
             three_pool_wasm_hash,
-            pools: Map::new(env),
+            stable_pool_wasm_hash,
+            metapool_wasm_hash,
+            pools_count: 0,
+            creation_fee: CreationFee::Free,
         }
     }
 
+    /// Transfers the creation fee from the deployer to the treasury, pools the factory deploys
+    /// itself are free
+    pub fn charge_creation_fee(&self, env: &Env, deployer: &Address) -> Result<(), Error> {
+        if let CreationFee::Charged(fee) = &self.creation_fee {
+            if *deployer != env.current_contract_address() {
+                token::Client::new(env, &fee.token).transfer(
+                    deployer,
+                    &fee.treasury,
+                    &safe_cast(fee.amount)?,
+                );
+            }
+        }
+
+        Ok(())
+    }
 
     pub fn sort_tokens(mut v: Vec<Address>) -> Vec<Address> {
         for i in 0..v.len() {
@@ -53,65 +112,194 @@ impl FactoryInfo {
         Ok(result)
     }
 
-    pub fn add_pool(&mut self, tokens: Vec<Address>, pool: &Address) {
-        self.pools.set(tokens, pool.clone());
+    /// Deployment salt of a pool, taken over the sorted tokens so that it does not depend on the
+    /// order they are passed in, the default tier leaves the tier out. Once the pool of the tokens
+    /// and tier is delisted the salt also takes the number of delisted pools
+    pub fn pool_salt(env: &Env, tokens: Vec<Address>, tier: u32) -> Result<BytesN<32>, Error> {
+        let sorted_tokens = FactoryInfo::sort_tokens(tokens);
+        let generation = PoolRecord::get_generation(env, sorted_tokens.clone(), tier);
+        let mut tokens_with_address = sorted_tokens;
+        tokens_with_address.push_front(env.current_contract_address());
+        let mut bytes = FactoryInfo::merge_addresses(tokens_with_address)?;
+        if tier != DEFAULT_TIER || generation > 0 {
+            bytes.extend_from_array(&tier.to_be_bytes());
+        }
+        if generation > 0 {
+            bytes.extend_from_array(&generation.to_be_bytes());
+        }
+
+        Ok(env.crypto().keccak256(&bytes))
+    }
+
+    pub fn add_pool(&mut self, env: &Env, record: &PoolRecord) {
+        record.register(env, self.pools_count);
+        self.pools_count += 1;
     }
 
-    pub fn get_pools(&self) -> Result<Map<Address, Vec<Address>>, Error> {
-        let mut map = Map::new(self.pools.env());
+    /// Listed pools with their tokens, kept for the callers of the legacy `pools` view
+    pub fn get_pools(&self, env: &Env) -> Result<Map<Address, Vec<Address>>, Error> {
+        let mut map = Map::new(env);
 
-        self.pools.iter().for_each(|(tokens, pool)| {
-            map.set(pool, tokens);
-        });
+        for index in 0..self.pools_count {
+            let record = PoolRecord::get(env, index)?;
+            if !record.is_delisted() {
+                map.set(record.pool, record.tokens);
+            }
+        }
 
         Ok(map)
     }
 
-    pub fn get_pool(&self, mut tokens: Vec<Address>) -> Result<Address, Error> {
-        tokens = FactoryInfo::sort_tokens(tokens);
+    /// Pools from the index `start` on, in the order they were created, the page covers at most
+    /// `MAX_PAGE_LIMIT` indices and holds the pools of `status` in them, the listed ones without it
+    pub fn get_pools_page(
+        &self,
+        env: &Env,
+        start: u32,
+        limit: u32,
+        status: Option<PoolStatus>,
+    ) -> Result<Vec<Address>, Error> {
+        let end = start
+            .saturating_add(limit.min(MAX_PAGE_LIMIT))
+            .min(self.pools_count);
+        let mut pools = Vec::new(env);
+
+        for index in start..end {
+            let record = PoolRecord::get(env, index)?;
+            let matches = match status {
+                Some(status) => record.status == status,
+                None => !record.is_delisted(),
+            };
+
+            if matches {
+                pools.push_back(record.pool);
+            }
+        }
+
+        Ok(pools)
+    }
+
+    pub fn get_pool(env: &Env, tokens: Vec<Address>, tier: u32) -> Result<Address, Error> {
+        Ok(PoolRecord::get_by_tokens(env, FactoryInfo::sort_tokens(tokens), tier)?.pool)
+    }
 
-        self.pools.get(tokens).ok_or(Error::NotFound)
+    /// Pools of all the tiers of a token set, in the order they were created
+    pub fn get_pool_variants(env: &Env, tokens: Vec<Address>) -> Vec<Address> {
+        PoolRecord::get_pools_by_tokens(env, FactoryInfo::sort_tokens(tokens))
     }
 }
 
 #[cfg(test)]
 mod tests {
-    use soroban_sdk::{vec, Address, Bytes, Env, String};
     use crate::storage::factory_info::FactoryInfo;
+    use soroban_sdk::{vec, Address, Bytes, Env, String};
 
     #[test]
     fn test_merge_addresses() {
         let env = Env::default();
 
-        let address_a = Address::from_string(&String::from_str(&env, "GAE73XQO7ONPTIJAF2S5RBCWSG2G7HWSREOP4UDXLHWBZEDBUIIQZ3Y7"));
-        let address_b = Address::from_string(&String::from_str(&env, "GCBJR4SJIVIRMVAOWFMSGAOCLDU6TVEIITJOO4NVAZ6RI3FC32E5RWP2"));
-        let address_c = Address::from_string(&String::from_str(&env, "GACWN434MDHQPLIUW6SPRDWTQ7BER5BTQWJGL2GDQ54IZYJHJQHODRTZ"));
+        let address_a = Address::from_string(&String::from_str(
+            &env,
+            "GAE73XQO7ONPTIJAF2S5RBCWSG2G7HWSREOP4UDXLHWBZEDBUIIQZ3Y7",
+        ));
+        let address_b = Address::from_string(&String::from_str(
+            &env,
+            "GCBJR4SJIVIRMVAOWFMSGAOCLDU6TVEIITJOO4NVAZ6RI3FC32E5RWP2",
+        ));
+        let address_c = Address::from_string(&String::from_str(
+            &env,
+            "GACWN434MDHQPLIUW6SPRDWTQ7BER5BTQWJGL2GDQ54IZYJHJQHODRTZ",
+        ));
 
-        let result = FactoryInfo::merge_addresses(vec![&env, address_a, address_b, address_c]).unwrap();
-        let expected = Bytes::from_slice(&env, &[9, 253, 222, 14, 251, 154, 249, 161, 32, 46, 165, 216, 132, 86, 145, 180, 111, 158, 210, 137, 28, 254, 80, 119, 89, 236, 28, 144, 97, 162, 17, 12,
-                                                              130, 152, 242, 73, 69, 81, 22, 84, 14, 177, 89, 35, 1, 194, 88, 233, 233, 212, 136, 68, 210, 231, 113, 181, 6, 125, 20, 108, 162, 222, 137, 216,
-                                                              5, 102, 243, 124, 96, 207, 7, 173, 20, 183, 164, 248, 142, 211, 135, 194, 72, 244, 51, 133, 146, 101, 232, 195, 135, 120, 140, 225, 39, 76, 14, 225]);
+        let result =
+            FactoryInfo::merge_addresses(vec![&env, address_a, address_b, address_c]).unwrap();
+        let expected = Bytes::from_slice(
+            &env,
+            &[
+                9, 253, 222, 14, 251, 154, 249, 161, 32, 46, 165, 216, 132, 86, 145, 180, 111, 158,
+                210, 137, 28, 254, 80, 119, 89, 236, 28, 144, 97, 162, 17, 12, 130, 152, 242, 73,
+                69, 81, 22, 84, 14, 177, 89, 35, 1, 194, 88, 233, 233, 212, 136, 68, 210, 231, 113,
+                181, 6, 125, 20, 108, 162, 222, 137, 216, 5, 102, 243, 124, 96, 207, 7, 173, 20,
+                183, 164, 248, 142, 211, 135, 194, 72, 244, 51, 133, 146, 101, 232, 195, 135, 120,
+                140, 225, 39, 76, 14, 225,
+            ],
+        );
 
         assert_eq!(result, expected);
-
     }
 
     #[test]
     fn test_sort_tokens() {
         let env = Env::default();
 
-        let address_a = Address::from_string(&String::from_str(&env, "GAE73XQO7ONPTIJAF2S5RBCWSG2G7HWSREOP4UDXLHWBZEDBUIIQZ3Y7"));
-        let address_b = Address::from_string(&String::from_str(&env, "GCBJR4SJIVIRMVAOWFMSGAOCLDU6TVEIITJOO4NVAZ6RI3FC32E5RWP2"));
-        let address_c = Address::from_string(&String::from_str(&env, "GACWN434MDHQPLIUW6SPRDWTQ7BER5BTQWJGL2GDQ54IZYJHJQHODRTZ"));
+        let address_a = Address::from_string(&String::from_str(
+            &env,
+            "GAE73XQO7ONPTIJAF2S5RBCWSG2G7HWSREOP4UDXLHWBZEDBUIIQZ3Y7",
+        ));
+        let address_b = Address::from_string(&String::from_str(
+            &env,
+            "GCBJR4SJIVIRMVAOWFMSGAOCLDU6TVEIITJOO4NVAZ6RI3FC32E5RWP2",
+        ));
+        let address_c = Address::from_string(&String::from_str(
+            &env,
+            "GACWN434MDHQPLIUW6SPRDWTQ7BER5BTQWJGL2GDQ54IZYJHJQHODRTZ",
+        ));
 
-        let expected = vec![&env, address_c.clone(), address_a.clone(), address_b.clone()];
-
-        assert_eq!(FactoryInfo::sort_tokens(vec![&env, address_a.clone(), address_b.clone(), address_c.clone()]), expected);
-        assert_eq!(FactoryInfo::sort_tokens(vec![&env, address_a.clone(), address_c.clone(), address_b.clone()]), expected);
-        assert_eq!(FactoryInfo::sort_tokens(vec![&env, address_b.clone(), address_a.clone(), address_c.clone()]), expected);
-        assert_eq!(FactoryInfo::sort_tokens(vec![&env, address_b.clone(), address_c.clone(), address_a.clone()]), expected);
-        assert_eq!(FactoryInfo::sort_tokens(vec![&env, address_c.clone(), address_a.clone(), address_b.clone()]), expected);
-        assert_eq!(FactoryInfo::sort_tokens(vec![&env, address_c, address_b, address_a]), expected);
+        let expected = vec![
+            &env,
+            address_c.clone(),
+            address_a.clone(),
+            address_b.clone(),
+        ];
 
+        assert_eq!(
+            FactoryInfo::sort_tokens(vec![
+                &env,
+                address_a.clone(),
+                address_b.clone(),
+                address_c.clone()
+            ]),
+            expected
+        );
+        assert_eq!(
+            FactoryInfo::sort_tokens(vec![
+                &env,
+                address_a.clone(),
+                address_c.clone(),
+                address_b.clone()
+            ]),
+            expected
+        );
+        assert_eq!(
+            FactoryInfo::sort_tokens(vec![
+                &env,
+                address_b.clone(),
+                address_a.clone(),
+                address_c.clone()
+            ]),
+            expected
+        );
+        assert_eq!(
+            FactoryInfo::sort_tokens(vec![
+                &env,
+                address_b.clone(),
+                address_c.clone(),
+                address_a.clone()
+            ]),
+            expected
+        );
+        assert_eq!(
+            FactoryInfo::sort_tokens(vec![
+                &env,
+                address_c.clone(),
+                address_a.clone(),
+                address_b.clone()
+            ]),
+            expected
+        );
+        assert_eq!(
+            FactoryInfo::sort_tokens(vec![&env, address_c, address_b, address_a]),
+            expected
+        );
     }
-}
\ No newline at end of file
+}
@@ -0,0 +1,20 @@
+use proc_macros::{extend_ttl_info_instance, Instance, SorobanData, SorobanSimpleData};
+use shared::soroban_data::SymbolKey;
+use soroban_sdk::{contracttype, Address, BytesN, Map, Vec};
+
+use super::factory_info::FactoryInfo;
+
+/// `FactoryInfo` layout of the factory deployed before the pool registry, stored under the same
+/// key, it keeps the pools in a map from the sorted tokens to the pool
+#[contracttype]
+#[derive(SorobanData, SorobanSimpleData, Instance)]
+#[extend_ttl_info_instance]
+pub struct LegacyFactoryInfo {
+    pub two_pool_wasm_hash: BytesN<32>,
+    pub three_pool_wasm_hash: BytesN<32>,
+    pub pools: Map<Vec<Address>, Address>,
+}
+
+impl SymbolKey for LegacyFactoryInfo {
+    const STORAGE_KEY: &'static str = FactoryInfo::STORAGE_KEY;
+}
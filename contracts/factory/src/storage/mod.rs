@@ -1 +1,5 @@
 pub mod factory_info;
+pub mod legacy_factory_info;
+pub mod permissionless_config;
+pub mod pool_record;
+pub mod token_allowlist;
@@ -0,0 +1,68 @@
+use proc_macros::{extend_ttl_info_instance, Instance, SorobanData, SorobanSimpleData, SymbolKey};
+use shared::{require, Error};
+use soroban_sdk::{contracttype, Address, Vec};
+
+use super::factory_info::DEFAULT_TIER;
+
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct ParamRange {
+    pub min: u128,
+    pub max: u128,
+}
+
+impl ParamRange {
+    #[inline]
+    pub fn contains(&self, value: u128) -> bool {
+        (self.min..=self.max).contains(&value)
+    }
+}
+
+/// Admin of the pools created by anyone
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub enum PoolAdmin {
+    Factory,
+    Protocol(Address),
+}
+
+/// Bounds of the pools anyone can create, the admin can create pools with any parameters
+#[contracttype]
+#[derive(Clone, Debug, SorobanData, SorobanSimpleData, SymbolKey, Instance)]
+#[extend_ttl_info_instance]
+pub struct PermissionlessConfig {
+    pub enabled: bool,
+    pub a: ParamRange,
+    pub fee_share_bp: ParamRange,
+    pub admin_fee_share_bp: ParamRange,
+    pub pool_admin: PoolAdmin,
+    /// Tiers open to the pools created by anyone, the default tier stays with the admin
+    pub tiers: Vec<u32>,
+}
+
+impl PermissionlessConfig {
+    pub fn require_valid(&self) -> Result<(), Error> {
+        for range in [&self.a, &self.fee_share_bp, &self.admin_fee_share_bp] {
+            require!(range.min <= range.max, Error::InvalidArg);
+        }
+        require!(!self.tiers.contains(DEFAULT_TIER), Error::InvalidArg);
+
+        Ok(())
+    }
+
+    pub fn require_params(
+        &self,
+        a: u128,
+        fee_share_bp: u128,
+        admin_fee_share_bp: u128,
+    ) -> Result<(), Error> {
+        require!(
+            self.a.contains(a)
+                && self.fee_share_bp.contains(fee_share_bp)
+                && self.admin_fee_share_bp.contains(admin_fee_share_bp),
+            Error::ParamOutOfBounds
+        );
+
+        Ok(())
+    }
+}
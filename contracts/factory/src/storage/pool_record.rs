@@ -0,0 +1,212 @@
+use proc_macros::{extend_ttl_info, Persistent, SorobanData};
+use shared::{consts::DAY_IN_LEDGERS, require, soroban_data::SorobanData, Error};
+use soroban_sdk::{contracttype, Address, BytesN, Env, Vec};
+
+use super::factory_info::MAX_PAGE_LIMIT;
+
+const BUMP_AMOUNT: u32 = 30 * DAY_IN_LEDGERS;
+const LIFETIME_THRESHOLD: u32 = BUMP_AMOUNT - DAY_IN_LEDGERS;
+
+#[contracttype]
+enum PoolKey {
+    Record(u32),
+    IndexByTokens(Vec<Address>, u32),
+    IndexByPool(Address),
+    PoolsByToken(Address),
+    PoolsByTokens(Vec<Address>),
+    Generation(Vec<Address>, u32),
+}
+
+#[contracttype]
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub enum PoolType {
+    TwoPool,
+    ThreePool,
+    StablePool,
+    Metapool,
+}
+
+/// A delisted pool is kept in the registry but left out of the lookups, its token set and tier
+/// can get a new pool
+#[contracttype]
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub enum PoolStatus {
+    Active,
+    Deprecated,
+    Delisted,
+}
+
+/// Registry entry of a pool deployed by the factory, stored under its index
+#[contracttype]
+#[derive(Clone, Debug, SorobanData, Persistent)]
+#[extend_ttl_info(BUMP_AMOUNT, LIFETIME_THRESHOLD)]
+pub struct PoolRecord {
+    pub pool: Address,
+    /// Sorted
+    pub tokens: Vec<Address>,
+    pub pool_type: PoolType,
+    /// Tells apart the pools of the same tokens
+    pub tier: u32,
+    /// Deployer of the pool
+    pub creator: Address,
+    pub created_ledger: u32,
+    /// Hash of the wasm the pool runs, kept up to date by `upgrade_pools`
+    pub wasm_hash: BytesN<32>,
+    pub status: PoolStatus,
+}
+
+/// Index of a `PoolRecord`, stored under the sorted tokens with the tier and under the pool
+/// address
+#[contracttype]
+#[derive(SorobanData, Persistent)]
+#[extend_ttl_info(BUMP_AMOUNT, LIFETIME_THRESHOLD)]
+struct PoolIndex(u32);
+
+/// Indices of the pools holding a token or a token set, in the order they were created, a listed
+/// pool takes one read of its record
+#[contracttype]
+#[derive(SorobanData, Persistent)]
+#[extend_ttl_info(BUMP_AMOUNT, LIFETIME_THRESHOLD)]
+struct PoolList(Vec<u32>);
+
+/// Number of times the pool of a token set and tier was delisted, gives the next pool a new salt
+#[contracttype]
+#[derive(SorobanData, Persistent)]
+#[extend_ttl_info(BUMP_AMOUNT, LIFETIME_THRESHOLD)]
+struct Generation(u32);
+
+impl PoolRecord {
+    #[allow(clippy::too_many_arguments)]
+    pub fn new(
+        env: &Env,
+        pool: Address,
+        tokens: Vec<Address>,
+        pool_type: PoolType,
+        tier: u32,
+        creator: Address,
+        wasm_hash: BytesN<32>,
+    ) -> Self {
+        PoolRecord {
+            pool,
+            tokens,
+            pool_type,
+            tier,
+            creator,
+            created_ledger: env.ledger().sequence(),
+            wasm_hash,
+            status: PoolStatus::Active,
+        }
+    }
+
+    pub fn get(env: &Env, index: u32) -> Result<Self, Error> {
+        Self::get_by_key(env, &PoolKey::Record(index)).map_err(|_| Error::NotFound)
+    }
+
+    pub fn get_by_tokens(env: &Env, sorted_tokens: Vec<Address>, tier: u32) -> Result<Self, Error> {
+        let index = PoolIndex::get_by_key(env, &PoolKey::IndexByTokens(sorted_tokens, tier))
+            .map_err(|_| Error::NotFound)?;
+
+        Self::get(env, index.0)
+    }
+
+    pub fn get_by_pool(env: &Env, pool: Address) -> Result<Self, Error> {
+        Self::get(env, Self::get_index(env, pool)?)
+    }
+
+    pub fn get_index(env: &Env, pool: Address) -> Result<u32, Error> {
+        PoolIndex::get_by_key(env, &PoolKey::IndexByPool(pool))
+            .map(|index| index.0)
+            .map_err(|_| Error::NotFound)
+    }
+
+    /// Pools of `token` from the position `start` of its list on, the page covers at most
+    /// `MAX_PAGE_LIMIT` positions and leaves out the delisted pools in them
+    pub fn get_pools_by_token(env: &Env, token: Address, start: u32, limit: u32) -> Vec<Address> {
+        let indices = get_pool_list(env, &PoolKey::PoolsByToken(token));
+        let end = start
+            .saturating_add(limit.min(MAX_PAGE_LIMIT))
+            .min(indices.len());
+
+        listed_pools(env, indices.slice(start.min(end)..end))
+    }
+
+    pub fn get_pools_by_tokens(env: &Env, sorted_tokens: Vec<Address>) -> Vec<Address> {
+        listed_pools(
+            env,
+            get_pool_list(env, &PoolKey::PoolsByTokens(sorted_tokens)),
+        )
+    }
+
+    pub fn get_generation(env: &Env, sorted_tokens: Vec<Address>, tier: u32) -> u32 {
+        Generation::get_by_key(env, &PoolKey::Generation(sorted_tokens, tier))
+            .map(|generation| generation.0)
+            .unwrap_or(0)
+    }
+
+    pub fn is_delisted(&self) -> bool {
+        self.status == PoolStatus::Delisted
+    }
+
+    /// Delisting drops the lookup by tokens and tier, listing the pool again takes it back unless
+    /// a new pool holds it
+    pub fn set_status(&mut self, env: &Env, index: u32, status: PoolStatus) -> Result<(), Error> {
+        let key = PoolKey::IndexByTokens(self.tokens.clone(), self.tier);
+
+        if !self.is_delisted() && status == PoolStatus::Delisted {
+            PoolIndex::remove_by_key(env, &key);
+
+            let generation = Self::get_generation(env, self.tokens.clone(), self.tier);
+            Generation(generation + 1)
+                .save_by_key(env, &PoolKey::Generation(self.tokens.clone(), self.tier));
+        } else if self.is_delisted() && status != PoolStatus::Delisted {
+            require!(PoolIndex::get_by_key(env, &key).is_err(), Error::PoolExist);
+            PoolIndex(index).save_by_key(env, &key);
+        }
+
+        self.status = status;
+        self.save(env, index);
+
+        Ok(())
+    }
+
+    pub fn save(&self, env: &Env, index: u32) {
+        self.save_by_key(env, &PoolKey::Record(index));
+    }
+
+    /// Saves a new record under `index` along with the lookups by tokens, by pool and by token
+    pub fn register(&self, env: &Env, index: u32) {
+        self.save(env, index);
+        PoolIndex(index).save_by_key(env, &PoolKey::IndexByTokens(self.tokens.clone(), self.tier));
+        PoolIndex(index).save_by_key(env, &PoolKey::IndexByPool(self.pool.clone()));
+
+        push_pool(env, &PoolKey::PoolsByTokens(self.tokens.clone()), index);
+        for token in self.tokens.iter() {
+            push_pool(env, &PoolKey::PoolsByToken(token), index);
+        }
+    }
+}
+
+fn get_pool_list(env: &Env, key: &PoolKey) -> Vec<u32> {
+    PoolList::get_by_key(env, key)
+        .map(|pool_list| pool_list.0)
+        .unwrap_or_else(|_| Vec::new(env))
+}
+
+fn listed_pools(env: &Env, indices: Vec<u32>) -> Vec<Address> {
+    let mut listed = Vec::new(env);
+    for index in indices.iter() {
+        if let Ok(record) = PoolRecord::get(env, index) {
+            if !record.is_delisted() {
+                listed.push_back(record.pool);
+            }
+        }
+    }
+
+    listed
+}
+
+fn push_pool(env: &Env, key: &PoolKey, index: u32) {
+    let mut indices = get_pool_list(env, key);
+    indices.push_back(index);
+    PoolList(indices).save_by_key(env, key);
+}
@@ -0,0 +1,75 @@
+use proc_macros::{
+    extend_ttl_info, extend_ttl_info_instance, Instance, Persistent, SorobanData,
+    SorobanSimpleData, SymbolKey,
+};
+use shared::{
+    consts::DAY_IN_LEDGERS,
+    require,
+    soroban_data::{SimpleSorobanData, SorobanData},
+    Error,
+};
+use soroban_sdk::{contracttype, token, Address, Env, Vec};
+
+const BUMP_AMOUNT: u32 = 30 * DAY_IN_LEDGERS;
+const LIFETIME_THRESHOLD: u32 = BUMP_AMOUNT - DAY_IN_LEDGERS;
+
+/// Above it the pools' precision conversions get close to overflowing `u128`
+pub const MAX_TOKEN_DECIMALS: u32 = 18;
+
+#[contracttype]
+enum TokenKey {
+    Allowed(Address),
+}
+
+/// Limits pool creation to the tokens the admin allowed once enabled
+#[contracttype]
+#[derive(SorobanData, SorobanSimpleData, SymbolKey, Instance)]
+#[extend_ttl_info_instance]
+pub struct TokenAllowlist {
+    pub enabled: bool,
+}
+
+/// Admin decision on a token, stored under the token
+#[contracttype]
+#[derive(SorobanData, Persistent)]
+#[extend_ttl_info(BUMP_AMOUNT, LIFETIME_THRESHOLD)]
+struct AllowedToken(bool);
+
+impl TokenAllowlist {
+    pub fn is_enabled(env: &Env) -> bool {
+        Self::get(env)
+            .map(|allowlist| allowlist.enabled)
+            .unwrap_or(false)
+    }
+
+    pub fn is_allowed(env: &Env, token: Address) -> bool {
+        AllowedToken::get_by_key(env, &TokenKey::Allowed(token))
+            .map(|allowed| allowed.0)
+            .unwrap_or(false)
+    }
+
+    pub fn set_allowed(env: &Env, token: Address, allowed: bool) {
+        AllowedToken(allowed).save_by_key(env, &TokenKey::Allowed(token));
+    }
+
+    /// Checks the tokens of a new pool against the allowlist and their decimals
+    pub fn require_valid_tokens(env: &Env, tokens: &Vec<Address>) -> Result<(), Error> {
+        let enabled = Self::is_enabled(env);
+
+        for token in tokens.iter() {
+            require!(
+                !enabled || Self::is_allowed(env, token.clone()),
+                Error::TokenNotAllowed
+            );
+
+            let decimals = token::Client::new(env, &token)
+                .try_decimals()
+                .ok()
+                .and_then(|decimals| decimals.ok())
+                .ok_or(Error::InvalidTokenDecimals)?;
+            require!(decimals <= MAX_TOKEN_DECIMALS, Error::InvalidTokenDecimals);
+        }
+
+        Ok(())
+    }
+}
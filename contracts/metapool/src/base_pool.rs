@@ -0,0 +1,37 @@
+use soroban_sdk::{contractclient, Address, Env};
+
+/// Number of tokens in the base pool
+pub const BASE_TOKENS_COUNT: u32 = 3;
+
+/// The `three_pool` a metapool is built on, its LP lives in the metapool's deposit there
+// only the generated client is used
+#[allow(dead_code)]
+#[contractclient(name = "BasePoolClient")]
+pub trait BasePool {
+    /// Returns the minted LP amount
+    fn deposit(env: Env, sender: Address, amounts: (u128, u128, u128), min_lp_amount: u128)
+        -> u128;
+
+    /// Returns the withdrawn amounts after fees and without rewards, token precision
+    fn withdraw(env: Env, sender: Address, lp_amount: u128) -> (u128, u128, u128);
+
+    /// Tokens are passed by index
+    fn swap(
+        env: Env,
+        sender: Address,
+        recipient: Address,
+        amount_in: u128,
+        receive_amount_min: u128,
+        token_from: u32,
+        token_to: u32,
+    ) -> u128;
+
+    fn transfer_lp(env: Env, sender: Address, recipient: Address, lp_amount: u128);
+
+    fn claim_rewards(env: Env, sender: Address);
+
+    /// `RATE_PRECISION` precision
+    fn get_virtual_price(env: Env) -> u128;
+
+    fn get_tokens(env: Env) -> (Address, Address, Address);
+}
@@ -0,0 +1,9 @@
+#![no_std]
+
+mod base_pool;
+mod contract;
+mod events;
+mod methods;
+mod storage;
+
+pub use contract::MetapoolContract;
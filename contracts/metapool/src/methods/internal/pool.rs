@@ -0,0 +1,311 @@
+use shared::{require, utils::num, Error};
+use soroban_sdk::{Address, Env};
+
+use crate::storage::{
+    common::{Direction, Token},
+    double_values::DoubleU128,
+    pool::Pool,
+    user_deposit::UserDeposit,
+};
+
+use super::pool_view::{DepositAmount, WithdrawAmount};
+
+impl Pool {
+    pub const BP: u128 = num::BP;
+    pub const FEE_DENOMINATOR: u128 = num::FEE_DENOMINATOR;
+
+    pub(crate) const MAX_A: u128 = 60;
+    /// Ceiling of the sum of the system precision balances, `get_d` and `get_y` stay within
+    /// `U256` below it
+    pub(crate) const MAX_TOKEN_BALANCE: u128 = 2u128.pow(60);
+    pub(crate) const SYSTEM_PRECISION: u32 = 3;
+    pub(crate) const MAX_OFFPEG_FEE_ITERATIONS: usize = 8;
+
+    pub const P: u128 = 48;
+    pub const VIRTUAL_PRICE_PRECISION: u128 = 1_000_000_000_000_000_000;
+
+    #[allow(clippy::too_many_arguments)]
+    pub fn swap(
+        &mut self,
+        env: &Env,
+        sender: Address,
+        recipient: Address,
+        amount: u128,
+        receive_amount_min: u128,
+        direction: Direction,
+    ) -> Result<(u128, u128), Error> {
+        if amount == 0 {
+            return Ok((0, 0));
+        }
+
+        let current_contract = env.current_contract_address();
+        let (token_from, token_to) = direction.get_tokens();
+
+        self.transfer(env, token_from as usize, &sender, &current_contract, amount)?;
+
+        let (output, fee) = self.swap_balances(amount, token_from)?;
+
+        require!(
+            output >= receive_amount_min,
+            Error::InsufficientReceivedAmount
+        );
+
+        self.transfer(
+            env,
+            token_to as usize,
+            &current_contract,
+            &recipient,
+            output,
+        )?;
+
+        Ok((output, fee))
+    }
+
+    /// Books a swap of `amount` already held by the pool, returns the output and the fee
+    pub(crate) fn swap_balances(
+        &mut self,
+        amount: u128,
+        token_from: Token,
+    ) -> Result<(u128, u128), Error> {
+        let token_to = token_from.opposite();
+        let receive_amount = self.get_receive_amount(amount, token_from)?;
+
+        self.token_balances[token_from] = receive_amount.token_from_new_balance;
+        self.token_balances[token_to] = receive_amount.token_to_new_balance;
+
+        self.add_rewards(receive_amount.fee, token_to);
+
+        Ok((receive_amount.output, receive_amount.fee))
+    }
+
+    pub fn deposit(
+        &mut self,
+        env: &Env,
+        amounts: DoubleU128,
+        sender: Address,
+        user_deposit: &mut UserDeposit,
+        min_lp_amount: u128,
+    ) -> Result<(DepositAmount, DoubleU128), Error> {
+        let current_contract = env.current_contract_address();
+
+        let deposit_amount = self.get_deposit_amount(amounts.clone())?;
+        self.token_balances = deposit_amount.new_token_balances.clone();
+
+        require!(deposit_amount.lp_amount >= min_lp_amount, Error::Slippage);
+
+        for (index, amount) in amounts.to_array().into_iter().enumerate() {
+            self.transfer(env, index, &sender, &current_contract, amount)?;
+        }
+
+        for (index, fee) in deposit_amount.fees.to_array().into_iter().enumerate() {
+            self.add_rewards(fee, index.into());
+        }
+
+        let rewards = self.deposit_lp(user_deposit, deposit_amount.lp_amount)?;
+
+        for (index, reward) in rewards.to_array().into_iter().enumerate() {
+            self.transfer(env, index, &current_contract, &sender, reward)?;
+        }
+
+        Ok((deposit_amount, rewards))
+    }
+
+    pub fn withdraw(
+        &mut self,
+        env: &Env,
+        sender: Address,
+        user_deposit: &mut UserDeposit,
+        lp_amount: u128,
+    ) -> Result<(WithdrawAmount, DoubleU128), Error> {
+        let current_contract = env.current_contract_address();
+        let d0 = self.total_lp_amount;
+        let old_balances = self.token_balances.clone();
+        let withdraw_amount = self.get_withdraw_amount(lp_amount)?;
+        let rewards_amounts = self.withdraw_lp(user_deposit, lp_amount)?;
+
+        for index in withdraw_amount.indexes {
+            let token_amount = self.amount_from_system_precision(
+                withdraw_amount.amounts[index],
+                self.tokens_decimals[index],
+            );
+            let token_amount = token_amount + rewards_amounts[index];
+
+            self.add_rewards(withdraw_amount.fees[index], index.into());
+            self.transfer(env, index, &current_contract, &sender, token_amount)?;
+        }
+
+        self.token_balances = withdraw_amount.new_token_balances.clone();
+        let d1 = self.total_lp_amount;
+
+        require!(
+            self.token_balances[0] < old_balances[0]
+                && self.token_balances[1] < old_balances[1]
+                && d1 < d0,
+            Error::ZeroChanges
+        );
+
+        Ok((withdraw_amount, rewards_amounts))
+    }
+
+    pub(crate) fn deposit_lp(
+        &mut self,
+        user_deposit: &mut UserDeposit,
+        lp_amount: u128,
+    ) -> Result<DoubleU128, Error> {
+        let pending = self.get_pending(user_deposit);
+
+        self.total_lp_amount += lp_amount;
+        user_deposit.lp_amount += lp_amount;
+        user_deposit.reward_debts = self.get_reward_debts(user_deposit);
+
+        Ok(pending)
+    }
+
+    pub(crate) fn withdraw_lp(
+        &mut self,
+        user_deposit: &mut UserDeposit,
+        lp_amount: u128,
+    ) -> Result<DoubleU128, Error> {
+        require!(user_deposit.lp_amount >= lp_amount, Error::NotEnoughAmount);
+
+        let pending = self.get_pending(user_deposit);
+
+        self.total_lp_amount -= lp_amount;
+        user_deposit.lp_amount -= lp_amount;
+        user_deposit.reward_debts = self.get_reward_debts(user_deposit);
+
+        Ok(pending)
+    }
+
+    pub fn claim_rewards(
+        &self,
+        env: &Env,
+        user: Address,
+        user_deposit: &mut UserDeposit,
+    ) -> Result<DoubleU128, Error> {
+        let mut pending = DoubleU128::default();
+
+        if user_deposit.lp_amount == 0 {
+            return Ok(pending);
+        }
+
+        let rewards = self.get_reward_debts(user_deposit);
+
+        for (index, reward) in rewards.to_array().into_iter().enumerate() {
+            pending[index] = reward - user_deposit.reward_debts[index];
+
+            if pending[index] > 0 {
+                user_deposit.reward_debts[index] = reward;
+
+                self.transfer(
+                    env,
+                    index,
+                    &env.current_contract_address(),
+                    &user,
+                    pending[index],
+                )?;
+            }
+        }
+
+        Ok(pending)
+    }
+
+    pub(crate) fn add_rewards(&mut self, reward_amount: u128, token: Token) {
+        if self.total_lp_amount == 0 {
+            return;
+        }
+
+        let (reward_amount, admin_fee_rewards) =
+            num::split_admin_fee(reward_amount, self.admin_fee_share_bp);
+        self.acc_rewards_per_share_p[token] += (reward_amount << Pool::P) / self.total_lp_amount;
+        self.admin_fee_amount[token] += admin_fee_rewards;
+    }
+
+    pub fn get_pending(&self, user_deposit: &UserDeposit) -> DoubleU128 {
+        if user_deposit.lp_amount == 0 {
+            return DoubleU128::default();
+        }
+
+        let rewards = self.get_reward_debts(user_deposit);
+
+        DoubleU128::from((
+            rewards[0] - user_deposit.reward_debts[0],
+            rewards[1] - user_deposit.reward_debts[1],
+        ))
+    }
+
+    pub fn get_reward_debts(&self, user_deposit: &UserDeposit) -> DoubleU128 {
+        let lp_amount = user_deposit.lp_amount;
+
+        DoubleU128::from((
+            num::accrued_rewards(lp_amount, self.acc_rewards_per_share_p[0], Pool::P),
+            num::accrued_rewards(lp_amount, self.acc_rewards_per_share_p[1], Pool::P),
+        ))
+    }
+
+    /// Balance of one token that keeps the invariant at `d` along with the balance `native_x` of
+    /// the other one
+    pub fn get_y(&self, native_x: u128, d: u128) -> Result<u128, Error> {
+        num::get_y(self.a, [native_x, 0], 1, d)
+    }
+
+    pub fn get_current_d(&self) -> Result<u128, Error> {
+        self.get_d_for_balances(&self.token_balances)
+    }
+
+    /// `get_d` of system precision balances scaled by the rates
+    pub fn get_d_for_balances(&self, balances_sp: &DoubleU128) -> Result<u128, Error> {
+        self.get_d(
+            self.scale_by_rate(balances_sp[0], 0),
+            self.scale_by_rate(balances_sp[1], 1),
+        )
+    }
+
+    /// Invariant of the current balances, the LP supply tracks it 1:1 while all rates are fixed
+    pub fn get_d0(&self) -> Result<u128, Error> {
+        if !self.has_dynamic_rates() {
+            return Ok(self.total_lp_amount);
+        }
+
+        self.get_current_d()
+    }
+
+    /// Invariant the imbalance fee of a deposit is measured against, unlike `get_d0` it counts
+    /// the part of the balances the LP supply does not cover
+    pub fn get_imbalance_fee_d0(&self) -> Result<u128, Error> {
+        if self.total_lp_amount == 0 {
+            return Ok(0);
+        }
+
+        self.get_current_d()
+    }
+
+    pub fn get_d(&self, x: u128, y: u128) -> Result<u128, Error> {
+        num::get_d(self.a, [x, y])
+    }
+
+    pub(crate) fn get_scaled_balances(&self) -> DoubleU128 {
+        DoubleU128::from((
+            self.scale_by_rate(self.token_balances[0], 0),
+            self.scale_by_rate(self.token_balances[1], 1),
+        ))
+    }
+
+    /// Values a system precision amount of the token in the pegged asset, the curve works on
+    /// scaled amounts
+    pub(crate) fn scale_by_rate(&self, amount_sp: u128, index: usize) -> u128 {
+        num::scale_by_rate(amount_sp, self.rates[index])
+    }
+
+    pub(crate) fn unscale_by_rate(&self, amount: u128, index: usize) -> u128 {
+        num::unscale_by_rate(amount, self.rates[index])
+    }
+
+    pub(crate) fn amount_to_system_precision(&self, amount: u128, decimals: u32) -> u128 {
+        num::to_system_precision(amount, decimals, Self::SYSTEM_PRECISION)
+    }
+
+    pub(crate) fn amount_from_system_precision(&self, amount: u128, decimals: u32) -> u128 {
+        num::from_system_precision(amount, decimals, Self::SYSTEM_PRECISION)
+    }
+}
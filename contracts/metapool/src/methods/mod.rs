@@ -0,0 +1,3 @@
+pub mod internal;
+pub mod public;
+pub mod view;
@@ -0,0 +1,162 @@
+use shared::{price_feed::read_price_rate, require, soroban_data::SimpleSorobanData, Error, Event};
+use soroban_sdk::{Address, Env};
+use storage::Admin;
+
+use crate::{
+    events::{
+        AdminChanged, AdminFeeShareUpdated, DepositFeeShareUpdated, OffpegFeeMultiplierUpdated,
+        RateSourceUpdated, SwapFeeShareUpdated, WithdrawFeeShareUpdated,
+    },
+    storage::{
+        common::{PriceOracle, RateSource, Token},
+        pool::Pool,
+    },
+};
+
+pub fn set_admin(env: Env, new_admin: Address) -> Result<(), Error> {
+    Admin::require_exist_auth(&env)?;
+    Admin(new_admin.clone()).save(&env);
+
+    AdminChanged { new_admin }.publish(&env);
+
+    Ok(())
+}
+
+pub fn set_swap_fee_share(env: Env, swap_fee_share: u128) -> Result<(), Error> {
+    Admin::require_exist_auth(&env)?;
+
+    require!(swap_fee_share < Pool::FEE_DENOMINATOR, Error::InvalidArg);
+
+    Pool::update(&env, |pool| {
+        pool.swap_fee_share = swap_fee_share;
+        require!(
+            pool.get_max_swap_fee_share() < Pool::FEE_DENOMINATOR,
+            Error::InvalidArg
+        );
+        Ok(())
+    })?;
+
+    SwapFeeShareUpdated { swap_fee_share }.publish(&env);
+
+    Ok(())
+}
+
+pub fn set_withdraw_fee_share(env: Env, withdraw_fee_share: u128) -> Result<(), Error> {
+    Admin::require_exist_auth(&env)?;
+
+    require!(
+        withdraw_fee_share < Pool::FEE_DENOMINATOR,
+        Error::InvalidArg
+    );
+
+    Pool::update(&env, |pool| {
+        pool.withdraw_fee_share = withdraw_fee_share;
+        Ok(())
+    })?;
+
+    WithdrawFeeShareUpdated { withdraw_fee_share }.publish(&env);
+
+    Ok(())
+}
+
+pub fn set_deposit_fee_share(env: Env, deposit_fee_share: u128) -> Result<(), Error> {
+    Admin::require_exist_auth(&env)?;
+
+    require!(deposit_fee_share < Pool::FEE_DENOMINATOR, Error::InvalidArg);
+
+    Pool::update(&env, |pool| {
+        pool.deposit_fee_share = deposit_fee_share;
+        Ok(())
+    })?;
+
+    DepositFeeShareUpdated { deposit_fee_share }.publish(&env);
+
+    Ok(())
+}
+
+pub fn set_admin_fee_share(env: Env, admin_fee_share_bp: u128) -> Result<(), Error> {
+    Admin::require_exist_auth(&env)?;
+
+    require!(admin_fee_share_bp < Pool::BP, Error::InvalidArg);
+
+    Pool::update(&env, |pool| {
+        pool.admin_fee_share_bp = admin_fee_share_bp;
+        Ok(())
+    })?;
+
+    AdminFeeShareUpdated { admin_fee_share_bp }.publish(&env);
+
+    Ok(())
+}
+
+pub fn set_offpeg_fee_multiplier(env: Env, offpeg_fee_multiplier_bp: u128) -> Result<(), Error> {
+    Admin::require_exist_auth(&env)?;
+
+    Pool::update(&env, |pool| {
+        pool.offpeg_fee_multiplier_bp = offpeg_fee_multiplier_bp;
+        require!(
+            pool.get_max_swap_fee_share() < Pool::FEE_DENOMINATOR,
+            Error::InvalidArg
+        );
+        Ok(())
+    })?;
+
+    OffpegFeeMultiplierUpdated {
+        offpeg_fee_multiplier_bp,
+    }
+    .publish(&env);
+
+    Ok(())
+}
+
+pub fn set_rate_provider(
+    env: Env,
+    token: Token,
+    rate_provider: Option<Address>,
+) -> Result<(), Error> {
+    Admin::require_exist_auth(&env)?;
+
+    set_rate_source(&env, token, RateSource::from(rate_provider))
+}
+
+pub fn set_oracle(
+    env: Env,
+    token: Token,
+    price_feed: Address,
+    max_price_age: u64,
+    max_price_deviation_bp: u128,
+) -> Result<(), Error> {
+    Admin::require_exist_auth(&env)?;
+    require!(max_price_deviation_bp <= Pool::BP, Error::InvalidArg);
+
+    let oracle = PriceOracle {
+        peg_rate: read_price_rate(&env, &price_feed, max_price_age)?,
+        price_feed,
+        max_price_age,
+        max_price_deviation_bp,
+    };
+
+    set_rate_source(&env, token, RateSource::Oracle(oracle))
+}
+
+/// Takes the current rate of the source as is, only while the pool holds no liquidity so a new
+/// source cannot reprice the deposits already in it
+fn set_rate_source(env: &Env, token: Token, rate_source: RateSource) -> Result<(), Error> {
+    // the base pool LP is always valued at the virtual price of the base pool
+    require!(matches!(token, Token::A), Error::InvalidArg);
+
+    let mut pool = Pool::get(env)?;
+    require!(pool.total_lp_amount == 0, Error::Forbidden);
+
+    pool.rates[token] = Pool::read_rate(env, &rate_source)?;
+    pool.rate_sources[token] = rate_source.clone();
+    pool.save(env);
+
+    RateSourceUpdated {
+        token: pool.tokens[token].clone(),
+        rate_source,
+    }
+    .publish(env);
+
+    Ok(())
+}
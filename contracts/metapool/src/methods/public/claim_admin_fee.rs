@@ -0,0 +1,39 @@
+use shared::{soroban_data::SimpleSorobanData, Error, Event};
+use soroban_sdk::Env;
+use storage::{Admin, OperationNonce};
+
+use crate::{
+    events::{AdminFeeClaimed, Sync},
+    storage::pool::Pool,
+};
+
+/// Base pool rewards count once `harvest` adds them, call it first to take their admin fee share
+pub fn claim_admin_fee(env: Env) -> Result<(), Error> {
+    let admin = Admin::get(&env)?;
+    admin.require_auth();
+
+    let current_contract = env.current_contract_address();
+    let mut pool = Pool::get(&env)?;
+
+    let fees = pool.admin_fee_amount.clone();
+
+    for (index, fee) in fees.to_array().into_iter().enumerate() {
+        if fee > 0 {
+            pool.transfer(&env, index, &current_contract, admin.as_ref(), fee)?;
+            pool.admin_fee_amount[index] = 0;
+            pool.save(&env);
+        }
+    }
+
+    if !fees.is_zero() {
+        AdminFeeClaimed {
+            admin: admin.0,
+            fees: fees.data,
+        }
+        .publish(&env);
+    }
+
+    Sync::from_pool(&pool, OperationNonce::next(&env)).publish(&env);
+
+    Ok(())
+}
@@ -0,0 +1,51 @@
+use shared::{soroban_data::SimpleSorobanData, Error, Event};
+use soroban_sdk::{Address, Env};
+use storage::OperationNonce;
+
+use crate::{
+    events::{Deposit, RewardsClaimed, Sync},
+    storage::{double_values::DoubleU128, pool::Pool, user_deposit::UserDeposit},
+};
+
+pub fn deposit(
+    env: Env,
+    sender: Address,
+    amounts: (u128, u128),
+    min_lp_amount: u128,
+) -> Result<(), Error> {
+    sender.require_auth();
+    let mut pool = Pool::get_with_rates(&env)?;
+    let mut user_deposit = UserDeposit::get(&env, sender.clone());
+    let amounts = DoubleU128::from(amounts);
+
+    let (deposit_amount, rewards) = pool.deposit(
+        &env,
+        amounts.clone(),
+        sender.clone(),
+        &mut user_deposit,
+        min_lp_amount,
+    )?;
+
+    pool.save(&env);
+    user_deposit.save(&env, sender.clone());
+
+    Deposit {
+        user: sender.clone(),
+        lp_amount: deposit_amount.lp_amount,
+        amounts: amounts.data,
+        fees: deposit_amount.fees.data,
+    }
+    .publish(&env);
+
+    if !rewards.is_zero() {
+        RewardsClaimed {
+            user: sender,
+            rewards: rewards.data,
+        }
+        .publish(&env);
+    }
+
+    Sync::from_pool(&pool, OperationNonce::next(&env)).publish(&env);
+
+    Ok(())
+}
@@ -0,0 +1,52 @@
+use shared::{require, soroban_data::SimpleSorobanData, utils::safe_cast, Error, Event};
+use soroban_sdk::Env;
+use storage::OperationNonce;
+
+use crate::{
+    base_pool::BASE_TOKENS_COUNT,
+    events::{BaseRewardsAdded, Sync},
+    storage::{common::Token, pool::Pool},
+};
+
+/// Claims the base pool rewards earned by the LP the pool holds and deposits them back into the
+/// base pool, the minted LP is added to the rewards of token `B` so the LPs get it less the admin
+/// fee share. Anyone can call it, a failed base pool deposit reverts the call and leaves the
+/// rewards for the next one
+pub fn harvest(env: Env, min_lp_amount: u128) -> Result<(), Error> {
+    let current_contract = env.current_contract_address();
+    let mut pool = Pool::get(&env)?;
+    require!(pool.total_lp_amount > 0, Error::ZeroAmount);
+    let base_pool = pool.get_base_pool(&env);
+
+    // The base pool also pays them out to the pool whenever its deposit there changes, the pool
+    // holds no base tokens otherwise
+    base_pool.claim_rewards(&current_contract);
+
+    let mut rewards = [0; BASE_TOKENS_COUNT as usize];
+    for (index, reward) in rewards.iter_mut().enumerate() {
+        *reward = safe_cast(
+            pool.get_base_token(&env, index as u32)
+                .balance(&current_contract),
+        )?;
+    }
+    require!(rewards.iter().any(|reward| *reward > 0), Error::ZeroAmount);
+
+    pool.authorize_base_transfers(&env, &rewards)?;
+    let lp_amount = base_pool.deposit(
+        &current_contract,
+        &(rewards[0], rewards[1], rewards[2]),
+        &min_lp_amount,
+    );
+
+    pool.add_rewards(lp_amount, Token::B);
+    pool.save(&env);
+
+    BaseRewardsAdded {
+        rewards: (rewards[0], rewards[1], rewards[2]),
+        lp_amount,
+    }
+    .publish(&env);
+    Sync::from_pool(&pool, OperationNonce::next(&env)).publish(&env);
+
+    Ok(())
+}
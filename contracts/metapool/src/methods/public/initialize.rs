@@ -0,0 +1,49 @@
+use shared::{require, soroban_data::SimpleSorobanData, Error};
+use soroban_sdk::{token, vec, Address, Env};
+use storage::{Admin, Upgrader};
+
+use crate::{base_pool::BasePoolClient, storage::pool::Pool};
+
+#[allow(clippy::too_many_arguments)]
+pub fn initialize(
+    env: Env,
+    admin: Address,
+    a: u128,
+    token: Address,
+    base_pool: Address,
+    fee_share_bp: u128,
+    admin_fee_share_bp: u128,
+    upgrader: Address,
+) -> Result<(), Error> {
+    require!(!Pool::has(&env), Error::Initialized);
+
+    require!(fee_share_bp < Pool::BP, Error::InvalidArg);
+    require!(admin_fee_share_bp < Pool::BP, Error::InvalidArg);
+    require!(a <= Pool::MAX_A, Error::InvalidArg);
+
+    let decimals = token::Client::new(&env, &token).decimals();
+    let base_pool_client = BasePoolClient::new(&env, &base_pool);
+    let (base_token_a, base_token_b, base_token_c) = base_pool_client.get_tokens();
+    let base_tokens = vec![&env, base_token_a, base_token_b, base_token_c];
+
+    require!(!base_tokens.contains(&token), Error::IdenticalAddresses);
+
+    let base_virtual_price = base_pool_client.get_virtual_price();
+    require!(base_virtual_price > 0, Error::InvalidRate);
+
+    Pool::from_init_params(
+        a,
+        token,
+        base_pool,
+        base_tokens,
+        decimals,
+        base_virtual_price,
+        fee_share_bp,
+        admin_fee_share_bp,
+    )
+    .save(&env);
+    Admin(admin).save(&env);
+    Upgrader(upgrader).save(&env);
+
+    Ok(())
+}
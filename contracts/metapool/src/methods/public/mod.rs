@@ -0,0 +1,19 @@
+mod admin;
+mod claim_admin_fee;
+mod claim_rewards;
+mod deposit;
+mod harvest;
+mod initialize;
+mod swap;
+mod swap_underlying;
+mod withdraw;
+
+pub use admin::*;
+pub use claim_admin_fee::*;
+pub use claim_rewards::*;
+pub use deposit::*;
+pub use harvest::*;
+pub use initialize::*;
+pub use swap::*;
+pub use swap_underlying::*;
+pub use withdraw::*;
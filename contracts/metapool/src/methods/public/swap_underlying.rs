@@ -0,0 +1,163 @@
+use shared::{require, soroban_data::SimpleSorobanData, utils::safe_cast, Error, Event};
+use soroban_sdk::{Address, Env};
+use storage::OperationNonce;
+
+use crate::{
+    base_pool::BASE_TOKENS_COUNT,
+    events::{SwappedUnderlying, Sync},
+    storage::{common::Token, pool::Pool},
+};
+
+/// Token `0` is the pool token and `1..=3` are the base pool tokens, one side of the trade has to
+/// be the pool token, the base tokens are traded in the base pool itself
+pub fn swap_underlying(
+    env: Env,
+    sender: Address,
+    recipient: Address,
+    from_amount: u128,
+    receive_amount_min: u128,
+    token_from: u32,
+    token_to: u32,
+) -> Result<u128, Error> {
+    sender.require_auth();
+    require!(
+        token_from <= BASE_TOKENS_COUNT && token_to <= BASE_TOKENS_COUNT,
+        Error::InvalidArg
+    );
+    require!((token_from == 0) != (token_to == 0), Error::InvalidArg);
+    require!(from_amount > 0, Error::ZeroAmount);
+
+    let mut pool = Pool::get_with_rates(&env)?;
+    let current_contract = env.current_contract_address();
+
+    let (to_amount, fee) = if token_from == 0 {
+        let base_index = token_to - 1;
+
+        pool.transfer(
+            &env,
+            Token::A as usize,
+            &sender,
+            &current_contract,
+            from_amount,
+        )?;
+        let (lp_amount, fee) = pool.swap_balances(from_amount, Token::A)?;
+
+        (
+            withdraw_base_token(&env, &pool, lp_amount, base_index)?,
+            fee,
+        )
+    } else {
+        let base_index = token_from - 1;
+
+        pool.get_base_token(&env, base_index).transfer(
+            &sender,
+            &current_contract,
+            &safe_cast(from_amount)?,
+        );
+        let lp_amount = deposit_base_token(&env, &pool, from_amount, base_index)?;
+
+        pool.swap_balances(lp_amount, Token::B)?
+    };
+
+    require!(
+        to_amount >= receive_amount_min,
+        Error::InsufficientReceivedAmount
+    );
+
+    if token_to == 0 {
+        pool.transfer(
+            &env,
+            Token::A as usize,
+            &current_contract,
+            &recipient,
+            to_amount,
+        )?;
+    } else {
+        pool.get_base_token(&env, token_to - 1).transfer(
+            &current_contract,
+            &recipient,
+            &safe_cast(to_amount)?,
+        );
+    }
+
+    pool.save(&env);
+
+    SwappedUnderlying {
+        from_token: get_underlying_token(&pool, token_from),
+        to_token: get_underlying_token(&pool, token_to),
+        from_amount,
+        to_amount,
+        sender,
+        recipient,
+        fee,
+    }
+    .publish(&env);
+
+    Sync::from_pool(&pool, OperationNonce::next(&env)).publish(&env);
+
+    Ok(to_amount)
+}
+
+fn get_underlying_token(pool: &Pool, index: u32) -> Address {
+    match index {
+        0 => pool.tokens[Token::A].clone(),
+        _ => pool.base_tokens.get_unchecked(index - 1),
+    }
+}
+
+/// Deposits the base token held by the pool into the base pool, returns the minted LP amount
+fn deposit_base_token(
+    env: &Env,
+    pool: &Pool,
+    amount: u128,
+    base_index: u32,
+) -> Result<u128, Error> {
+    let mut amounts = [0; BASE_TOKENS_COUNT as usize];
+    amounts[base_index as usize] = amount;
+
+    pool.authorize_base_transfers(env, &amounts)?;
+
+    Ok(pool.get_base_pool(env).deposit(
+        &env.current_contract_address(),
+        &(amounts[0], amounts[1], amounts[2]),
+        &0,
+    ))
+}
+
+/// Withdraws `lp_amount` from the base pool and swaps the other tokens into the one at
+/// `base_index`, returns its amount
+fn withdraw_base_token(
+    env: &Env,
+    pool: &Pool,
+    lp_amount: u128,
+    base_index: u32,
+) -> Result<u128, Error> {
+    let current_contract = env.current_contract_address();
+    let base_pool = pool.get_base_pool(env);
+
+    let (amount_a, amount_b, amount_c) = base_pool.withdraw(&current_contract, &lp_amount);
+    let amounts = [amount_a, amount_b, amount_c];
+    let mut to_amount = amounts[base_index as usize];
+
+    for (index, amount) in amounts.into_iter().enumerate() {
+        if index as u32 == base_index || amount == 0 {
+            continue;
+        }
+
+        let mut transfer_amounts = [0; BASE_TOKENS_COUNT as usize];
+        transfer_amounts[index] = amount;
+        pool.authorize_base_transfers(env, &transfer_amounts)?;
+
+        let index = index as u32;
+        to_amount += base_pool.swap(
+            &current_contract,
+            &current_contract,
+            &amount,
+            &0,
+            &index,
+            &base_index,
+        );
+    }
+
+    Ok(to_amount)
+}
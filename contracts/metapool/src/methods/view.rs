@@ -0,0 +1,131 @@
+use shared::{soroban_data::SimpleSorobanData, Error};
+use soroban_sdk::{Address, Env, Vec};
+use storage::Admin;
+
+use crate::storage::user_deposit::UserDeposit;
+use crate::storage::{common::Token, pool::Pool};
+
+use super::internal::pool_view::{
+    DepositPreview, PoolInfo, SwapQuote, WithdrawAmountView, WithdrawPreview,
+};
+
+pub fn pending_reward(env: Env, user: Address) -> Result<(u128, u128), Error> {
+    let user = UserDeposit::get(&env, user);
+    let pool = Pool::get(&env)?;
+
+    let pending = pool.get_pending(&user);
+
+    Ok((pending[0], pending[1]))
+}
+
+/// Missing deposits are read as zero without touching their storage entries
+pub fn pending_rewards(env: Env, users: Vec<Address>) -> Result<Vec<(u128, u128)>, Error> {
+    let pool = Pool::get(&env)?;
+    let mut pending_rewards = Vec::new(&env);
+
+    for user in users {
+        let user = UserDeposit::get(&env, user);
+        pending_rewards.push_back(pool.get_pending(&user).data);
+    }
+
+    Ok(pending_rewards)
+}
+
+pub fn get_pool(env: Env) -> Result<Pool, Error> {
+    Pool::get_with_last_rates(&env)
+}
+
+pub fn get_d(env: Env) -> Result<u128, Error> {
+    Pool::get_with_last_rates(&env)?.get_d0()
+}
+
+pub fn get_user_deposit(env: Env, user: Address) -> Result<UserDeposit, Error> {
+    Ok(UserDeposit::get(&env, user))
+}
+
+/// Missing deposits are read as zero without touching their storage entries
+pub fn get_user_deposits(env: Env, users: Vec<Address>) -> Result<Vec<UserDeposit>, Error> {
+    let mut user_deposits = Vec::new(&env);
+
+    for user in users {
+        user_deposits.push_back(UserDeposit::get(&env, user));
+    }
+
+    Ok(user_deposits)
+}
+
+pub fn get_receive_amount(env: Env, input: u128, token_from: Token) -> Result<(u128, u128), Error> {
+    let receive_amount = Pool::get_with_rates(&env)?.get_receive_amount(input, token_from)?;
+    Ok((receive_amount.output, receive_amount.fee))
+}
+
+pub fn quote_swap(env: Env, input: u128, token_from: Token) -> Result<SwapQuote, Error> {
+    Pool::get_with_rates(&env)?.quote_swap(input, token_from)
+}
+
+pub fn get_send_amount(env: Env, output: u128, token_to: Token) -> Result<(u128, u128), Error> {
+    Pool::get_with_rates(&env)?.get_send_amount(output, token_to)
+}
+
+pub fn get_withdraw_amount(env: Env, lp_amount: u128) -> Result<WithdrawAmountView, Error> {
+    Ok(Pool::get_with_last_rates(&env)?
+        .get_withdraw_amount(lp_amount)?
+        .into())
+}
+
+/// Returns the minted LP amount and the imbalance fees in token precision
+pub fn get_deposit_amount(env: Env, amounts: (u128, u128)) -> Result<(u128, (u128, u128)), Error> {
+    let deposit_amount = Pool::get_with_rates(&env)?.get_deposit_amount(amounts.into())?;
+
+    Ok((deposit_amount.lp_amount, deposit_amount.fees.data))
+}
+
+pub fn preview_deposit(
+    env: Env,
+    user: Address,
+    amounts: (u128, u128),
+) -> Result<DepositPreview, Error> {
+    let user_deposit = UserDeposit::get(&env, user);
+    Pool::get_with_rates(&env)?.preview_deposit(&user_deposit, amounts.into())
+}
+
+pub fn preview_withdraw(
+    env: Env,
+    user: Address,
+    lp_amount: u128,
+) -> Result<WithdrawPreview, Error> {
+    let user_deposit = UserDeposit::get(&env, user);
+    Pool::get_with_last_rates(&env)?.preview_withdraw(&user_deposit, lp_amount)
+}
+
+pub fn get_admin(env: Env) -> Result<Address, Error> {
+    Ok(Admin::get(&env)?.0)
+}
+
+pub fn get_pool_info(env: Env, user: Option<Address>) -> Result<PoolInfo, Error> {
+    let pool = Pool::get_with_last_rates(&env)?;
+    let user_deposit = user
+        .map(|user| UserDeposit::get(&env, user))
+        .unwrap_or_default();
+
+    Ok(PoolInfo {
+        tokens: pool.tokens.data.clone(),
+        tokens_decimals: pool.tokens_decimals.data,
+        rates: pool.rates.data,
+        token_balances: pool.get_token_balances().data,
+        a: pool.a,
+        swap_fee_share: pool.swap_fee_share,
+        withdraw_fee_share: pool.withdraw_fee_share,
+        deposit_fee_share: pool.deposit_fee_share,
+        swap_fee_share_bp: Pool::fee_share_to_bp(pool.swap_fee_share),
+        withdraw_fee_share_bp: Pool::fee_share_to_bp(pool.withdraw_fee_share),
+        deposit_fee_share_bp: Pool::fee_share_to_bp(pool.deposit_fee_share),
+        admin_fee_share_bp: pool.admin_fee_share_bp,
+        offpeg_fee_multiplier_bp: pool.offpeg_fee_multiplier_bp,
+        total_lp_amount: pool.total_lp_amount,
+        virtual_price: pool.get_virtual_price()?,
+        admin: Admin::get(&env)?.0,
+        user_lp_amount: user_deposit.lp_amount,
+        user_pending_rewards: pool.get_pending(&user_deposit).data,
+    })
+}
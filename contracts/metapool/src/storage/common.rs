@@ -0,0 +1,80 @@
+use soroban_sdk::{contracttype, Address};
+
+#[contracttype]
+#[derive(Debug, Clone, Copy)]
+pub enum Direction {
+    A2B,
+    B2A,
+}
+
+impl Direction {
+    #[inline]
+    pub fn get_tokens(&self) -> (Token, Token) {
+        match self {
+            Direction::A2B => (Token::A, Token::B),
+            Direction::B2A => (Token::B, Token::A),
+        }
+    }
+}
+
+#[contracttype]
+#[derive(Debug, Clone, Copy)]
+#[repr(usize)]
+pub enum Token {
+    A = 0,
+    B = 1,
+}
+
+impl From<usize> for Token {
+    fn from(value: usize) -> Self {
+        match value {
+            0 => Token::A,
+            1 => Token::B,
+            _ => unreachable!(),
+        }
+    }
+}
+
+impl Token {
+    pub fn opposite(&self) -> Token {
+        match self {
+            Token::A => Token::B,
+            Token::B => Token::A,
+        }
+    }
+}
+
+/// Where the rate of a token against the pegged asset comes from
+#[contracttype]
+#[derive(Debug, Clone)]
+pub enum RateSource {
+    /// 1:1 after decimal normalisation
+    Fixed,
+    /// Rate provider contract of a yield-bearing or wrapped token
+    Provider(Address),
+    /// Price feed of a token with a moving exchange rate, such as EUR against USD
+    Oracle(PriceOracle),
+    /// Virtual price of the base pool LP
+    BasePool(Address),
+}
+
+#[contracttype]
+#[derive(Debug, Clone)]
+pub struct PriceOracle {
+    pub price_feed: Address,
+    /// seconds
+    pub max_price_age: u64,
+    /// Largest move of the price away from `peg_rate`
+    pub max_price_deviation_bp: u128,
+    /// Price when the oracle was set, `RATE_PRECISION` precision
+    pub peg_rate: u128,
+}
+
+impl From<Option<Address>> for RateSource {
+    fn from(rate_provider: Option<Address>) -> Self {
+        match rate_provider {
+            Some(rate_provider) => RateSource::Provider(rate_provider),
+            None => RateSource::Fixed,
+        }
+    }
+}
@@ -0,0 +1,4 @@
+pub mod common;
+pub mod double_values;
+pub mod pool;
+pub mod user_deposit;
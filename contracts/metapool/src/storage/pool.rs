@@ -0,0 +1,244 @@
+use proc_macros::{extend_ttl_info_instance, Instance, SorobanData, SorobanSimpleData, SymbolKey};
+use shared::{
+    price_feed::read_price_rate,
+    rate_provider::{read_provider_rate, RATE_PRECISION},
+    require,
+    soroban_data::SimpleSorobanData,
+    utils::safe_cast,
+    Error,
+};
+use soroban_sdk::{
+    auth::{ContractContext, InvokerContractAuthEntry, SubContractInvocation},
+    contracttype,
+    token::{self, TokenClient},
+    vec, Address, Env, IntoVal, Symbol, Vec,
+};
+
+use crate::base_pool::{BasePoolClient, BASE_TOKENS_COUNT};
+
+use super::{
+    common::{RateSource, Token},
+    double_values::{DoubleAddress, DoubleRateSource, DoubleU128, DoubleU32},
+};
+
+/// Token `B` is the LP of the base pool, `tokens` holds the base pool address for it
+#[contracttype]
+#[derive(Debug, Clone, SorobanData, SorobanSimpleData, SymbolKey, Instance)]
+#[extend_ttl_info_instance]
+pub struct Pool {
+    pub a: u128,
+
+    /// `Pool::FEE_DENOMINATOR` precision
+    pub swap_fee_share: u128,
+    /// `Pool::FEE_DENOMINATOR` precision
+    pub withdraw_fee_share: u128,
+    /// `Pool::FEE_DENOMINATOR` precision, charged on the imbalanced part of a deposit, zero
+    /// until the admin sets it so deposits keep their previous cost
+    pub deposit_fee_share: u128,
+    pub admin_fee_share_bp: u128,
+    /// Scales the swap fee up as the pool gets imbalanced, `Pool::BP` and below keep the fee flat
+    pub offpeg_fee_multiplier_bp: u128,
+    pub total_lp_amount: u128,
+
+    pub tokens: DoubleAddress,
+    pub tokens_decimals: DoubleU32,
+    pub rate_sources: DoubleRateSource,
+    /// Rates last read from `rate_sources`, `RATE_PRECISION` precision
+    pub rates: DoubleU128,
+    pub token_balances: DoubleU128,
+    pub acc_rewards_per_share_p: DoubleU128,
+    pub admin_fee_amount: DoubleU128,
+
+    /// Tokens of the base pool in its order, indices `1..=3` of the underlying swaps
+    pub base_tokens: Vec<Address>,
+}
+
+impl Pool {
+    #[allow(clippy::too_many_arguments)]
+    pub fn from_init_params(
+        a: u128,
+        token: Address,
+        base_pool: Address,
+        base_tokens: Vec<Address>,
+        decimals: u32,
+        base_virtual_price: u128,
+        fee_share_bp: u128,
+        admin_fee_share_bp: u128,
+    ) -> Self {
+        Pool {
+            a,
+
+            swap_fee_share: Self::fee_share_from_bp(fee_share_bp),
+            withdraw_fee_share: Self::fee_share_from_bp(fee_share_bp),
+            deposit_fee_share: 0,
+            admin_fee_share_bp,
+            offpeg_fee_multiplier_bp: 0,
+            total_lp_amount: 0,
+
+            tokens: DoubleAddress::from((token, base_pool.clone())),
+            tokens_decimals: DoubleU32::from((decimals, Self::SYSTEM_PRECISION)),
+            rate_sources: DoubleRateSource::from((
+                RateSource::Fixed,
+                RateSource::BasePool(base_pool),
+            )),
+            rates: DoubleU128::from((RATE_PRECISION, base_virtual_price)),
+            token_balances: DoubleU128::default(),
+            acc_rewards_per_share_p: DoubleU128::default(),
+            admin_fee_amount: DoubleU128::default(),
+
+            base_tokens,
+        }
+    }
+
+    /// Loads the pool with the rates refreshed from its rate providers and oracles
+    pub fn get_with_rates(env: &Env) -> Result<Self, Error> {
+        let mut pool = Self::get(env)?;
+        pool.update_rates(env)?;
+
+        Ok(pool)
+    }
+
+    /// Loads the pool for withdrawals and views, a rate source that cannot be read keeps
+    /// the last rate stored in the pool so a failing provider or oracle does not lock funds
+    pub fn get_with_last_rates(env: &Env) -> Result<Self, Error> {
+        let mut pool = Self::get(env)?;
+
+        for (index, rate_source) in pool.rate_sources.to_array().into_iter().enumerate() {
+            if let Ok(rate) = Self::read_rate(env, &rate_source) {
+                pool.rates[index] = rate;
+            }
+        }
+
+        Ok(pool)
+    }
+
+    pub fn update_rates(&mut self, env: &Env) -> Result<(), Error> {
+        for (index, rate_source) in self.rate_sources.to_array().into_iter().enumerate() {
+            self.rates[index] = Self::read_rate(env, &rate_source)?;
+        }
+
+        Ok(())
+    }
+
+    /// `RATE_PRECISION` precision
+    pub fn read_rate(env: &Env, rate_source: &RateSource) -> Result<u128, Error> {
+        let rate = match rate_source {
+            RateSource::Fixed => RATE_PRECISION,
+            RateSource::Provider(rate_provider) => read_provider_rate(env, rate_provider)?,
+            RateSource::Oracle(oracle) => {
+                let rate = read_price_rate(env, &oracle.price_feed, oracle.max_price_age)?;
+
+                let max_deviation = oracle
+                    .peg_rate
+                    .checked_mul(oracle.max_price_deviation_bp)
+                    .ok_or(Error::U256Overflow)?;
+                require!(
+                    rate.abs_diff(oracle.peg_rate)
+                        .checked_mul(Self::BP)
+                        .is_some_and(|deviation| deviation <= max_deviation),
+                    Error::PriceDeviation
+                );
+
+                rate
+            }
+            RateSource::BasePool(base_pool) => BasePoolClient::new(env, base_pool)
+                .try_get_virtual_price()
+                .ok()
+                .and_then(|rate| rate.ok())
+                .ok_or(Error::InvalidRate)?,
+        };
+
+        require!(rate > 0, Error::InvalidRate);
+
+        Ok(rate)
+    }
+
+    #[inline]
+    pub fn has_dynamic_rates(&self) -> bool {
+        self.rate_sources
+            .to_array()
+            .iter()
+            .any(|rate_source| !matches!(rate_source, RateSource::Fixed))
+    }
+
+    #[inline]
+    pub fn fee_share_from_bp(fee_share_bp: u128) -> u128 {
+        fee_share_bp * (Self::FEE_DENOMINATOR / Self::BP)
+    }
+
+    #[inline]
+    pub fn fee_share_to_bp(fee_share: u128) -> u128 {
+        fee_share / (Self::FEE_DENOMINATOR / Self::BP)
+    }
+
+    /// Moves the token at `index`, the base pool LP is moved between deposits in the base pool
+    pub fn transfer(
+        &self,
+        env: &Env,
+        index: usize,
+        from: &Address,
+        to: &Address,
+        amount: u128,
+    ) -> Result<(), Error> {
+        if amount == 0 {
+            return Ok(());
+        }
+
+        match Token::from(index) {
+            Token::A => {
+                token::Client::new(env, &self.tokens[index]).transfer(
+                    from,
+                    to,
+                    &safe_cast(amount)?,
+                );
+            }
+            Token::B => self.get_base_pool(env).transfer_lp(from, to, &amount),
+        }
+
+        Ok(())
+    }
+
+    #[inline]
+    pub fn get_base_pool(&self, env: &Env) -> BasePoolClient<'_> {
+        BasePoolClient::new(env, &self.tokens[Token::B])
+    }
+
+    #[inline]
+    pub fn get_base_token(&self, env: &Env, index: u32) -> TokenClient<'_> {
+        token::Client::new(env, &self.base_tokens.get_unchecked(index))
+    }
+
+    /// Lets the base pool pull the `amounts` of its tokens from the pool in the next call
+    pub fn authorize_base_transfers(
+        &self,
+        env: &Env,
+        amounts: &[u128; BASE_TOKENS_COUNT as usize],
+    ) -> Result<(), Error> {
+        let mut auth_entries = Vec::new(env);
+
+        for (index, amount) in amounts.iter().enumerate() {
+            if *amount == 0 {
+                continue;
+            }
+
+            let args = (
+                env.current_contract_address(),
+                self.tokens[Token::B].clone(),
+                safe_cast::<_, i128>(*amount)?,
+            );
+
+            auth_entries.push_back(InvokerContractAuthEntry::Contract(SubContractInvocation {
+                context: ContractContext {
+                    contract: self.base_tokens.get_unchecked(index as u32),
+                    fn_name: Symbol::new(env, "transfer"),
+                    args: args.into_val(env),
+                },
+                sub_invocations: vec![env],
+            }));
+        }
+
+        env.authorize_as_current_contract(auth_entries);
+
+        Ok(())
+    }
+}
@@ -1,17 +1,24 @@
-use shared::{utils::extend_ttl_instance, Error};
-use soroban_sdk::{contract, contractimpl, Address, BytesN, Env};
-use storage::Admin;
+use shared::{utils::extend_ttl_instance, Error, Event};
+use soroban_sdk::{contract, contractimpl, Address, BytesN, Env, Vec};
+use storage::Upgrader;
 
 use crate::{
+    events::Upgraded,
     methods::{
-        internal::pool_view::WithdrawAmountView,
+        internal::pool_view::{
+            DepositPreview, PoolInfo, SwapQuote, WithdrawAmountView, WithdrawPreview,
+        },
         public::{
-            claim_admin_fee, claim_rewards, deposit, initialize, set_admin, set_admin_fee_share,
-            set_fee_share, swap, withdraw,
+            claim_admin_fee, claim_rewards, deposit, initialize, migrate, set_admin,
+            set_admin_fee_share, set_deposit_fee_share, set_offpeg_fee_multiplier, set_oracle,
+            set_rate_provider, set_swap_fee_share, set_upgrader, set_withdraw_fee_share, swap,
+            withdraw,
         },
         view::{
-            get_admin, get_d, get_deposit_amount, get_pool, get_receive_amount, get_send_amount,
-            get_user_deposit, get_withdraw_amount, pending_reward,
+            get_admin, get_d, get_deposit_amount, get_pool, get_pool_info, get_receive_amount,
+            get_send_amount, get_upgrader, get_user_deposit, get_user_deposits,
+            get_withdraw_amount, pending_reward, pending_rewards, preview_deposit,
+            preview_withdraw, quote_swap,
         },
     },
     storage::{
@@ -35,6 +42,7 @@ impl PoolContract {
         token_b: Address,
         fee_share_bp: u128,
         admin_fee_share_bp: u128,
+        upgrader: Address,
     ) -> Result<(), Error> {
         initialize(
             env,
@@ -44,6 +52,7 @@ impl PoolContract {
             token_b,
             fee_share_bp,
             admin_fee_share_bp,
+            upgrader,
         )
     }
 
@@ -110,10 +119,59 @@ impl PoolContract {
         set_admin_fee_share(env, admin_fee_share_bp)
     }
 
-    pub fn set_fee_share(env: Env, fee_share_bp: u128) -> Result<(), Error> {
+    pub fn set_swap_fee_share(env: Env, swap_fee_share: u128) -> Result<(), Error> {
+        extend_ttl_instance(&env);
+
+        set_swap_fee_share(env, swap_fee_share)
+    }
+
+    pub fn set_withdraw_fee_share(env: Env, withdraw_fee_share: u128) -> Result<(), Error> {
+        extend_ttl_instance(&env);
+
+        set_withdraw_fee_share(env, withdraw_fee_share)
+    }
+
+    pub fn set_deposit_fee_share(env: Env, deposit_fee_share: u128) -> Result<(), Error> {
+        extend_ttl_instance(&env);
+
+        set_deposit_fee_share(env, deposit_fee_share)
+    }
+
+    pub fn set_offpeg_fee_multiplier(
+        env: Env,
+        offpeg_fee_multiplier_bp: u128,
+    ) -> Result<(), Error> {
         extend_ttl_instance(&env);
 
-        set_fee_share(env, fee_share_bp)
+        set_offpeg_fee_multiplier(env, offpeg_fee_multiplier_bp)
+    }
+
+    pub fn set_rate_provider(
+        env: Env,
+        token: Token,
+        rate_provider: Option<Address>,
+    ) -> Result<(), Error> {
+        extend_ttl_instance(&env);
+
+        set_rate_provider(env, token, rate_provider)
+    }
+
+    pub fn set_oracle(
+        env: Env,
+        token: Token,
+        price_feed: Address,
+        max_price_age: u64,
+        max_price_deviation_bp: u128,
+    ) -> Result<(), Error> {
+        extend_ttl_instance(&env);
+
+        set_oracle(
+            env,
+            token,
+            price_feed,
+            max_price_age,
+            max_price_deviation_bp,
+        )
     }
 
     // ----------- View -----------
@@ -122,6 +180,10 @@ impl PoolContract {
         pending_reward(env, user)
     }
 
+    pub fn pending_rewards(env: Env, users: Vec<Address>) -> Result<Vec<(u128, u128)>, Error> {
+        pending_rewards(env, users)
+    }
+
     pub fn get_pool(env: Env) -> Result<Pool, Error> {
         get_pool(env)
     }
@@ -130,6 +192,10 @@ impl PoolContract {
         get_user_deposit(env, user)
     }
 
+    pub fn get_user_deposits(env: Env, users: Vec<Address>) -> Result<Vec<UserDeposit>, Error> {
+        get_user_deposits(env, users)
+    }
+
     pub fn get_d(env: Env) -> Result<u128, Error> {
         get_d(env)
     }
@@ -142,6 +208,10 @@ impl PoolContract {
         get_receive_amount(env, input, token_from)
     }
 
+    pub fn quote_swap(env: Env, input: u128, token_from: Token) -> Result<SwapQuote, Error> {
+        quote_swap(env, input, token_from)
+    }
+
     pub fn get_send_amount(env: Env, output: u128, token_to: Token) -> Result<(u128, u128), Error> {
         get_send_amount(env, output, token_to)
     }
@@ -150,20 +220,64 @@ impl PoolContract {
         get_withdraw_amount(env, lp_amount)
     }
 
-    pub fn get_deposit_amount(env: Env, amounts: (u128, u128)) -> Result<u128, Error> {
+    pub fn get_deposit_amount(
+        env: Env,
+        amounts: (u128, u128),
+    ) -> Result<(u128, (u128, u128)), Error> {
         get_deposit_amount(env, amounts)
     }
 
+    pub fn preview_deposit(
+        env: Env,
+        user: Address,
+        amounts: (u128, u128),
+    ) -> Result<DepositPreview, Error> {
+        preview_deposit(env, user, amounts)
+    }
+
+    pub fn preview_withdraw(
+        env: Env,
+        user: Address,
+        lp_amount: u128,
+    ) -> Result<WithdrawPreview, Error> {
+        preview_withdraw(env, user, lp_amount)
+    }
+
     pub fn get_admin(env: Env) -> Result<Address, Error> {
         get_admin(env)
     }
 
+    pub fn get_upgrader(env: Env) -> Result<Address, Error> {
+        get_upgrader(env)
+    }
+
+    pub fn get_pool_info(env: Env, user: Option<Address>) -> Result<PoolInfo, Error> {
+        get_pool_info(env, user)
+    }
+
     // ----------- Upgrade -----------
 
+    /// Upgrades by the upgrader, the factory for the pools it deploys
     pub fn upgrade(env: Env, new_wasm_hash: BytesN<32>) -> Result<(), Error> {
-        Admin::require_exist_auth(&env)?;
+        Upgrader::require_exist_auth(&env)?;
+
+        env.deployer()
+            .update_current_contract_wasm(new_wasm_hash.clone());
+
+        Upgraded { new_wasm_hash }.publish(&env);
 
-        env.deployer().update_current_contract_wasm(new_wasm_hash);
         Ok(())
     }
+
+    pub fn set_upgrader(env: Env, new_upgrader: Address) -> Result<(), Error> {
+        extend_ttl_instance(&env);
+
+        set_upgrader(env, new_upgrader)
+    }
+
+    pub fn migrate(env: Env) -> Result<(), Error> {
+        extend_ttl_instance(&env);
+
+        migrate(env)
+    }
 }
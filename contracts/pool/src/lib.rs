@@ -1,3 +1,6 @@
+//! The two-token StableSwap pool, superseded by the generic `stable_pool`. It is kept for the
+//! pools already deployed and the factory's existing pool type and only takes fixes, new
+//! features go to `stable_pool` on top of the math and storage in `shared` and `storage`
 #![no_std]
 
 mod contract;
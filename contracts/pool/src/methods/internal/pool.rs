@@ -1,9 +1,6 @@
-use core::cmp::Ordering;
-
-use ethnum::U256;
 use shared::{
     require,
-    utils::{num::*, safe_cast},
+    utils::{num, safe_cast},
     Error,
 };
 use soroban_sdk::{Address, Env};
@@ -15,16 +12,22 @@ use crate::storage::{
     user_deposit::UserDeposit,
 };
 
-use super::pool_view::WithdrawAmount;
+use super::pool_view::{DepositAmount, WithdrawAmount};
 
 impl Pool {
     pub const BP: u128 = 10000;
+    /// Swap, withdraw and deposit fee shares are fractions of it, one basis point is 100
+    pub const FEE_DENOMINATOR: u128 = 1_000_000;
 
     pub(crate) const MAX_A: u128 = 60;
-    pub(crate) const MAX_TOKEN_BALANCE: u128 = 2u128.pow(40);
+    /// Ceiling of the sum of the system precision balances, `get_d` and `get_y` stay within
+    /// `U256` below it
+    pub(crate) const MAX_TOKEN_BALANCE: u128 = 2u128.pow(60);
     pub(crate) const SYSTEM_PRECISION: u32 = 3;
+    pub(crate) const MAX_OFFPEG_FEE_ITERATIONS: usize = 8;
 
     pub const P: u128 = 48;
+    pub const VIRTUAL_PRICE_PRECISION: u128 = 1_000_000_000_000_000_000;
 
     #[allow(clippy::too_many_arguments)]
     pub fn swap(
@@ -73,15 +76,11 @@ impl Pool {
         sender: Address,
         user_deposit: &mut UserDeposit,
         min_lp_amount: u128,
-    ) -> Result<(DoubleU128, u128), Error> {
+    ) -> Result<(DepositAmount, DoubleU128), Error> {
         let current_contract = env.current_contract_address();
 
-        if self.total_lp_amount == 0 {
-            require!(amounts.data.0 == amounts.data.1, Error::InvalidFirstDeposit);
-        }
-
         let deposit_amount = self.get_deposit_amount(amounts.clone())?;
-        self.token_balances = deposit_amount.new_token_balances;
+        self.token_balances = deposit_amount.new_token_balances.clone();
 
         require!(deposit_amount.lp_amount >= min_lp_amount, Error::Slippage);
 
@@ -97,6 +96,10 @@ impl Pool {
             );
         }
 
+        for (index, fee) in deposit_amount.fees.to_array().into_iter().enumerate() {
+            self.add_rewards(fee, index.into());
+        }
+
         let rewards = self.deposit_lp(user_deposit, deposit_amount.lp_amount)?;
 
         for (index, reward) in rewards.to_array().into_iter().enumerate() {
@@ -111,7 +114,7 @@ impl Pool {
             );
         }
 
-        Ok((rewards, deposit_amount.lp_amount))
+        Ok((deposit_amount, rewards))
     }
 
     pub fn withdraw(
@@ -217,11 +220,13 @@ impl Pool {
     }
 
     pub(crate) fn add_rewards(&mut self, mut reward_amount: u128, token: Token) {
-        if self.total_lp_amount > 0 {
-            let admin_fee_rewards = reward_amount * self.admin_fee_share_bp / Pool::BP;
-            reward_amount -= admin_fee_rewards;
-            self.acc_rewards_per_share_p[token] +=
-                (reward_amount << Pool::P) / self.total_lp_amount;
+        let admin_fee_rewards = reward_amount * self.admin_fee_share_bp / Pool::BP;
+        reward_amount -= admin_fee_rewards;
+
+        if let Some(rewards_per_share_p) =
+            (reward_amount << Pool::P).checked_div(self.total_lp_amount)
+        {
+            self.acc_rewards_per_share_p[token] += rewards_per_share_p;
             self.admin_fee_amount[token] += admin_fee_rewards;
         }
     }
@@ -246,64 +251,69 @@ impl Pool {
         ))
     }
 
-    // y = (sqrt(x(4AD³ + x (4A(D - x) - D )²)) + x (4A(D - x) - D ))/8Ax
+    /// Balance of one token that keeps the invariant at `d` along with the balance `native_x` of
+    /// the other one
     pub fn get_y(&self, native_x: u128, d: u128) -> Result<u128, Error> {
-        let a4 = self.a << 2;
-
-        let int_a4: i128 = safe_cast(a4)?;
-        let int_d: i128 = safe_cast(d)?;
-        let int_native_x: i128 = safe_cast(native_x)?;
-
-        let ddd = U256::new(d * d) * d;
-        // 4A(D - x) - D
-        let part1 = int_a4 * (int_d - int_native_x) - int_d;
-        // x * (4AD³ + x(part1²))
-        let part2 = (ddd * a4 + (U256::new(safe_cast(part1 * part1)?) * native_x)) * native_x;
-        // (sqrt(part2) + x(part1))
-        let sqrt_sum = safe_cast::<_, i128>(sqrt(&part2).as_u128())? + (int_native_x * part1);
-        // (sqrt(part2) + x(part1)) / 8Ax)
-        Ok(safe_cast::<_, u128>(sqrt_sum)? / ((self.a << 3) * native_x))
+        num::get_y(self.a, [native_x, 0], 1, d)
     }
 
     pub fn get_current_d(&self) -> Result<u128, Error> {
-        self.get_d(self.token_balances[0], self.token_balances[1])
+        self.get_d_for_balances(&self.token_balances)
     }
 
-    pub fn get_d(&self, x: u128, y: u128) -> Result<u128, Error> {
-        let xy: u128 = x * y;
-        // Axy(x+y)
-        let p1 = U256::new(self.a * (x + y) * xy);
-
-        // xy(4A - 1) / 3
-        let p2 = U256::new(xy * ((self.a << 2) - 1) / 3);
-
-        // sqrt(p1² + p2³)
-        let p3 = sqrt(&(square(p1)? + cube(p2)?));
-
-        // cbrt(p1 + p3) + cbrt(p1 - p3)
-        let mut d = cbrt(&(p1.checked_add(p3).ok_or(Error::U256Overflow)?))?;
-        if p3.gt(&p1) {
-            d -= cbrt(&(p3 - p1))?;
-        } else {
-            d += cbrt(&(p1 - p3))?;
+    /// `get_d` of system precision balances scaled by the rates
+    pub fn get_d_for_balances(&self, balances_sp: &DoubleU128) -> Result<u128, Error> {
+        self.get_d(
+            self.scale_by_rate(balances_sp[0], 0),
+            self.scale_by_rate(balances_sp[1], 1),
+        )
+    }
+
+    /// Invariant of the current balances, the LP supply tracks it 1:1 while all rates are fixed
+    pub fn get_d0(&self) -> Result<u128, Error> {
+        if !self.has_dynamic_rates() {
+            return Ok(self.total_lp_amount);
         }
 
-        Ok(d << 1)
+        self.get_current_d()
     }
 
-    pub(crate) fn amount_to_system_precision(&self, amount: u128, decimals: u32) -> u128 {
-        match decimals.cmp(&Self::SYSTEM_PRECISION) {
-            Ordering::Greater => amount / (10u128.pow(decimals - Self::SYSTEM_PRECISION)),
-            Ordering::Less => amount * (10u128.pow(Self::SYSTEM_PRECISION - decimals)),
-            Ordering::Equal => amount,
+    /// Invariant the imbalance fee of a deposit is measured against, unlike `get_d0` it counts
+    /// the part of the balances the LP supply does not cover
+    pub fn get_imbalance_fee_d0(&self) -> Result<u128, Error> {
+        if self.total_lp_amount == 0 {
+            return Ok(0);
         }
+
+        self.get_current_d()
+    }
+
+    pub fn get_d(&self, x: u128, y: u128) -> Result<u128, Error> {
+        num::get_d(self.a, [x, y])
+    }
+
+    pub(crate) fn get_scaled_balances(&self) -> DoubleU128 {
+        DoubleU128::from((
+            self.scale_by_rate(self.token_balances[0], 0),
+            self.scale_by_rate(self.token_balances[1], 1),
+        ))
+    }
+
+    /// Values a system precision amount of the token in the pegged asset, the curve works on
+    /// scaled amounts
+    pub(crate) fn scale_by_rate(&self, amount_sp: u128, index: usize) -> u128 {
+        num::scale_by_rate(amount_sp, self.rates[index])
+    }
+
+    pub(crate) fn unscale_by_rate(&self, amount: u128, index: usize) -> u128 {
+        num::unscale_by_rate(amount, self.rates[index])
+    }
+
+    pub(crate) fn amount_to_system_precision(&self, amount: u128, decimals: u32) -> u128 {
+        num::to_system_precision(amount, decimals, Self::SYSTEM_PRECISION)
     }
 
     pub(crate) fn amount_from_system_precision(&self, amount: u128, decimals: u32) -> u128 {
-        match decimals.cmp(&Self::SYSTEM_PRECISION) {
-            Ordering::Greater => amount * (10u128.pow(decimals - Self::SYSTEM_PRECISION)),
-            Ordering::Less => amount / (10u128.pow(Self::SYSTEM_PRECISION - decimals)),
-            Ordering::Equal => amount,
-        }
+        num::from_system_precision(amount, decimals, Self::SYSTEM_PRECISION)
     }
 }
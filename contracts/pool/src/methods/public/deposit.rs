@@ -1,8 +1,9 @@
 use shared::{soroban_data::SimpleSorobanData, Error, Event};
 use soroban_sdk::{Address, Env};
+use storage::OperationNonce;
 
 use crate::{
-    events::{Deposit, RewardsClaimed},
+    events::{Deposit, RewardsClaimed, Sync},
     storage::{double_values::DoubleU128, pool::Pool, user_deposit::UserDeposit},
 };
 
@@ -13,11 +14,11 @@ pub fn deposit(
     min_lp_amount: u128,
 ) -> Result<(), Error> {
     sender.require_auth();
-    let mut pool = Pool::get(&env)?;
+    let mut pool = Pool::get_with_rates(&env)?;
     let mut user_deposit = UserDeposit::get(&env, sender.clone());
     let amounts = DoubleU128::from(amounts);
 
-    let (rewards, lp_amount) = pool.deposit(
+    let (deposit_amount, rewards) = pool.deposit(
         &env,
         amounts.clone(),
         sender.clone(),
@@ -30,8 +31,9 @@ pub fn deposit(
 
     Deposit {
         user: sender.clone(),
-        lp_amount,
+        lp_amount: deposit_amount.lp_amount,
         amounts: amounts.data,
+        fees: deposit_amount.fees.data,
     }
     .publish(&env);
 
@@ -43,5 +45,7 @@ pub fn deposit(
         .publish(&env);
     }
 
+    Sync::from_pool(&pool, OperationNonce::next(&env)).publish(&env);
+
     Ok(())
 }
@@ -1,6 +1,6 @@
 use shared::{require, soroban_data::SimpleSorobanData, Error};
 use soroban_sdk::{token, Address, Env};
-use storage::Admin;
+use storage::{Admin, Upgrader};
 
 use crate::storage::pool::Pool;
 
@@ -13,6 +13,7 @@ pub fn initialize(
     token_b: Address,
     fee_share_bp: u128,
     admin_fee_share_bp: u128,
+    upgrader: Address,
 ) -> Result<(), Error> {
     require!(!Pool::has(&env), Error::Initialized);
 
@@ -33,6 +34,7 @@ pub fn initialize(
     )
     .save(&env);
     Admin(admin).save(&env);
+    Upgrader(upgrader).save(&env);
 
     Ok(())
 }
@@ -0,0 +1,14 @@
+use shared::{soroban_data::SimpleSorobanData, Error};
+use soroban_sdk::Env;
+use storage::Admin;
+
+use crate::storage::{legacy_pool::LegacyPool, pool::Pool};
+
+/// Converts the pool stored by the previous contract version, to be called right after `upgrade`
+pub fn migrate(env: Env) -> Result<(), Error> {
+    Admin::require_exist_auth(&env)?;
+
+    Pool::from(LegacyPool::get(&env)?).save(&env);
+
+    Ok(())
+}
@@ -1,15 +1,17 @@
+mod admin;
 mod claim_admin_fee;
 mod claim_rewards;
 mod deposit;
 mod initialize;
-mod admin;
+mod migrate;
 mod swap;
 mod withdraw;
 
+pub use admin::*;
 pub use claim_admin_fee::*;
 pub use claim_rewards::*;
 pub use deposit::*;
 pub use initialize::*;
-pub use admin::*;
+pub use migrate::*;
 pub use swap::*;
 pub use withdraw::*;
@@ -1,8 +1,9 @@
 use shared::{soroban_data::SimpleSorobanData, Error, Event};
 use soroban_sdk::{Address, Env};
+use storage::OperationNonce;
 
 use crate::{
-    events::Swapped,
+    events::{Swapped, Sync},
     storage::{common::Direction, pool::Pool},
 };
 
@@ -15,7 +16,7 @@ pub fn swap(
     direction: Direction,
 ) -> Result<u128, Error> {
     sender.require_auth();
-    let mut pool = Pool::get(&env)?;
+    let mut pool = Pool::get_with_rates(&env)?;
 
     let (to_amount, fee) = pool.swap(
         &env,
@@ -41,5 +42,7 @@ pub fn swap(
     }
     .publish(&env);
 
+    Sync::from_pool(&pool, OperationNonce::next(&env)).publish(&env);
+
     Ok(to_amount)
 }
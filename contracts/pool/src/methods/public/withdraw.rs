@@ -1,14 +1,15 @@
 use shared::{soroban_data::SimpleSorobanData, Error, Event};
 use soroban_sdk::{Address, Env};
+use storage::OperationNonce;
 
 use crate::{
-    events::{RewardsClaimed, Withdraw},
+    events::{RewardsClaimed, Sync, Withdraw},
     storage::{pool::Pool, user_deposit::UserDeposit},
 };
 
 pub fn withdraw(env: Env, sender: Address, lp_amount: u128) -> Result<(), Error> {
     sender.require_auth();
-    let mut pool = Pool::get(&env)?;
+    let mut pool = Pool::get_with_last_rates(&env)?;
     let mut user_deposit = UserDeposit::get(&env, sender.clone());
 
     let (withdraw_amount, rewards) =
@@ -33,5 +34,7 @@ pub fn withdraw(env: Env, sender: Address, lp_amount: u128) -> Result<(), Error>
         .publish(&env);
     }
 
+    Sync::from_pool(&pool, OperationNonce::next(&env)).publish(&env);
+
     Ok(())
 }
@@ -1,4 +1,4 @@
-use soroban_sdk::contracttype;
+use soroban_sdk::{contracttype, Address};
 
 #[contracttype]
 #[derive(Debug, Clone, Copy)]
@@ -43,3 +43,36 @@ impl Token {
         }
     }
 }
+
+/// Where the rate of a token against the pegged asset comes from
+#[contracttype]
+#[derive(Debug, Clone)]
+pub enum RateSource {
+    /// 1:1 after decimal normalisation
+    Fixed,
+    /// Rate provider contract of a yield-bearing or wrapped token
+    Provider(Address),
+    /// Price feed of a token with a moving exchange rate, such as EUR against USD
+    Oracle(PriceOracle),
+}
+
+#[contracttype]
+#[derive(Debug, Clone)]
+pub struct PriceOracle {
+    pub price_feed: Address,
+    /// seconds
+    pub max_price_age: u64,
+    /// Largest move of the price away from `peg_rate`
+    pub max_price_deviation_bp: u128,
+    /// Price when the oracle was set, `RATE_PRECISION` precision
+    pub peg_rate: u128,
+}
+
+impl From<Option<Address>> for RateSource {
+    fn from(rate_provider: Option<Address>) -> Self {
+        match rate_provider {
+            Some(rate_provider) => RateSource::Provider(rate_provider),
+            None => RateSource::Fixed,
+        }
+    }
+}
@@ -5,7 +5,7 @@ use core::{
     ops::{Index, IndexMut},
 };
 
-use super::common::Token;
+use super::common::{RateSource, Token};
 
 #[macro_export]
 macro_rules! double_value {
@@ -79,6 +79,15 @@ macro_rules! double_value {
 double_value!(DoubleAddress, Address);
 double_value!(DoubleU128, u128);
 double_value!(DoubleU32, u32);
+double_value!(DoubleRateSource, RateSource);
+
+impl Default for DoubleRateSource {
+    fn default() -> Self {
+        Self {
+            data: (RateSource::Fixed, RateSource::Fixed),
+        }
+    }
+}
 
 #[allow(clippy::derivable_impls)]
 impl Default for DoubleU128 {
@@ -1,4 +1,5 @@
 pub mod common;
 pub mod double_values;
+pub mod legacy_pool;
 pub mod pool;
 pub mod user_deposit;
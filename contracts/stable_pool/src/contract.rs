@@ -0,0 +1,269 @@
+use shared::{utils::extend_ttl_instance, Error, Event};
+use soroban_sdk::{contract, contractimpl, Address, BytesN, Env, Vec};
+use storage::Upgrader;
+
+use crate::{
+    events::Upgraded,
+    methods::{
+        internal::pool_view::{
+            DepositPreview, PoolInfo, SwapQuote, WithdrawAmount, WithdrawPreview,
+        },
+        public::{
+            claim_admin_fee, claim_rewards, deposit, initialize, set_admin, set_admin_fee_share,
+            set_deposit_fee_share, set_offpeg_fee_multiplier, set_oracle, set_rate_provider,
+            set_swap_fee_share, set_withdraw_fee_share, swap, withdraw,
+        },
+        view::{
+            get_admin, get_d, get_deposit_amount, get_pool, get_pool_info, get_receive_amount,
+            get_send_amount, get_user_deposit, get_user_deposits, get_withdraw_amount,
+            pending_reward, pending_rewards, preview_deposit, preview_withdraw, quote_swap,
+        },
+    },
+    storage::{pool::Pool, user_deposit::UserDeposit},
+};
+
+#[contract]
+pub struct PoolContract;
+
+#[contractimpl]
+impl PoolContract {
+    /// Takes 2 to 8 `tokens`, every per-token argument and result is ordered the same way
+    pub fn initialize(
+        env: Env,
+        admin: Address,
+        a: u128,
+        tokens: Vec<Address>,
+        fee_share_bp: u128,
+        admin_fee_share_bp: u128,
+        upgrader: Address,
+    ) -> Result<(), Error> {
+        initialize(
+            env,
+            admin,
+            a,
+            tokens,
+            fee_share_bp,
+            admin_fee_share_bp,
+            upgrader,
+        )
+    }
+
+    pub fn deposit(
+        env: Env,
+        sender: Address,
+        amounts: Vec<u128>,
+        min_lp_amount: u128,
+    ) -> Result<(), Error> {
+        extend_ttl_instance(&env);
+
+        deposit(env, sender, amounts, min_lp_amount)
+    }
+
+    pub fn withdraw(env: Env, sender: Address, lp_amount: u128) -> Result<(), Error> {
+        extend_ttl_instance(&env);
+
+        withdraw(env, sender, lp_amount)
+    }
+
+    pub fn swap(
+        env: Env,
+        sender: Address,
+        recipient: Address,
+        amount_in: u128,
+        receive_amount_min: u128,
+        token_from: u32,
+        token_to: u32,
+    ) -> Result<u128, Error> {
+        extend_ttl_instance(&env);
+
+        swap(
+            env,
+            sender,
+            recipient,
+            amount_in,
+            receive_amount_min,
+            token_from,
+            token_to,
+        )
+    }
+
+    pub fn claim_rewards(env: Env, sender: Address) -> Result<(), Error> {
+        extend_ttl_instance(&env);
+
+        claim_rewards(env, sender)
+    }
+
+    // ----------- Admin -----------
+
+    pub fn claim_admin_fee(env: Env) -> Result<(), Error> {
+        extend_ttl_instance(&env);
+
+        claim_admin_fee(env)
+    }
+
+    pub fn set_admin(env: Env, new_admin: Address) -> Result<(), Error> {
+        extend_ttl_instance(&env);
+
+        set_admin(env, new_admin)
+    }
+
+    pub fn set_admin_fee_share(env: Env, admin_fee_share_bp: u128) -> Result<(), Error> {
+        extend_ttl_instance(&env);
+
+        set_admin_fee_share(env, admin_fee_share_bp)
+    }
+
+    pub fn set_swap_fee_share(env: Env, swap_fee_share: u128) -> Result<(), Error> {
+        extend_ttl_instance(&env);
+
+        set_swap_fee_share(env, swap_fee_share)
+    }
+
+    pub fn set_withdraw_fee_share(env: Env, withdraw_fee_share: u128) -> Result<(), Error> {
+        extend_ttl_instance(&env);
+
+        set_withdraw_fee_share(env, withdraw_fee_share)
+    }
+
+    pub fn set_deposit_fee_share(env: Env, deposit_fee_share: u128) -> Result<(), Error> {
+        extend_ttl_instance(&env);
+
+        set_deposit_fee_share(env, deposit_fee_share)
+    }
+
+    pub fn set_offpeg_fee_multiplier(
+        env: Env,
+        offpeg_fee_multiplier_bp: u128,
+    ) -> Result<(), Error> {
+        extend_ttl_instance(&env);
+
+        set_offpeg_fee_multiplier(env, offpeg_fee_multiplier_bp)
+    }
+
+    pub fn set_rate_provider(
+        env: Env,
+        token: u32,
+        rate_provider: Option<Address>,
+    ) -> Result<(), Error> {
+        extend_ttl_instance(&env);
+
+        set_rate_provider(env, token, rate_provider)
+    }
+
+    pub fn set_oracle(
+        env: Env,
+        token: u32,
+        price_feed: Address,
+        max_price_age: u64,
+        max_price_deviation_bp: u128,
+    ) -> Result<(), Error> {
+        extend_ttl_instance(&env);
+
+        set_oracle(
+            env,
+            token,
+            price_feed,
+            max_price_age,
+            max_price_deviation_bp,
+        )
+    }
+
+    // ----------- View -----------
+
+    pub fn pending_reward(env: Env, user: Address) -> Result<Vec<u128>, Error> {
+        pending_reward(env, user)
+    }
+
+    pub fn pending_rewards(env: Env, users: Vec<Address>) -> Result<Vec<Vec<u128>>, Error> {
+        pending_rewards(env, users)
+    }
+
+    pub fn get_pool(env: Env) -> Result<Pool, Error> {
+        get_pool(env)
+    }
+
+    pub fn get_user_deposit(env: Env, user: Address) -> Result<UserDeposit, Error> {
+        get_user_deposit(env, user)
+    }
+
+    pub fn get_user_deposits(env: Env, users: Vec<Address>) -> Result<Vec<UserDeposit>, Error> {
+        get_user_deposits(env, users)
+    }
+
+    pub fn get_d(env: Env) -> Result<u128, Error> {
+        get_d(env)
+    }
+
+    pub fn get_receive_amount(
+        env: Env,
+        input: u128,
+        token_from: u32,
+        token_to: u32,
+    ) -> Result<(u128, u128), Error> {
+        get_receive_amount(env, input, token_from, token_to)
+    }
+
+    pub fn quote_swap(
+        env: Env,
+        input: u128,
+        token_from: u32,
+        token_to: u32,
+    ) -> Result<SwapQuote, Error> {
+        quote_swap(env, input, token_from, token_to)
+    }
+
+    pub fn get_send_amount(
+        env: Env,
+        output: u128,
+        token_from: u32,
+        token_to: u32,
+    ) -> Result<(u128, u128), Error> {
+        get_send_amount(env, output, token_from, token_to)
+    }
+
+    pub fn get_withdraw_amount(env: Env, lp_amount: u128) -> Result<WithdrawAmount, Error> {
+        get_withdraw_amount(env, lp_amount)
+    }
+
+    pub fn get_deposit_amount(env: Env, amounts: Vec<u128>) -> Result<(u128, Vec<u128>), Error> {
+        get_deposit_amount(env, amounts)
+    }
+
+    pub fn preview_deposit(
+        env: Env,
+        user: Address,
+        amounts: Vec<u128>,
+    ) -> Result<DepositPreview, Error> {
+        preview_deposit(env, user, amounts)
+    }
+
+    pub fn preview_withdraw(
+        env: Env,
+        user: Address,
+        lp_amount: u128,
+    ) -> Result<WithdrawPreview, Error> {
+        preview_withdraw(env, user, lp_amount)
+    }
+
+    pub fn get_admin(env: Env) -> Result<Address, Error> {
+        get_admin(env)
+    }
+
+    pub fn get_pool_info(env: Env, user: Option<Address>) -> Result<PoolInfo, Error> {
+        get_pool_info(env, user)
+    }
+
+    // ----------- Upgrade -----------
+
+    /// Upgrades by the upgrader, the factory for the pools it deploys
+    pub fn upgrade(env: Env, new_wasm_hash: BytesN<32>) -> Result<(), Error> {
+        Upgrader::require_exist_auth(&env)?;
+
+        env.deployer()
+            .update_current_contract_wasm(new_wasm_hash.clone());
+
+        Upgraded { new_wasm_hash }.publish(&env);
+
+        Ok(())
+    }
+}
@@ -0,0 +1,140 @@
+use soroban_sdk::{contracttype, Address, BytesN, Vec};
+
+use proc_macros::Event;
+
+use crate::storage::{common::RateSource, pool::Pool};
+
+#[derive(Event)]
+#[contracttype]
+pub struct Swapped {
+    #[topic]
+    pub sender: Address,
+    pub recipient: Address,
+    #[topic]
+    pub from_token: Address,
+    #[topic]
+    pub to_token: Address,
+    // token precision
+    pub from_amount: u128,
+    // token precision
+    pub to_amount: u128,
+    // token precision
+    pub fee: u128,
+}
+
+#[derive(Event)]
+#[contracttype]
+pub struct Deposit {
+    #[topic]
+    pub user: Address,
+    // system precision
+    pub lp_amount: u128,
+    // token precision
+    pub amounts: Vec<u128>,
+    // token precision
+    pub fees: Vec<u128>,
+}
+
+#[derive(Event)]
+#[contracttype]
+pub struct Withdraw {
+    #[topic]
+    pub user: Address,
+    // system precision
+    pub lp_amount: u128,
+    // system precision
+    pub amounts: Vec<u128>,
+    // token precision
+    pub fees: Vec<u128>,
+}
+
+#[derive(Event)]
+#[contracttype]
+pub struct RewardsClaimed {
+    #[topic]
+    pub user: Address,
+    // token precision
+    pub rewards: Vec<u128>,
+}
+
+#[derive(Event)]
+#[contracttype]
+pub struct AdminFeeClaimed {
+    #[topic]
+    pub admin: Address,
+    // token precision
+    pub fees: Vec<u128>,
+}
+
+#[derive(Event)]
+#[contracttype]
+pub struct SwapFeeShareUpdated {
+    pub swap_fee_share: u128,
+}
+
+#[derive(Event)]
+#[contracttype]
+pub struct WithdrawFeeShareUpdated {
+    pub withdraw_fee_share: u128,
+}
+
+#[derive(Event)]
+#[contracttype]
+pub struct DepositFeeShareUpdated {
+    pub deposit_fee_share: u128,
+}
+
+#[derive(Event)]
+#[contracttype]
+pub struct AdminFeeShareUpdated {
+    pub admin_fee_share_bp: u128,
+}
+
+#[derive(Event)]
+#[contracttype]
+pub struct OffpegFeeMultiplierUpdated {
+    pub offpeg_fee_multiplier_bp: u128,
+}
+
+#[derive(Event)]
+#[contracttype]
+pub struct RateSourceUpdated {
+    #[topic]
+    pub token: Address,
+    pub rate_source: RateSource,
+}
+
+#[derive(Event)]
+#[contracttype]
+pub struct AdminChanged {
+    #[topic]
+    pub new_admin: Address,
+}
+
+#[derive(Event)]
+#[contracttype]
+pub struct Upgraded {
+    pub new_wasm_hash: BytesN<32>,
+}
+
+#[derive(Event)]
+#[contracttype]
+pub struct Sync {
+    // system precision
+    pub token_balances: Vec<u128>,
+    // system precision
+    pub total_lp_amount: u128,
+    pub acc_rewards_per_share_p: Vec<u128>,
+    pub nonce: u64,
+}
+
+impl Sync {
+    pub fn from_pool(pool: &Pool, nonce: u64) -> Self {
+        Sync {
+            token_balances: pool.token_balances.clone(),
+            total_lp_amount: pool.total_lp_amount,
+            acc_rewards_per_share_p: pool.acc_rewards_per_share_p.clone(),
+            nonce,
+        }
+    }
+}
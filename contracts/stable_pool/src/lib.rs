@@ -0,0 +1,8 @@
+#![no_std]
+
+mod contract;
+mod events;
+mod methods;
+mod storage;
+
+pub use contract::PoolContract;
@@ -0,0 +1,339 @@
+use shared::{
+    require,
+    utils::{num, safe_cast},
+    Error,
+};
+use soroban_sdk::{Address, Env, Vec};
+
+use crate::storage::{pool::Pool, user_deposit::UserDeposit};
+
+use super::pool_view::{DepositAmount, WithdrawAmount};
+
+impl Pool {
+    pub const BP: u128 = num::BP;
+    pub const FEE_DENOMINATOR: u128 = num::FEE_DENOMINATOR;
+
+    pub(crate) const MIN_TOKENS: u32 = 2;
+    pub(crate) const MAX_TOKENS: u32 = 8;
+    pub(crate) const MAX_A: u128 = 60;
+    /// Ceiling of the sum of the system precision balances, `get_d` and `get_y` stay within
+    /// `U256` below it
+    pub(crate) const MAX_TOKEN_BALANCE: u128 = 2u128.pow(60);
+    pub(crate) const SYSTEM_PRECISION: u32 = 3;
+    pub(crate) const MAX_OFFPEG_FEE_ITERATIONS: usize = 8;
+
+    pub const P: u128 = 48;
+    pub const VIRTUAL_PRICE_PRECISION: u128 = 1_000_000_000_000_000_000;
+
+    #[allow(clippy::too_many_arguments)]
+    pub fn swap(
+        &mut self,
+        env: &Env,
+        sender: Address,
+        recipient: Address,
+        amount: u128,
+        receive_amount_min: u128,
+        token_from: u32,
+        token_to: u32,
+    ) -> Result<(u128, u128), Error> {
+        self.require_token_pair(token_from, token_to)?;
+
+        if amount == 0 {
+            return Ok((0, 0));
+        }
+
+        let current_contract = env.current_contract_address();
+        let receive_amount = self.get_receive_amount(amount, token_from, token_to)?;
+
+        self.get_token(env, token_from)
+            .transfer(&sender, &current_contract, &safe_cast(amount)?);
+
+        self.token_balances
+            .set(token_from, receive_amount.token_from_new_balance);
+        self.token_balances
+            .set(token_to, receive_amount.token_to_new_balance);
+
+        self.add_rewards(receive_amount.fee, token_to);
+
+        require!(
+            receive_amount.output >= receive_amount_min,
+            Error::InsufficientReceivedAmount
+        );
+
+        self.get_token(env, token_to).transfer(
+            &current_contract,
+            &recipient,
+            &safe_cast(receive_amount.output)?,
+        );
+
+        Ok((receive_amount.output, receive_amount.fee))
+    }
+
+    pub fn deposit(
+        &mut self,
+        env: &Env,
+        amounts: Vec<u128>,
+        sender: Address,
+        user_deposit: &mut UserDeposit,
+        min_lp_amount: u128,
+    ) -> Result<(DepositAmount, Vec<u128>), Error> {
+        let current_contract = env.current_contract_address();
+
+        let deposit_amount = self.get_deposit_amount(amounts.clone())?;
+        self.token_balances = deposit_amount.new_token_balances.clone();
+
+        require!(deposit_amount.lp_amount >= min_lp_amount, Error::Slippage);
+
+        for (index, amount) in amounts.iter().enumerate() {
+            if amount == 0 {
+                continue;
+            }
+
+            self.get_token(env, index as u32).transfer(
+                &sender,
+                &current_contract,
+                &safe_cast(amount)?,
+            );
+        }
+
+        for (index, fee) in deposit_amount.fees.iter().enumerate() {
+            self.add_rewards(fee, index as u32);
+        }
+
+        let rewards = self.deposit_lp(user_deposit, deposit_amount.lp_amount)?;
+
+        for (index, reward) in rewards.iter().enumerate() {
+            if reward == 0 {
+                continue;
+            }
+
+            self.get_token(env, index as u32).transfer(
+                &current_contract,
+                &sender,
+                &safe_cast(reward)?,
+            );
+        }
+
+        Ok((deposit_amount, rewards))
+    }
+
+    pub fn withdraw(
+        &mut self,
+        env: &Env,
+        sender: Address,
+        user_deposit: &mut UserDeposit,
+        lp_amount: u128,
+    ) -> Result<(WithdrawAmount, Vec<u128>), Error> {
+        let current_contract = env.current_contract_address();
+        let d0 = self.total_lp_amount;
+        let old_balances = self.token_balances.clone();
+        let withdraw_amount = self.get_withdraw_amount(lp_amount)?;
+        let rewards_amounts = self.withdraw_lp(user_deposit, lp_amount)?;
+
+        for index in 0..self.tokens_count() {
+            let token_amount = self.amount_from_system_precision(
+                withdraw_amount.amounts.get_unchecked(index),
+                self.tokens_decimals.get_unchecked(index),
+            );
+            let token_amount = token_amount + rewards_amounts.get_unchecked(index);
+
+            self.add_rewards(withdraw_amount.fees.get_unchecked(index), index);
+            self.get_token(env, index).transfer(
+                &current_contract,
+                &sender,
+                &safe_cast(token_amount)?,
+            );
+        }
+
+        self.token_balances = withdraw_amount.new_token_balances.clone();
+        let d1 = self.total_lp_amount;
+
+        require!(
+            self.token_balances
+                .iter()
+                .zip(old_balances.iter())
+                .all(|(new_balance, old_balance)| new_balance < old_balance)
+                && d1 < d0,
+            Error::ZeroChanges
+        );
+
+        Ok((withdraw_amount, rewards_amounts))
+    }
+
+    pub(crate) fn deposit_lp(
+        &mut self,
+        user_deposit: &mut UserDeposit,
+        lp_amount: u128,
+    ) -> Result<Vec<u128>, Error> {
+        let pending = self.get_pending(user_deposit);
+
+        self.total_lp_amount += lp_amount;
+        user_deposit.lp_amount += lp_amount;
+        user_deposit.reward_debts = self.get_reward_debts(user_deposit);
+
+        Ok(pending)
+    }
+
+    pub(crate) fn withdraw_lp(
+        &mut self,
+        user_deposit: &mut UserDeposit,
+        lp_amount: u128,
+    ) -> Result<Vec<u128>, Error> {
+        require!(user_deposit.lp_amount >= lp_amount, Error::NotEnoughAmount);
+
+        let pending = self.get_pending(user_deposit);
+
+        self.total_lp_amount -= lp_amount;
+        user_deposit.lp_amount -= lp_amount;
+        user_deposit.reward_debts = self.get_reward_debts(user_deposit);
+
+        Ok(pending)
+    }
+
+    pub fn claim_rewards(
+        &self,
+        env: &Env,
+        user: Address,
+        user_deposit: &mut UserDeposit,
+    ) -> Result<Vec<u128>, Error> {
+        let pending = self.get_pending(user_deposit);
+
+        if user_deposit.lp_amount == 0 {
+            return Ok(pending);
+        }
+
+        user_deposit.reward_debts = self.get_reward_debts(user_deposit);
+
+        for (index, reward) in pending.iter().enumerate() {
+            if reward > 0 {
+                self.get_token(env, index as u32).transfer(
+                    &env.current_contract_address(),
+                    &user,
+                    &safe_cast(reward)?,
+                );
+            }
+        }
+
+        Ok(pending)
+    }
+
+    pub(crate) fn add_rewards(&mut self, reward_amount: u128, token: u32) {
+        if self.total_lp_amount == 0 {
+            return;
+        }
+
+        let (reward_amount, admin_fee_rewards) =
+            num::split_admin_fee(reward_amount, self.admin_fee_share_bp);
+        self.acc_rewards_per_share_p.set(
+            token,
+            self.acc_rewards_per_share_p.get_unchecked(token)
+                + (reward_amount << Pool::P) / self.total_lp_amount,
+        );
+        self.admin_fee_amount.set(
+            token,
+            self.admin_fee_amount.get_unchecked(token) + admin_fee_rewards,
+        );
+    }
+
+    pub fn get_pending(&self, user_deposit: &UserDeposit) -> Vec<u128> {
+        if user_deposit.lp_amount == 0 {
+            return self.zero_amounts();
+        }
+
+        let mut pending = self.get_reward_debts(user_deposit);
+        for (index, reward_debt) in user_deposit.reward_debts.iter().enumerate() {
+            let index = index as u32;
+            pending.set(index, pending.get_unchecked(index) - reward_debt);
+        }
+
+        pending
+    }
+
+    pub fn get_reward_debts(&self, user_deposit: &UserDeposit) -> Vec<u128> {
+        let mut reward_debts = self.zero_amounts();
+        for (index, acc_reward_per_share_p) in self.acc_rewards_per_share_p.iter().enumerate() {
+            reward_debts.set(
+                index as u32,
+                num::accrued_rewards(user_deposit.lp_amount, acc_reward_per_share_p, Pool::P),
+            );
+        }
+
+        reward_debts
+    }
+
+    pub(crate) fn require_token_pair(&self, token_from: u32, token_to: u32) -> Result<(), Error> {
+        require!(
+            token_from < self.tokens_count() && token_to < self.tokens_count(),
+            Error::InvalidArg
+        );
+        require!(token_from != token_to, Error::InvalidArg);
+
+        Ok(())
+    }
+
+    /// Scaled balance of the token at `index` that keeps the invariant at `d` along with the
+    /// other scaled `balances`
+    pub fn get_y(&self, balances: &Vec<u128>, index: u32, d: u128) -> Result<u128, Error> {
+        num::get_y(self.a, balances.iter(), index, d)
+    }
+
+    pub fn get_current_d(&self) -> Result<u128, Error> {
+        self.get_d_for_balances(&self.token_balances)
+    }
+
+    /// `get_d` of system precision balances scaled by the rates
+    pub fn get_d_for_balances(&self, balances_sp: &Vec<u128>) -> Result<u128, Error> {
+        num::get_d(self.a, self.scale_by_rates(balances_sp).iter())
+    }
+
+    /// Invariant of the current balances, the LP supply tracks it 1:1 while all rates are fixed
+    pub fn get_d0(&self) -> Result<u128, Error> {
+        if !self.has_dynamic_rates() {
+            return Ok(self.total_lp_amount);
+        }
+
+        self.get_current_d()
+    }
+
+    /// Invariant the imbalance fee of a deposit is measured against, unlike `get_d0` it counts
+    /// the part of the balances the LP supply does not cover
+    pub fn get_imbalance_fee_d0(&self) -> Result<u128, Error> {
+        if self.total_lp_amount == 0 {
+            return Ok(0);
+        }
+
+        self.get_current_d()
+    }
+
+    pub(crate) fn get_scaled_balances(&self) -> Vec<u128> {
+        self.scale_by_rates(&self.token_balances)
+    }
+
+    fn scale_by_rates(&self, amounts_sp: &Vec<u128>) -> Vec<u128> {
+        let mut scaled_amounts = self.zero_amounts();
+        for (index, amount_sp) in amounts_sp.iter().enumerate() {
+            let index = index as u32;
+            scaled_amounts.set(index, self.scale_by_rate(amount_sp, index));
+        }
+
+        scaled_amounts
+    }
+
+    /// Values a system precision amount of the token in the pegged asset, the curve works on
+    /// scaled amounts
+    pub(crate) fn scale_by_rate(&self, amount_sp: u128, index: u32) -> u128 {
+        num::scale_by_rate(amount_sp, self.rates.get_unchecked(index))
+    }
+
+    pub(crate) fn unscale_by_rate(&self, amount: u128, index: u32) -> u128 {
+        num::unscale_by_rate(amount, self.rates.get_unchecked(index))
+    }
+
+    pub(crate) fn amount_to_system_precision(&self, amount: u128, decimals: u32) -> u128 {
+        num::to_system_precision(amount, decimals, Self::SYSTEM_PRECISION)
+    }
+
+    pub(crate) fn amount_from_system_precision(&self, amount: u128, decimals: u32) -> u128 {
+        num::from_system_precision(amount, decimals, Self::SYSTEM_PRECISION)
+    }
+}
@@ -0,0 +1,669 @@
+use ethnum::U256;
+use shared::{
+    require,
+    utils::{num, safe_cast},
+    Error,
+};
+use soroban_sdk::{contracttype, Address, Vec};
+
+use crate::storage::{pool::Pool, user_deposit::UserDeposit};
+
+pub struct ReceiveAmount {
+    pub token_from_new_balance: u128,
+    pub token_to_new_balance: u128,
+    pub output: u128,
+    pub fee: u128,
+}
+
+#[contracttype]
+#[derive(Debug)]
+pub struct WithdrawAmount {
+    /// system precision
+    pub amounts: Vec<u128>,
+    /// token precision
+    pub fees: Vec<u128>,
+    /// system precision
+    pub new_token_balances: Vec<u128>,
+}
+
+#[contracttype]
+#[derive(Debug)]
+pub struct PoolInfo {
+    pub tokens: Vec<Address>,
+    pub tokens_decimals: Vec<u32>,
+    /// `RATE_PRECISION` is 1.0
+    pub rates: Vec<u128>,
+    /// token precision
+    pub token_balances: Vec<u128>,
+    pub a: u128,
+    /// `Pool::FEE_DENOMINATOR` precision
+    pub swap_fee_share: u128,
+    /// `Pool::FEE_DENOMINATOR` precision
+    pub withdraw_fee_share: u128,
+    /// `Pool::FEE_DENOMINATOR` precision
+    pub deposit_fee_share: u128,
+    pub admin_fee_share_bp: u128,
+    pub offpeg_fee_multiplier_bp: u128,
+    /// system precision
+    pub total_lp_amount: u128,
+    /// `Pool::VIRTUAL_PRICE_PRECISION` is 1.0
+    pub virtual_price: u128,
+    pub admin: Address,
+    /// system precision, zero if no user was given
+    pub user_lp_amount: u128,
+    /// token precision, zeros if no user was given
+    pub user_pending_rewards: Vec<u128>,
+}
+
+#[contracttype]
+#[derive(Debug)]
+pub struct SwapQuote {
+    /// token precision
+    pub output: u128,
+    /// token precision
+    pub fee: u128,
+    /// Output shortfall against the spot price of the curve, fee excluded
+    pub price_impact_bp: u128,
+    /// token precision
+    pub new_token_balances: Vec<u128>,
+}
+
+#[contracttype]
+#[derive(Debug)]
+pub struct DepositPreview {
+    /// system precision
+    pub lp_amount: u128,
+    /// Imbalance fees, token precision
+    pub fees: Vec<u128>,
+    /// Rewards claimed along with the deposit, token precision
+    pub rewards: Vec<u128>,
+    /// system precision
+    pub user_lp_amount: u128,
+}
+
+#[contracttype]
+#[derive(Debug)]
+pub struct WithdrawPreview {
+    /// system precision
+    pub lp_amount: u128,
+    /// Withdrawn tokens after fees and without rewards, token precision
+    pub amounts: Vec<u128>,
+    /// token precision
+    pub fees: Vec<u128>,
+    /// Rewards claimed along with the withdrawal, token precision
+    pub rewards: Vec<u128>,
+    /// system precision
+    pub user_lp_amount: u128,
+}
+
+pub struct DepositAmount {
+    pub lp_amount: u128,
+    /// token precision
+    pub fees: Vec<u128>,
+    pub new_token_balances: Vec<u128>,
+}
+
+impl Pool {
+    pub fn get_virtual_price(&self) -> Result<u128, Error> {
+        if self.total_lp_amount == 0 {
+            return Ok(0);
+        }
+
+        Ok(self.get_current_d()? * Self::VIRTUAL_PRICE_PRECISION / self.total_lp_amount)
+    }
+
+    pub fn get_token_balances(&self) -> Vec<u128> {
+        self.amounts_from_system_precision(&self.token_balances)
+    }
+
+    pub(crate) fn amounts_from_system_precision(&self, amounts_sp: &Vec<u128>) -> Vec<u128> {
+        let mut amounts = self.zero_amounts();
+
+        for (index, amount_sp) in amounts_sp.iter().enumerate() {
+            let index = index as u32;
+            amounts.set(
+                index,
+                self.amount_from_system_precision(
+                    amount_sp,
+                    self.tokens_decimals.get_unchecked(index),
+                ),
+            );
+        }
+
+        amounts
+    }
+
+    pub fn quote_swap(
+        &self,
+        input: u128,
+        token_from: u32,
+        token_to: u32,
+    ) -> Result<SwapQuote, Error> {
+        self.require_token_pair(token_from, token_to)?;
+        require!(input > 0, Error::ZeroAmount);
+        require!(self.total_lp_amount > 0, Error::ZeroOutput);
+
+        let receive_amount = self.get_receive_amount(input, token_from, token_to)?;
+        require!(receive_amount.output > 0, Error::ZeroOutput);
+
+        let input_sp =
+            receive_amount.token_from_new_balance - self.token_balances.get_unchecked(token_from);
+        let output_sp =
+            self.token_balances.get_unchecked(token_to) - receive_amount.token_to_new_balance;
+        let spot_output_sp = self.get_spot_output(input_sp, token_from, token_to)?;
+
+        let mut new_token_balances = self.token_balances.clone();
+        new_token_balances.set(token_from, receive_amount.token_from_new_balance);
+        new_token_balances.set(token_to, receive_amount.token_to_new_balance);
+
+        Ok(SwapQuote {
+            output: receive_amount.output,
+            fee: receive_amount.fee,
+            price_impact_bp: num::price_impact_bp(spot_output_sp, output_sp),
+            new_token_balances: self.amounts_from_system_precision(&new_token_balances),
+        })
+    }
+
+    /// Output for `input_sp` at the marginal price of the curve, system precision
+    pub fn get_spot_output(
+        &self,
+        input_sp: u128,
+        token_from: u32,
+        token_to: u32,
+    ) -> Result<u128, Error> {
+        let balances = self.get_scaled_balances();
+        let n = U256::from(self.tokens_count());
+        let ann = U256::new(self.a) * n.pow(self.tokens_count());
+        let d = U256::new(self.get_d0()?);
+
+        // Dⁿ⁺¹ / (nⁿ·Πx)
+        let mut d_p = d;
+        for balance in balances.iter() {
+            d_p = d_p.checked_mul(d).ok_or(Error::U256Overflow)? / (U256::new(balance) * n);
+        }
+
+        let x = balances.get_unchecked(token_from);
+        let y = balances.get_unchecked(token_to);
+
+        // dy/dx = y(A·nⁿ·x + D_P) / x(A·nⁿ·y + D_P)
+        let output = num::spot_output(
+            self.scale_by_rate(input_sp, token_from),
+            x,
+            y,
+            ann * x + d_p,
+            ann * y + d_p,
+        )?;
+
+        Ok(self.unscale_by_rate(output, token_to))
+    }
+
+    #[inline]
+    pub fn is_offpeg_fee_enabled(&self) -> bool {
+        num::is_offpeg_fee_enabled(self.swap_fee_share, self.offpeg_fee_multiplier_bp)
+    }
+
+    pub fn get_max_swap_fee_share(&self) -> u128 {
+        num::max_swap_fee_share(self.swap_fee_share, self.offpeg_fee_multiplier_bp)
+    }
+
+    /// Swap fee share as a `(numerator, denominator)` fraction for the balances of the swapped
+    /// tokens after the swap
+    pub fn get_swap_fee_share(&self, balance_sp: u128, other_balance_sp: u128) -> (U256, U256) {
+        num::swap_fee_share(
+            self.swap_fee_share,
+            self.offpeg_fee_multiplier_bp,
+            balance_sp,
+            other_balance_sp,
+        )
+    }
+
+    pub fn get_receive_amount(
+        &self,
+        input: u128,
+        token_from: u32,
+        token_to: u32,
+    ) -> Result<ReceiveAmount, Error> {
+        self.require_token_pair(token_from, token_to)?;
+
+        let d0 = self.get_d0()?;
+        let mut balances = self.get_scaled_balances();
+        let scaled_to_balance = balances.get_unchecked(token_to);
+        let input_sp =
+            self.amount_to_system_precision(input, self.tokens_decimals.get_unchecked(token_from));
+        let mut output_sp = 0;
+
+        let token_from_new_balance = self.token_balances.get_unchecked(token_from) + input_sp;
+        let scaled_from_new_balance = self.scale_by_rate(token_from_new_balance, token_from);
+        balances.set(token_from, scaled_from_new_balance);
+
+        // One unit more stays in the pool against the rounding of `get_y`
+        let scaled_to_new_balance = self.get_y(&balances, token_to, d0)? + 1;
+        if scaled_to_balance > scaled_to_new_balance {
+            output_sp = self.unscale_by_rate(scaled_to_balance - scaled_to_new_balance, token_to);
+        }
+        let token_to_new_balance = self.token_balances.get_unchecked(token_to) - output_sp;
+        let mut output = self
+            .amount_from_system_precision(output_sp, self.tokens_decimals.get_unchecked(token_to));
+        let (fee_share, fee_share_denominator) =
+            self.get_swap_fee_share(scaled_from_new_balance, scaled_to_new_balance);
+        let fee = safe_cast(U256::new(output) * fee_share / fee_share_denominator)?;
+
+        output -= fee;
+
+        Ok(ReceiveAmount {
+            token_from_new_balance,
+            token_to_new_balance,
+            output,
+            fee,
+        })
+    }
+
+    pub fn get_send_amount(
+        &self,
+        output: u128,
+        token_from: u32,
+        token_to: u32,
+    ) -> Result<(u128, u128), Error> {
+        self.require_token_pair(token_from, token_to)?;
+
+        let d0 = self.get_d0()?;
+        let mut balances = self.get_scaled_balances();
+        let scaled_from_balance = balances.get_unchecked(token_from);
+        let scaled_to_balance = balances.get_unchecked(token_to);
+        let to_decimals = self.tokens_decimals.get_unchecked(token_to);
+        // Rounds up by at most one unit against the fee of `get_receive_amount`
+        let mut fee = output * self.swap_fee_share / (Self::FEE_DENOMINATOR - self.swap_fee_share);
+        if self.is_offpeg_fee_enabled() {
+            // The fee depends on the balances after the swap, which depend on the fee,
+            // so it is refined starting from the flat one until it settles
+            for _ in 0..Self::MAX_OFFPEG_FEE_ITERATIONS {
+                let output_sp = self.amount_to_system_precision(output + fee, to_decimals);
+                let scaled_to_new_balance =
+                    scaled_to_balance - self.scale_by_rate(output_sp, token_to);
+                balances.set(token_to, scaled_to_new_balance);
+                let scaled_from_new_balance = self.get_y(&balances, token_from, d0)? + 1;
+                let (fee_share, fee_share_denominator) =
+                    self.get_swap_fee_share(scaled_from_new_balance, scaled_to_new_balance);
+                let new_fee: u128 =
+                    safe_cast(U256::new(output) * fee_share / (fee_share_denominator - fee_share))?;
+
+                if new_fee == fee {
+                    break;
+                }
+                fee = new_fee;
+            }
+        }
+        let output_sp = self.amount_to_system_precision(output + fee, to_decimals);
+        let mut input = 0;
+
+        balances.set(
+            token_to,
+            scaled_to_balance - self.scale_by_rate(output_sp, token_to),
+        );
+        let scaled_from_new_balance = self.get_y(&balances, token_from, d0)? + 1;
+        if scaled_from_balance < scaled_from_new_balance {
+            input = self.amount_from_system_precision(
+                self.unscale_by_rate(scaled_from_new_balance - scaled_from_balance, token_from),
+                self.tokens_decimals.get_unchecked(token_from),
+            );
+        }
+
+        Ok((input, fee))
+    }
+
+    pub fn preview_deposit(
+        &self,
+        user_deposit: &UserDeposit,
+        amounts: Vec<u128>,
+    ) -> Result<DepositPreview, Error> {
+        let deposit_amount = self.get_deposit_amount(amounts)?;
+
+        // Imbalance fees are distributed before the deposit, the user gets a share of them
+        let mut pool = self.clone();
+        for (index, fee) in deposit_amount.fees.iter().enumerate() {
+            pool.add_rewards(fee, index as u32);
+        }
+
+        Ok(DepositPreview {
+            lp_amount: deposit_amount.lp_amount,
+            fees: deposit_amount.fees,
+            rewards: pool.get_pending(user_deposit),
+            user_lp_amount: user_deposit.lp_amount + deposit_amount.lp_amount,
+        })
+    }
+
+    pub fn preview_withdraw(
+        &self,
+        user_deposit: &UserDeposit,
+        lp_amount: u128,
+    ) -> Result<WithdrawPreview, Error> {
+        require!(user_deposit.lp_amount >= lp_amount, Error::NotEnoughAmount);
+
+        let withdraw_amount = self.get_withdraw_amount(lp_amount)?;
+
+        Ok(WithdrawPreview {
+            lp_amount,
+            amounts: self.amounts_from_system_precision(&withdraw_amount.amounts),
+            fees: withdraw_amount.fees,
+            rewards: self.get_pending(user_deposit),
+            user_lp_amount: user_deposit.lp_amount - lp_amount,
+        })
+    }
+
+    /// Withdraws every token proportionally except the one with the smallest balance, whose
+    /// amount keeps the invariant at the reduced `D`
+    pub fn get_withdraw_amount(&self, lp_amount: u128) -> Result<WithdrawAmount, Error> {
+        let d0 = self.get_d0()?;
+        let balances = self.get_scaled_balances();
+
+        let d1 = d0 - self.lp_amount_to_d(lp_amount, d0);
+        let mut less = 0;
+        for (index, balance) in balances.iter().enumerate() {
+            if balance < balances.get_unchecked(less) {
+                less = index as u32;
+            }
+        }
+
+        let mut amounts_sp = self.zero_amounts();
+        let mut new_balances = balances.clone();
+        for (index, token_balance) in self.token_balances.iter().enumerate() {
+            let index = index as u32;
+            if index == less {
+                continue;
+            }
+
+            let token_amount_sp = token_balance * lp_amount / self.total_lp_amount;
+            amounts_sp.set(index, token_amount_sp);
+            new_balances.set(
+                index,
+                self.scale_by_rate(token_balance - token_amount_sp, index),
+            );
+        }
+        let y = self.get_y(&new_balances, less, d1)?;
+        amounts_sp.set(
+            less,
+            self.unscale_by_rate(balances.get_unchecked(less) - y, less),
+        );
+
+        let mut amounts = self.zero_amounts();
+        let mut fees = self.zero_amounts();
+        let mut new_token_balances = self.token_balances.clone();
+
+        for (index, token_amount_sp) in amounts_sp.iter().enumerate() {
+            let index = index as u32;
+            let decimals = self.tokens_decimals.get_unchecked(index);
+            let token_amount = self.amount_from_system_precision(token_amount_sp, decimals);
+            let fee = token_amount * self.withdraw_fee_share / Self::FEE_DENOMINATOR;
+
+            let token_amount_sp = self.amount_to_system_precision(token_amount - fee, decimals);
+
+            fees.set(index, fee);
+            amounts.set(index, token_amount_sp);
+            new_token_balances.set(
+                index,
+                new_token_balances.get_unchecked(index) - token_amount_sp,
+            );
+        }
+
+        Ok(WithdrawAmount {
+            amounts,
+            fees,
+            new_token_balances,
+        })
+    }
+
+    pub fn get_deposit_amount(&self, amounts: Vec<u128>) -> Result<DepositAmount, Error> {
+        require!(amounts.len() == self.tokens_count(), Error::InvalidArg);
+
+        if self.total_lp_amount == 0 {
+            let first_amount = amounts.get_unchecked(0);
+            require!(
+                amounts.iter().all(|amount| amount == first_amount),
+                Error::InvalidFirstDeposit
+            );
+        }
+
+        let d0 = self.get_d0()?;
+
+        let mut new_token_balances_sp = self.token_balances.clone();
+        let mut total_amount_sp = 0;
+
+        for (index, amount) in amounts.iter().enumerate() {
+            let index = index as u32;
+            let amount_sp =
+                self.amount_to_system_precision(amount, self.tokens_decimals.get_unchecked(index));
+
+            total_amount_sp += amount_sp;
+            new_token_balances_sp.set(
+                index,
+                new_token_balances_sp.get_unchecked(index) + amount_sp,
+            );
+        }
+
+        require!(total_amount_sp > 0, Error::ZeroAmount);
+
+        let d1 = self.get_d_for_balances(&new_token_balances_sp)?;
+
+        require!(d1 > d0, Error::Forbidden);
+        require!(
+            new_token_balances_sp.iter().sum::<u128>() < Self::MAX_TOKEN_BALANCE,
+            Error::PoolOverflow
+        );
+
+        let fees = self.charge_imbalance_fees(
+            &mut new_token_balances_sp,
+            self.get_imbalance_fee_d0()?,
+            d1,
+        );
+        let d2 = self.get_d_for_balances(&new_token_balances_sp)?;
+
+        require!(d2 > d0, Error::Forbidden);
+
+        Ok(DepositAmount {
+            lp_amount: self.d_to_lp_amount(d2 - d0, d0),
+            fees,
+            new_token_balances: new_token_balances_sp,
+        })
+    }
+
+    /// LP amount worth `d_amount` of the invariant, equal to it until the rates move
+    fn d_to_lp_amount(&self, d_amount: u128, d0: u128) -> u128 {
+        if self.total_lp_amount == 0 {
+            return d_amount;
+        }
+
+        d_amount * self.total_lp_amount / d0
+    }
+
+    fn lp_amount_to_d(&self, lp_amount: u128, d0: u128) -> u128 {
+        lp_amount * d0 / self.total_lp_amount
+    }
+
+    /// Charges the StableSwap imbalance fee on the deviation of every balance from its
+    /// proportional share of `d1` and removes it from the balances, returns fees in token precision
+    fn charge_imbalance_fees(
+        &self,
+        new_token_balances_sp: &mut Vec<u128>,
+        d0: u128,
+        d1: u128,
+    ) -> Vec<u128> {
+        let mut fees = self.zero_amounts();
+
+        if d0 == 0 {
+            return fees;
+        }
+
+        let n = self.tokens_count() as u128;
+        let old_balances = self.get_scaled_balances();
+
+        for (index, old_balance) in old_balances.iter().enumerate() {
+            let index = index as u32;
+            let new_balance_sp = new_token_balances_sp.get_unchecked(index);
+            let fee = num::imbalance_fee(
+                old_balance,
+                self.scale_by_rate(new_balance_sp, index),
+                d0,
+                d1,
+                self.deposit_fee_share,
+                n,
+            );
+            let fee_sp = self.unscale_by_rate(fee, index);
+
+            new_token_balances_sp.set(index, new_balance_sp - fee_sp);
+            fees.set(
+                index,
+                self.amount_from_system_precision(
+                    fee_sp,
+                    self.tokens_decimals.get_unchecked(index),
+                ),
+            );
+        }
+
+        fees
+    }
+}
+
+#[allow(clippy::inconsistent_digit_grouping)]
+#[cfg(test)]
+mod tests {
+    extern crate std;
+
+    use shared::{soroban_data::SimpleSorobanData, Error};
+    use soroban_sdk::{contract, contractimpl, testutils::Address as _, vec, Address, Env, Vec};
+
+    use crate::storage::pool::Pool;
+
+    #[contract]
+    pub struct TestPool;
+
+    #[contractimpl]
+    impl TestPool {
+        pub fn init(env: Env, tokens_count: u32) {
+            let mut tokens = Vec::new(&env);
+            let mut decimals = Vec::new(&env);
+            for _ in 0..tokens_count {
+                tokens.push_back(Address::generate(&env));
+                decimals.push_back(7);
+            }
+
+            Pool::from_init_params(&env, 20, tokens, decimals, 100, 1).save(&env);
+        }
+
+        pub fn set_balances(env: Env, new_balances: Vec<u128>) -> Result<(), Error> {
+            Pool::update(&env, |pool| {
+                pool.token_balances = new_balances;
+                pool.total_lp_amount = pool.get_current_d()?;
+                Ok(())
+            })
+        }
+
+        pub fn set_swap_fee_share(env: Env, swap_fee_share: u128) -> Result<(), Error> {
+            Pool::update(&env, |pool| {
+                pool.swap_fee_share = swap_fee_share;
+                Ok(())
+            })
+        }
+
+        pub fn get_receive_amount(
+            env: Env,
+            amount: u128,
+            token_from: u32,
+            token_to: u32,
+        ) -> Result<(u128, u128), Error> {
+            let receive_amount =
+                Pool::get(&env)?.get_receive_amount(amount, token_from, token_to)?;
+            Ok((receive_amount.output, receive_amount.fee))
+        }
+
+        pub fn get_send_amount(
+            env: Env,
+            amount: u128,
+            token_from: u32,
+            token_to: u32,
+        ) -> Result<(u128, u128), Error> {
+            Pool::get(&env)?.get_send_amount(amount, token_from, token_to)
+        }
+    }
+
+    fn create_pool(env: &Env, balances: Vec<u128>) -> TestPoolClient<'_> {
+        let test_pool_id = env.register_contract(None, TestPool);
+        let pool = TestPoolClient::new(env, &test_pool_id);
+        pool.init(&balances.len());
+        pool.set_balances(&balances);
+
+        pool
+    }
+
+    #[test]
+    fn test() {
+        let env = Env::default();
+        let pool = create_pool(&env, vec![&env, 200_000_000, 200_000_000, 200_000_000]);
+
+        let input = 10_000_0000000_u128;
+        let (output, fee) = pool.get_receive_amount(&input, &0, &1);
+        let (calc_input, calc_fee) = pool.get_send_amount(&output, &0, &1);
+
+        assert_eq!(input, calc_input);
+        assert_eq!(fee, calc_fee);
+    }
+
+    #[test]
+    fn test_disbalance() {
+        let env = Env::default();
+        let pool = create_pool(
+            &env,
+            vec![&env, 200_000_000, 500_000_000, 200_000_000, 350_000_000],
+        );
+
+        let input = 10_000_0000000_u128;
+        let (output, fee) = pool.get_receive_amount(&input, &3, &1);
+        let (calc_input, calc_fee) = pool.get_send_amount(&output, &3, &1);
+
+        assert_eq!(input, calc_input);
+        assert_eq!(fee, calc_fee);
+    }
+
+    #[test]
+    fn test_sub_bp_fee() {
+        let env = Env::default();
+        let pool = create_pool(&env, vec![&env, 200_000_000, 500_000_000]);
+        // 0.004%
+        pool.set_swap_fee_share(&40);
+
+        let input = 10_000_0000000_u128;
+        let (output, fee) = pool.get_receive_amount(&input, &0, &1);
+        let (calc_input, calc_fee) = pool.get_send_amount(&output, &0, &1);
+
+        assert_eq!(fee, (output + fee) * 40 / Pool::FEE_DENOMINATOR);
+        assert_eq!(input, calc_input);
+        assert_eq!(fee, calc_fee);
+    }
+
+    #[test]
+    fn test_eight_tokens() {
+        let env = Env::default();
+        let pool = create_pool(
+            &env,
+            vec![
+                &env,
+                100_000_000,
+                100_000_000,
+                100_000_000,
+                100_000_000,
+                100_000_000,
+                100_000_000,
+                100_000_000,
+                100_000_000,
+            ],
+        );
+
+        let input = 10_000_0000000_u128;
+        let (output, fee) = pool.get_receive_amount(&input, &7, &0);
+        let (calc_input, calc_fee) = pool.get_send_amount(&output, &7, &0);
+
+        assert!(output + fee < input);
+        assert!(output + fee > input * 99 / 100);
+        assert_eq!(input, calc_input);
+        assert_eq!(fee, calc_fee);
+    }
+}
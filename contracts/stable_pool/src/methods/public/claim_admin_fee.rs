@@ -0,0 +1,41 @@
+use shared::{soroban_data::SimpleSorobanData, utils::safe_cast, Error, Event};
+use soroban_sdk::Env;
+use storage::{Admin, OperationNonce};
+
+use crate::{
+    events::{AdminFeeClaimed, Sync},
+    storage::pool::Pool,
+};
+
+pub fn claim_admin_fee(env: Env) -> Result<(), Error> {
+    let admin = Admin::get(&env)?;
+    admin.require_auth();
+
+    let mut pool = Pool::get(&env)?;
+    let fees = pool.admin_fee_amount.clone();
+
+    for (index, fee) in fees.iter().enumerate() {
+        if fee > 0 {
+            let index = index as u32;
+            pool.get_token(&env, index).transfer(
+                &env.current_contract_address(),
+                admin.as_ref(),
+                &safe_cast(fee)?,
+            );
+            pool.admin_fee_amount.set(index, 0);
+        }
+    }
+    pool.save(&env);
+
+    if fees.iter().any(|fee| fee > 0) {
+        AdminFeeClaimed {
+            admin: admin.0,
+            fees,
+        }
+        .publish(&env);
+    }
+
+    Sync::from_pool(&pool, OperationNonce::next(&env)).publish(&env);
+
+    Ok(())
+}
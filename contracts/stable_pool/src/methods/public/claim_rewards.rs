@@ -0,0 +1,29 @@
+use shared::{soroban_data::SimpleSorobanData, Error, Event};
+use soroban_sdk::{Address, Env};
+
+use crate::{
+    events::RewardsClaimed,
+    storage::{pool::Pool, user_deposit::UserDeposit},
+};
+
+pub fn claim_rewards(env: Env, sender: Address) -> Result<(), Error> {
+    sender.require_auth();
+    let pool = Pool::get(&env)?;
+
+    let mut user_deposit = UserDeposit::get(&env, sender.clone());
+    let rewards = pool.claim_rewards(&env, sender.clone(), &mut user_deposit)?;
+
+    if rewards.iter().sum::<u128>() == 0 {
+        return Ok(());
+    }
+
+    user_deposit.save(&env, sender.clone());
+
+    RewardsClaimed {
+        user: sender,
+        rewards,
+    }
+    .publish(&env);
+
+    Ok(())
+}
@@ -0,0 +1,50 @@
+use shared::{soroban_data::SimpleSorobanData, Error, Event};
+use soroban_sdk::{Address, Env, Vec};
+use storage::OperationNonce;
+
+use crate::{
+    events::{Deposit, RewardsClaimed, Sync},
+    storage::{pool::Pool, user_deposit::UserDeposit},
+};
+
+pub fn deposit(
+    env: Env,
+    sender: Address,
+    amounts: Vec<u128>,
+    min_lp_amount: u128,
+) -> Result<(), Error> {
+    sender.require_auth();
+    let mut pool = Pool::get_with_rates(&env)?;
+    let mut user_deposit = UserDeposit::get(&env, sender.clone());
+
+    let (deposit_amount, rewards) = pool.deposit(
+        &env,
+        amounts.clone(),
+        sender.clone(),
+        &mut user_deposit,
+        min_lp_amount,
+    )?;
+
+    pool.save(&env);
+    user_deposit.save(&env, sender.clone());
+
+    Deposit {
+        user: sender.clone(),
+        lp_amount: deposit_amount.lp_amount,
+        amounts,
+        fees: deposit_amount.fees,
+    }
+    .publish(&env);
+
+    if rewards.iter().any(|reward| reward > 0) {
+        RewardsClaimed {
+            user: sender,
+            rewards,
+        }
+        .publish(&env);
+    }
+
+    Sync::from_pool(&pool, OperationNonce::next(&env)).publish(&env);
+
+    Ok(())
+}
@@ -0,0 +1,44 @@
+use shared::{require, soroban_data::SimpleSorobanData, Error};
+use soroban_sdk::{token, Address, Env, Vec};
+use storage::{Admin, Upgrader};
+
+use crate::storage::pool::Pool;
+
+pub fn initialize(
+    env: Env,
+    admin: Address,
+    a: u128,
+    tokens: Vec<Address>,
+    fee_share_bp: u128,
+    admin_fee_share_bp: u128,
+    upgrader: Address,
+) -> Result<(), Error> {
+    require!(!Pool::has(&env), Error::Initialized);
+
+    require!(
+        (Pool::MIN_TOKENS..=Pool::MAX_TOKENS).contains(&tokens.len()),
+        Error::InvalidNumberOfTokens
+    );
+    require!(fee_share_bp < Pool::BP, Error::InvalidArg);
+    require!(admin_fee_share_bp < Pool::BP, Error::InvalidArg);
+    require!(a > 0 && a <= Pool::MAX_A, Error::InvalidArg);
+
+    let mut tokens_decimals = Vec::new(&env);
+    for token in tokens.iter() {
+        tokens_decimals.push_back(token::Client::new(&env, &token).decimals());
+    }
+
+    Pool::from_init_params(
+        &env,
+        a,
+        tokens,
+        tokens_decimals,
+        fee_share_bp,
+        admin_fee_share_bp,
+    )
+    .save(&env);
+    Admin(admin).save(&env);
+    Upgrader(upgrader).save(&env);
+
+    Ok(())
+}
@@ -0,0 +1,48 @@
+use shared::{soroban_data::SimpleSorobanData, Error, Event};
+use soroban_sdk::{Address, Env};
+use storage::OperationNonce;
+
+use crate::{
+    events::{Swapped, Sync},
+    storage::pool::Pool,
+};
+
+pub fn swap(
+    env: Env,
+    sender: Address,
+    recipient: Address,
+    from_amount: u128,
+    receive_amount_min: u128,
+    token_from: u32,
+    token_to: u32,
+) -> Result<u128, Error> {
+    sender.require_auth();
+    let mut pool = Pool::get_with_rates(&env)?;
+
+    let (to_amount, fee) = pool.swap(
+        &env,
+        sender.clone(),
+        recipient.clone(),
+        from_amount,
+        receive_amount_min,
+        token_from,
+        token_to,
+    )?;
+
+    pool.save(&env);
+
+    Swapped {
+        from_token: pool.tokens.get_unchecked(token_from),
+        to_token: pool.tokens.get_unchecked(token_to),
+        from_amount,
+        to_amount,
+        sender,
+        recipient,
+        fee,
+    }
+    .publish(&env);
+
+    Sync::from_pool(&pool, OperationNonce::next(&env)).publish(&env);
+
+    Ok(to_amount)
+}
@@ -0,0 +1,40 @@
+use shared::{soroban_data::SimpleSorobanData, Error, Event};
+use soroban_sdk::{Address, Env};
+use storage::OperationNonce;
+
+use crate::{
+    events::{RewardsClaimed, Sync, Withdraw},
+    storage::{pool::Pool, user_deposit::UserDeposit},
+};
+
+pub fn withdraw(env: Env, sender: Address, lp_amount: u128) -> Result<(), Error> {
+    sender.require_auth();
+    let mut pool = Pool::get_with_last_rates(&env)?;
+    let mut user_deposit = UserDeposit::get(&env, sender.clone());
+
+    let (withdraw_amount, rewards) =
+        pool.withdraw(&env, sender.clone(), &mut user_deposit, lp_amount)?;
+
+    pool.save(&env);
+    user_deposit.save(&env, sender.clone());
+
+    Withdraw {
+        user: sender.clone(),
+        lp_amount,
+        amounts: withdraw_amount.amounts,
+        fees: withdraw_amount.fees,
+    }
+    .publish(&env);
+
+    if rewards.iter().any(|reward| reward > 0) {
+        RewardsClaimed {
+            user: sender,
+            rewards,
+        }
+        .publish(&env);
+    }
+
+    Sync::from_pool(&pool, OperationNonce::next(&env)).publish(&env);
+
+    Ok(())
+}
@@ -0,0 +1,3 @@
+pub mod common;
+pub mod pool;
+pub mod user_deposit;
@@ -0,0 +1,183 @@
+use proc_macros::{extend_ttl_info_instance, Instance, SorobanData, SorobanSimpleData, SymbolKey};
+use shared::{
+    price_feed::read_price_rate,
+    rate_provider::{read_provider_rate, RATE_PRECISION},
+    require,
+    soroban_data::SimpleSorobanData,
+    Error,
+};
+use soroban_sdk::{
+    contracttype,
+    token::{self, TokenClient},
+    Address, Env, Vec,
+};
+
+use super::common::RateSource;
+
+/// All the per-token fields hold one entry per token, in the order of `tokens`
+#[contracttype]
+#[derive(Debug, Clone, SorobanData, SorobanSimpleData, SymbolKey, Instance)]
+#[extend_ttl_info_instance]
+pub struct Pool {
+    pub a: u128,
+
+    /// `Pool::FEE_DENOMINATOR` precision
+    pub swap_fee_share: u128,
+    /// `Pool::FEE_DENOMINATOR` precision
+    pub withdraw_fee_share: u128,
+    /// `Pool::FEE_DENOMINATOR` precision, charged on the imbalanced part of a deposit, zero
+    /// until the admin sets it so deposits keep their previous cost
+    pub deposit_fee_share: u128,
+    pub admin_fee_share_bp: u128,
+    /// Scales the swap fee up as the pool gets imbalanced, `Pool::BP` and below keep the fee flat
+    pub offpeg_fee_multiplier_bp: u128,
+    pub total_lp_amount: u128,
+
+    pub tokens: Vec<Address>,
+    pub tokens_decimals: Vec<u32>,
+    pub rate_sources: Vec<RateSource>,
+    /// Rates last read from `rate_sources`, `RATE_PRECISION` precision
+    pub rates: Vec<u128>,
+    pub token_balances: Vec<u128>,
+    pub acc_rewards_per_share_p: Vec<u128>,
+    pub admin_fee_amount: Vec<u128>,
+}
+
+impl Pool {
+    pub fn from_init_params(
+        env: &Env,
+        a: u128,
+        tokens: Vec<Address>,
+        tokens_decimals: Vec<u32>,
+        fee_share_bp: u128,
+        admin_fee_share_bp: u128,
+    ) -> Self {
+        let mut rate_sources = Vec::new(env);
+        let mut rates = Vec::new(env);
+        for _ in 0..tokens.len() {
+            rate_sources.push_back(RateSource::Fixed);
+            rates.push_back(RATE_PRECISION);
+        }
+        let zero_amounts = zero_amounts(env, tokens.len());
+
+        Pool {
+            a,
+
+            swap_fee_share: Self::fee_share_from_bp(fee_share_bp),
+            withdraw_fee_share: Self::fee_share_from_bp(fee_share_bp),
+            deposit_fee_share: 0,
+            admin_fee_share_bp,
+            offpeg_fee_multiplier_bp: 0,
+            total_lp_amount: 0,
+
+            tokens,
+            tokens_decimals,
+            rate_sources,
+            rates,
+            token_balances: zero_amounts.clone(),
+            acc_rewards_per_share_p: zero_amounts.clone(),
+            admin_fee_amount: zero_amounts,
+        }
+    }
+
+    /// Loads the pool with the rates refreshed from its rate providers and oracles
+    pub fn get_with_rates(env: &Env) -> Result<Self, Error> {
+        let mut pool = Self::get(env)?;
+        pool.update_rates(env)?;
+
+        Ok(pool)
+    }
+
+    /// Loads the pool for withdrawals and views, a rate source that cannot be read keeps
+    /// the last rate stored in the pool so a failing provider or oracle does not lock funds
+    pub fn get_with_last_rates(env: &Env) -> Result<Self, Error> {
+        let mut pool = Self::get(env)?;
+
+        for (index, rate_source) in pool.rate_sources.iter().enumerate() {
+            if let Ok(rate) = Self::read_rate(env, &rate_source) {
+                pool.rates.set(index as u32, rate);
+            }
+        }
+
+        Ok(pool)
+    }
+
+    pub fn update_rates(&mut self, env: &Env) -> Result<(), Error> {
+        for (index, rate_source) in self.rate_sources.iter().enumerate() {
+            self.rates
+                .set(index as u32, Self::read_rate(env, &rate_source)?);
+        }
+
+        Ok(())
+    }
+
+    /// `RATE_PRECISION` precision
+    pub fn read_rate(env: &Env, rate_source: &RateSource) -> Result<u128, Error> {
+        let rate = match rate_source {
+            RateSource::Fixed => RATE_PRECISION,
+            RateSource::Provider(rate_provider) => read_provider_rate(env, rate_provider)?,
+            RateSource::Oracle(oracle) => {
+                let rate = read_price_rate(env, &oracle.price_feed, oracle.max_price_age)?;
+
+                let max_deviation = oracle
+                    .peg_rate
+                    .checked_mul(oracle.max_price_deviation_bp)
+                    .ok_or(Error::U256Overflow)?;
+                require!(
+                    rate.abs_diff(oracle.peg_rate)
+                        .checked_mul(Self::BP)
+                        .is_some_and(|deviation| deviation <= max_deviation),
+                    Error::PriceDeviation
+                );
+
+                rate
+            }
+        };
+
+        require!(rate > 0, Error::InvalidRate);
+
+        Ok(rate)
+    }
+
+    #[inline]
+    pub fn has_dynamic_rates(&self) -> bool {
+        self.rate_sources
+            .iter()
+            .any(|rate_source| !matches!(rate_source, RateSource::Fixed))
+    }
+
+    #[inline]
+    pub fn fee_share_from_bp(fee_share_bp: u128) -> u128 {
+        fee_share_bp * (Self::FEE_DENOMINATOR / Self::BP)
+    }
+
+    #[inline]
+    pub fn fee_share_to_bp(fee_share: u128) -> u128 {
+        fee_share / (Self::FEE_DENOMINATOR / Self::BP)
+    }
+
+    #[inline]
+    pub fn tokens_count(&self) -> u32 {
+        self.tokens.len()
+    }
+
+    /// One zero amount per token
+    #[inline]
+    pub fn zero_amounts(&self) -> Vec<u128> {
+        zero_amounts(self.tokens.env(), self.tokens_count())
+    }
+
+    #[inline]
+    pub fn get_token(&self, env: &Env, index: u32) -> TokenClient<'_> {
+        token::Client::new(env, &self.tokens.get_unchecked(index))
+    }
+}
+
+fn zero_amounts(env: &Env, len: u32) -> Vec<u128> {
+    let mut amounts = Vec::new(env);
+    for _ in 0..len {
+        amounts.push_back(0);
+    }
+
+    amounts
+}
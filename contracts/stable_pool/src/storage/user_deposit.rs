@@ -0,0 +1,33 @@
+use proc_macros::{extend_ttl_info, Persistent, SorobanData};
+use shared::consts::DAY_IN_LEDGERS;
+use shared::soroban_data::SorobanData;
+use soroban_sdk::{contracttype, Address, Env, Vec};
+
+const BUMP_AMOUNT: u32 = 30 * DAY_IN_LEDGERS;
+const LIFETIME_THRESHOLD: u32 = BUMP_AMOUNT - DAY_IN_LEDGERS;
+
+#[contracttype]
+#[derive(Clone, Debug, SorobanData, Persistent)]
+#[extend_ttl_info(BUMP_AMOUNT, LIFETIME_THRESHOLD)]
+pub struct UserDeposit {
+    pub lp_amount: u128,
+    /// One per token, empty until the first deposit
+    pub reward_debts: Vec<u128>,
+}
+
+impl UserDeposit {
+    pub fn new(env: &Env) -> UserDeposit {
+        UserDeposit {
+            lp_amount: 0,
+            reward_debts: Vec::new(env),
+        }
+    }
+
+    pub fn get(env: &Env, address: Address) -> UserDeposit {
+        UserDeposit::get_by_key(env, &address).unwrap_or_else(|_| UserDeposit::new(env))
+    }
+
+    pub fn save(&self, env: &Env, address: Address) {
+        self.save_by_key(env, &address);
+    }
+}
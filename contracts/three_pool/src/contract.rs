@@ -1,24 +1,27 @@
-use shared::{utils::extend_ttl_instance, Error};
-use soroban_sdk::{contract, contractimpl, Address, BytesN, Env};
-use storage::Admin;
+use shared::{utils::extend_ttl_instance, Error, Event};
+use soroban_sdk::{contract, contractimpl, Address, BytesN, Env, Vec};
+use storage::Upgrader;
 
 use crate::{
+    events::Upgraded,
     methods::{
-        internal::pool_view::WithdrawAmountView,
+        internal::pool_view::{
+            DepositPreview, PoolInfo, SwapQuote, WithdrawAmountView, WithdrawPreview,
+        },
         public::{
-            claim_admin_fee, claim_rewards, deposit, initialize, set_admin, set_admin_fee_share,
-            set_fee_share, swap, withdraw,
+            claim_admin_fee, claim_rewards, deposit, initialize, migrate, set_admin,
+            set_admin_fee_share, set_deposit_fee_share, set_offpeg_fee_multiplier, set_oracle,
+            set_rate_provider, set_swap_fee_share, set_upgrader, set_withdraw_fee_share, swap,
+            transfer_lp, withdraw,
         },
         view::{
-            get_admin, get_d, get_deposit_amount, get_pool, get_receive_amount, get_send_amount,
-            get_user_deposit, get_withdraw_amount, pending_reward,
+            get_admin, get_d, get_deposit_amount, get_pool, get_pool_info, get_receive_amount,
+            get_send_amount, get_tokens, get_upgrader, get_user_deposit, get_user_deposits,
+            get_virtual_price, get_withdraw_amount, pending_reward, pending_rewards,
+            preview_deposit, preview_withdraw, quote_swap,
         },
     },
-    storage::{
-        common::{Token},
-        pool::Pool,
-        user_deposit::UserDeposit,
-    },
+    storage::{common::Token, pool::Pool, user_deposit::UserDeposit},
 };
 
 #[contract]
@@ -36,6 +39,7 @@ impl PoolContract {
         token_c: Address,
         fee_share_bp: u128,
         admin_fee_share_bp: u128,
+        upgrader: Address,
     ) -> Result<(), Error> {
         initialize(
             env,
@@ -46,21 +50,30 @@ impl PoolContract {
             token_c,
             fee_share_bp,
             admin_fee_share_bp,
+            upgrader,
         )
     }
 
+    /// Returns the minted LP amount, the metapool needs it to account for its base deposits.
+    /// Returned `()` before, callers that ignore the result are not affected
     pub fn deposit(
         env: Env,
         sender: Address,
         amounts: (u128, u128, u128),
         min_lp_amount: u128,
-    ) -> Result<(), Error> {
+    ) -> Result<u128, Error> {
         extend_ttl_instance(&env);
 
         deposit(env, sender, amounts, min_lp_amount)
     }
 
-    pub fn withdraw(env: Env, sender: Address, lp_amount: u128) -> Result<(), Error> {
+    /// Returns the withdrawn amounts of the tokens in pool order, token precision.
+    /// Returned `()` before, callers that ignore the result are not affected
+    pub fn withdraw(
+        env: Env,
+        sender: Address,
+        lp_amount: u128,
+    ) -> Result<(u128, u128, u128), Error> {
         extend_ttl_instance(&env);
 
         withdraw(env, sender, lp_amount)
@@ -94,6 +107,18 @@ impl PoolContract {
         claim_rewards(env, sender)
     }
 
+    /// Moves LP to another deposit, which lets other contracts hold it, such as a metapool
+    pub fn transfer_lp(
+        env: Env,
+        sender: Address,
+        recipient: Address,
+        lp_amount: u128,
+    ) -> Result<(), Error> {
+        extend_ttl_instance(&env);
+
+        transfer_lp(env, sender, recipient, lp_amount)
+    }
+
     // ----------- Admin -----------
 
     pub fn claim_admin_fee(env: Env) -> Result<(), Error> {
@@ -114,10 +139,59 @@ impl PoolContract {
         set_admin_fee_share(env, admin_fee_share_bp)
     }
 
-    pub fn set_fee_share(env: Env, fee_share_bp: u128) -> Result<(), Error> {
+    pub fn set_swap_fee_share(env: Env, swap_fee_share: u128) -> Result<(), Error> {
+        extend_ttl_instance(&env);
+
+        set_swap_fee_share(env, swap_fee_share)
+    }
+
+    pub fn set_withdraw_fee_share(env: Env, withdraw_fee_share: u128) -> Result<(), Error> {
+        extend_ttl_instance(&env);
+
+        set_withdraw_fee_share(env, withdraw_fee_share)
+    }
+
+    pub fn set_deposit_fee_share(env: Env, deposit_fee_share: u128) -> Result<(), Error> {
         extend_ttl_instance(&env);
 
-        set_fee_share(env, fee_share_bp)
+        set_deposit_fee_share(env, deposit_fee_share)
+    }
+
+    pub fn set_offpeg_fee_multiplier(
+        env: Env,
+        offpeg_fee_multiplier_bp: u128,
+    ) -> Result<(), Error> {
+        extend_ttl_instance(&env);
+
+        set_offpeg_fee_multiplier(env, offpeg_fee_multiplier_bp)
+    }
+
+    pub fn set_rate_provider(
+        env: Env,
+        token: Token,
+        rate_provider: Option<Address>,
+    ) -> Result<(), Error> {
+        extend_ttl_instance(&env);
+
+        set_rate_provider(env, token, rate_provider)
+    }
+
+    pub fn set_oracle(
+        env: Env,
+        token: Token,
+        price_feed: Address,
+        max_price_age: u64,
+        max_price_deviation_bp: u128,
+    ) -> Result<(), Error> {
+        extend_ttl_instance(&env);
+
+        set_oracle(
+            env,
+            token,
+            price_feed,
+            max_price_age,
+            max_price_deviation_bp,
+        )
     }
 
     // ----------- View -----------
@@ -126,6 +200,13 @@ impl PoolContract {
         pending_reward(env, user)
     }
 
+    pub fn pending_rewards(
+        env: Env,
+        users: Vec<Address>,
+    ) -> Result<Vec<(u128, u128, u128)>, Error> {
+        pending_rewards(env, users)
+    }
+
     pub fn get_pool(env: Env) -> Result<Pool, Error> {
         get_pool(env)
     }
@@ -134,6 +215,10 @@ impl PoolContract {
         get_user_deposit(env, user)
     }
 
+    pub fn get_user_deposits(env: Env, users: Vec<Address>) -> Result<Vec<UserDeposit>, Error> {
+        get_user_deposits(env, users)
+    }
+
     pub fn get_d(env: Env) -> Result<u128, Error> {
         get_d(env)
     }
@@ -147,7 +232,21 @@ impl PoolContract {
         get_receive_amount(env, input, token_from, token_to)
     }
 
-    pub fn get_send_amount(env: Env, output: u128, token_from: Token, token_to: Token) -> Result<(u128, u128), Error> {
+    pub fn quote_swap(
+        env: Env,
+        input: u128,
+        token_from: Token,
+        token_to: Token,
+    ) -> Result<SwapQuote, Error> {
+        quote_swap(env, input, token_from, token_to)
+    }
+
+    pub fn get_send_amount(
+        env: Env,
+        output: u128,
+        token_from: Token,
+        token_to: Token,
+    ) -> Result<(u128, u128), Error> {
         get_send_amount(env, output, token_from, token_to)
     }
 
@@ -155,20 +254,72 @@ impl PoolContract {
         get_withdraw_amount(env, lp_amount)
     }
 
-    pub fn get_deposit_amount(env: Env, amounts: (u128, u128, u128)) -> Result<u128, Error> {
+    pub fn get_deposit_amount(
+        env: Env,
+        amounts: (u128, u128, u128),
+    ) -> Result<(u128, (u128, u128, u128)), Error> {
         get_deposit_amount(env, amounts)
     }
 
+    pub fn preview_deposit(
+        env: Env,
+        user: Address,
+        amounts: (u128, u128, u128),
+    ) -> Result<DepositPreview, Error> {
+        preview_deposit(env, user, amounts)
+    }
+
+    pub fn preview_withdraw(
+        env: Env,
+        user: Address,
+        lp_amount: u128,
+    ) -> Result<WithdrawPreview, Error> {
+        preview_withdraw(env, user, lp_amount)
+    }
+
+    pub fn get_virtual_price(env: Env) -> Result<u128, Error> {
+        get_virtual_price(env)
+    }
+
+    pub fn get_tokens(env: Env) -> Result<(Address, Address, Address), Error> {
+        get_tokens(env)
+    }
+
     pub fn get_admin(env: Env) -> Result<Address, Error> {
         get_admin(env)
     }
 
+    pub fn get_upgrader(env: Env) -> Result<Address, Error> {
+        get_upgrader(env)
+    }
+
+    pub fn get_pool_info(env: Env, user: Option<Address>) -> Result<PoolInfo, Error> {
+        get_pool_info(env, user)
+    }
+
     // ----------- Upgrade -----------
 
+    /// Upgrades by the upgrader, the factory for the pools it deploys
     pub fn upgrade(env: Env, new_wasm_hash: BytesN<32>) -> Result<(), Error> {
-        Admin::require_exist_auth(&env)?;
+        Upgrader::require_exist_auth(&env)?;
+
+        env.deployer()
+            .update_current_contract_wasm(new_wasm_hash.clone());
+
+        Upgraded { new_wasm_hash }.publish(&env);
 
-        env.deployer().update_current_contract_wasm(new_wasm_hash);
         Ok(())
     }
+
+    pub fn set_upgrader(env: Env, new_upgrader: Address) -> Result<(), Error> {
+        extend_ttl_instance(&env);
+
+        set_upgrader(env, new_upgrader)
+    }
+
+    pub fn migrate(env: Env) -> Result<(), Error> {
+        extend_ttl_instance(&env);
+
+        migrate(env)
+    }
 }
@@ -1,30 +1,29 @@
-use core::cmp::Ordering;
-
-use ethnum::I256;
 use shared::{
     require,
-    utils::{num::*, safe_cast},
+    utils::{num, safe_cast},
     Error,
 };
 use soroban_sdk::{Address, Env};
 
-use crate::storage::{
-    common::{Token},
-    pool::Pool,
-    user_deposit::UserDeposit,
-};
 use crate::storage::triple_values::TripleU128;
+use crate::storage::{common::Token, pool::Pool, user_deposit::UserDeposit};
 
-use super::pool_view::WithdrawAmount;
+use super::pool_view::{DepositAmount, WithdrawAmount};
 
 impl Pool {
     pub const BP: u128 = 10000;
+    /// Swap, withdraw and deposit fee shares are fractions of it, one basis point is 100
+    pub const FEE_DENOMINATOR: u128 = 1_000_000;
 
     pub(crate) const MAX_A: u128 = 60;
-    pub(crate) const MAX_TOKEN_BALANCE: u128 = 2u128.pow(40);
+    /// Ceiling of the sum of the system precision balances, `get_d` and `get_y` stay within
+    /// `U256` below it
+    pub(crate) const MAX_TOKEN_BALANCE: u128 = 2u128.pow(60);
     pub(crate) const SYSTEM_PRECISION: u32 = 3;
+    pub(crate) const MAX_OFFPEG_FEE_ITERATIONS: usize = 8;
 
     pub const P: u128 = 48;
+    pub const VIRTUAL_PRICE_PRECISION: u128 = 1_000_000_000_000_000_000;
 
     #[allow(clippy::too_many_arguments)]
     pub fn swap(
@@ -73,15 +72,11 @@ impl Pool {
         sender: Address,
         user_deposit: &mut UserDeposit,
         min_lp_amount: u128,
-    ) -> Result<(TripleU128, u128), Error> {
+    ) -> Result<(DepositAmount, TripleU128), Error> {
         let current_contract = env.current_contract_address();
 
-        if self.total_lp_amount == 0 {
-            require!(amounts.data.0 == amounts.data.1 && amounts.data.0 == amounts.data.2 && amounts.data.1 == amounts.data.2, Error::InvalidFirstDeposit);
-        }
-
         let deposit_amount = self.get_deposit_amount(amounts.clone())?;
-        self.token_balances = deposit_amount.new_token_balances;
+        self.token_balances = deposit_amount.new_token_balances.clone();
 
         require!(deposit_amount.lp_amount >= min_lp_amount, Error::Slippage);
 
@@ -97,6 +92,10 @@ impl Pool {
             );
         }
 
+        for (index, fee) in deposit_amount.fees.to_array().into_iter().enumerate() {
+            self.add_rewards(fee, index.into());
+        }
+
         let rewards = self.deposit_lp(user_deposit, deposit_amount.lp_amount)?;
 
         for (index, reward) in rewards.to_array().into_iter().enumerate() {
@@ -111,7 +110,7 @@ impl Pool {
             );
         }
 
-        Ok((rewards, deposit_amount.lp_amount))
+        Ok((deposit_amount, rewards))
     }
 
     pub fn withdraw(
@@ -155,6 +154,37 @@ impl Pool {
         Ok((withdraw_amount, rewards_amounts))
     }
 
+    /// Moves LP between deposits, both users get their pending rewards paid out
+    pub fn transfer_lp(
+        &mut self,
+        env: &Env,
+        sender: (&Address, &mut UserDeposit),
+        recipient: (&Address, &mut UserDeposit),
+        lp_amount: u128,
+    ) -> Result<(TripleU128, TripleU128), Error> {
+        let sender_rewards = self.withdraw_lp(sender.1, lp_amount)?;
+        let recipient_rewards = self.deposit_lp(recipient.1, lp_amount)?;
+
+        for (user, rewards) in [
+            (sender.0, &sender_rewards),
+            (recipient.0, &recipient_rewards),
+        ] {
+            for (index, reward) in rewards.to_array().into_iter().enumerate() {
+                if reward == 0 {
+                    continue;
+                }
+
+                self.get_token_by_index(env, index).transfer(
+                    &env.current_contract_address(),
+                    user,
+                    &safe_cast(reward)?,
+                );
+            }
+        }
+
+        Ok((sender_rewards, recipient_rewards))
+    }
+
     pub(crate) fn deposit_lp(
         &mut self,
         user_deposit: &mut UserDeposit,
@@ -217,11 +247,13 @@ impl Pool {
     }
 
     pub(crate) fn add_rewards(&mut self, mut reward_amount: u128, token: Token) {
-        if self.total_lp_amount > 0 {
-            let admin_fee_rewards = reward_amount * self.admin_fee_share_bp / Pool::BP;
-            reward_amount -= admin_fee_rewards;
-            self.acc_rewards_per_share_p[token] +=
-                (reward_amount << Pool::P) / self.total_lp_amount;
+        let admin_fee_rewards = reward_amount * self.admin_fee_share_bp / Pool::BP;
+        reward_amount -= admin_fee_rewards;
+
+        if let Some(rewards_per_share_p) =
+            (reward_amount << Pool::P).checked_div(self.total_lp_amount)
+        {
+            self.acc_rewards_per_share_p[token] += rewards_per_share_p;
             self.admin_fee_amount[token] += admin_fee_rewards;
         }
     }
@@ -248,60 +280,75 @@ impl Pool {
         ))
     }
 
+    /// Balance of the middle token that keeps the invariant at `d` along with the balances `x`
+    /// and `z` of the other two
+    pub fn get_y(&self, x: u128, z: u128, d: u128) -> Result<u128, Error> {
+        num::get_y(self.a, [x, 0, z], 1, d)
+    }
 
-    pub fn get_y(&self, x128: u128, z128: u128, d128: u128) -> Result<u128, Error> {
-        let x = I256::from(x128);
-        let z = I256::from(z128);
-        let d = I256::from(d128);
-        let a = I256::from(self.a);
-        let a27 = a * 27;
+    pub fn get_current_d(&self) -> Result<u128, Error> {
+        self.get_d_for_balances(&self.token_balances)
+    }
 
-        let b= x + z - d + d / a27;
-        let c= d.pow(4) / (-27 * a27 * x * z);
-        Ok(((-b + sqrt(&(b.pow(2) - 4 * c).unsigned_abs()).as_i256()) / 2).as_u128())
+    /// `get_d` of system precision balances scaled by the rates
+    pub fn get_d_for_balances(&self, balances_sp: &TripleU128) -> Result<u128, Error> {
+        self.get_d(
+            self.scale_by_rate(balances_sp[0], 0),
+            self.scale_by_rate(balances_sp[1], 1),
+            self.scale_by_rate(balances_sp[2], 2),
+        )
     }
 
-    pub fn get_current_d(&self) -> Result<u128, Error> {
-        self.get_d(self.token_balances[0], self.token_balances[1], self.token_balances[2])
+    /// Invariant of the current balances, the LP supply tracks it 1:1 while all rates are fixed
+    pub fn get_d0(&self) -> Result<u128, Error> {
+        if !self.has_dynamic_rates() {
+            return Ok(self.total_lp_amount);
+        }
+
+        self.get_current_d()
     }
 
-    pub fn get_d(&self, x128: u128, y128: u128, z128: u128) -> Result<u128, Error> {
-        let x = I256::from(x128);
-        let y = I256::from(y128);
-        let z = I256::from(z128);
-        let a = I256::from(self.a);
-
-        let mut d = x + y + z;
-        loop {
-            let f = 27 * a * (x + y + z) - (27 * a * d - d) - d.pow(4) / (27 * x * y * z);
-            let df = -4 * d.pow(3) / (27 * x * y * z) - 27 * a + 1;
-            if f.abs() < df.abs() {
-                break;
-            }
-            d -= f / df;
+    /// Invariant the imbalance fee of a deposit is measured against, unlike `get_d0` it counts
+    /// the part of the balances the LP supply does not cover
+    pub fn get_imbalance_fee_d0(&self) -> Result<u128, Error> {
+        if self.total_lp_amount == 0 {
+            return Ok(0);
         }
 
-        Ok(d.as_u128())
+        self.get_current_d()
+    }
+
+    pub fn get_d(&self, x: u128, y: u128, z: u128) -> Result<u128, Error> {
+        num::get_d(self.a, [x, y, z])
+    }
+
+    pub(crate) fn get_scaled_balances(&self) -> TripleU128 {
+        TripleU128::from((
+            self.scale_by_rate(self.token_balances[0], 0),
+            self.scale_by_rate(self.token_balances[1], 1),
+            self.scale_by_rate(self.token_balances[2], 2),
+        ))
+    }
+
+    /// Values a system precision amount of the token in the pegged asset, the curve works on
+    /// scaled amounts
+    pub(crate) fn scale_by_rate(&self, amount_sp: u128, index: usize) -> u128 {
+        num::scale_by_rate(amount_sp, self.rates[index])
+    }
+
+    pub(crate) fn unscale_by_rate(&self, amount: u128, index: usize) -> u128 {
+        num::unscale_by_rate(amount, self.rates[index])
     }
 
     pub(crate) fn amount_to_system_precision(&self, amount: u128, decimals: u32) -> u128 {
-        match decimals.cmp(&Self::SYSTEM_PRECISION) {
-            Ordering::Greater => amount / (10u128.pow(decimals - Self::SYSTEM_PRECISION)),
-            Ordering::Less => amount * (10u128.pow(Self::SYSTEM_PRECISION - decimals)),
-            Ordering::Equal => amount,
-        }
+        num::to_system_precision(amount, decimals, Self::SYSTEM_PRECISION)
     }
 
     pub(crate) fn amount_from_system_precision(&self, amount: u128, decimals: u32) -> u128 {
-        match decimals.cmp(&Self::SYSTEM_PRECISION) {
-            Ordering::Greater => amount * (10u128.pow(decimals - Self::SYSTEM_PRECISION)),
-            Ordering::Less => amount / (10u128.pow(Self::SYSTEM_PRECISION - decimals)),
-            Ordering::Equal => amount,
-        }
+        num::from_system_precision(amount, decimals, Self::SYSTEM_PRECISION)
     }
 }
 
-
 #[allow(clippy::inconsistent_digit_grouping)]
 #[cfg(test)]
 mod tests {
@@ -310,7 +357,7 @@ mod tests {
     use shared::{soroban_data::SimpleSorobanData, Error};
     use soroban_sdk::{contract, contractimpl, testutils::Address as _, Address, Env};
 
-    use crate::storage::{pool::Pool};
+    use crate::storage::pool::Pool;
     use crate::storage::triple_values::TripleU128;
 
     #[contract]
@@ -355,11 +402,11 @@ mod tests {
         let big_d = 157_831_140_060_220_325;
         let mid_d = 6_084_878_857_843_302;
         assert_eq!(pool.get_y(&n, &n, &(n * 3)), n);
-        assert_eq!( pool.get_y(&n, &(n / 1_000), &big_d), n - 1);
-        assert_eq!( pool.get_y(&n, &n, &big_d), n / 1_000 - 1);
-        assert_eq!( pool.get_y(&n, &(n / 1_000), &mid_d), n / 1_000_000 - 1);
-        assert_eq!( pool.get_y(&n, &(n / 1_000_000), &mid_d), n / 1_000 - 1);
-        assert_eq!( pool.get_y(&(n/ 1_000), &(n / 1_000_000), &mid_d), n - 14);
+        assert_eq!(pool.get_y(&n, &(n / 1_000), &big_d), n - 2);
+        assert_eq!(pool.get_y(&n, &n, &big_d), n / 1_000 - 1);
+        assert_eq!(pool.get_y(&n, &(n / 1_000), &mid_d), n / 1_000_000 - 1);
+        assert_eq!(pool.get_y(&n, &(n / 1_000_000), &mid_d), n / 1_000 - 1);
+        assert_eq!(pool.get_y(&(n / 1_000), &(n / 1_000_000), &mid_d), n - 15);
     }
 
     #[test]
@@ -370,13 +417,39 @@ mod tests {
         let pool = TestPoolClient::new(&env, &test_pool_id);
         pool.init();
 
-        assert_eq!(pool.get_d(&2_000_000, &256_364, &5_000_000), 7_197_881);
+        assert_eq!(pool.get_d(&2_000_000, &256_364, &5_000_000), 7_197_880);
 
         let n = 100_000_000_000_000_000;
         let big_d = 157_831_140_060_220_325;
         assert_eq!(pool.get_d(&n, &n, &n), n * 3);
         assert_eq!(pool.get_d(&n, &n, &(n / 1_000)), big_d);
-        assert_eq!(pool.get_d(&n, &(n / 1_000), &(n / 1_000_000)), 6_084_878_857_843_302);
+        assert_eq!(
+            pool.get_d(&n, &(n / 1_000), &(n / 1_000_000)),
+            6_084_878_857_843_310
+        );
+
+        // A balanced pool at the balance ceiling
+        let max = Pool::MAX_TOKEN_BALANCE / 3;
+        assert_eq!(pool.get_d(&max, &max, &max), max * 3);
+        assert_eq!(pool.get_y(&max, &max, &(max * 3)), max);
+    }
+
+    #[test]
+    fn test_zero_balance() {
+        let env = Env::default();
 
+        let test_pool_id = env.register_contract(None, TestPool);
+        let pool = TestPoolClient::new(&env, &test_pool_id);
+        pool.init();
+
+        assert_eq!(pool.get_d(&0, &0, &0), 0);
+        assert_eq!(
+            pool.try_get_d(&1_000_000, &1_000_000, &0),
+            Err(Ok(Error::ZeroAmount))
+        );
+        assert_eq!(
+            pool.try_get_y(&1_000_000, &0, &3_000_000),
+            Err(Ok(Error::ZeroAmount))
+        );
     }
 }
@@ -1,8 +1,13 @@
-use shared::{require, Error};
-use soroban_sdk::contracttype;
+use ethnum::U256;
+use shared::{
+    require,
+    utils::{num, safe_cast},
+    Error,
+};
+use soroban_sdk::{contracttype, Address};
 
-use crate::storage::{common::Token, pool::Pool};
 use crate::storage::triple_values::TripleU128;
+use crate::storage::{common::Token, pool::Pool, user_deposit::UserDeposit};
 
 pub struct ReceiveAmount {
     pub token_from_new_balance: u128,
@@ -36,33 +41,218 @@ impl From<WithdrawAmount> for WithdrawAmountView {
     }
 }
 
+#[contracttype]
+#[derive(Debug)]
+pub struct PoolInfo {
+    pub tokens: (Address, Address, Address),
+    pub tokens_decimals: (u32, u32, u32),
+    /// `RATE_PRECISION` is 1.0
+    pub rates: (u128, u128, u128),
+    /// token precision
+    pub token_balances: (u128, u128, u128),
+    pub a: u128,
+    /// `Pool::FEE_DENOMINATOR` precision
+    pub swap_fee_share: u128,
+    /// `Pool::FEE_DENOMINATOR` precision
+    pub withdraw_fee_share: u128,
+    /// `Pool::FEE_DENOMINATOR` precision
+    pub deposit_fee_share: u128,
+    /// Rounded down to basis points
+    pub swap_fee_share_bp: u128,
+    /// Rounded down to basis points
+    pub withdraw_fee_share_bp: u128,
+    /// Rounded down to basis points
+    pub deposit_fee_share_bp: u128,
+    pub admin_fee_share_bp: u128,
+    pub offpeg_fee_multiplier_bp: u128,
+    /// system precision
+    pub total_lp_amount: u128,
+    /// `Pool::VIRTUAL_PRICE_PRECISION` is 1.0
+    pub virtual_price: u128,
+    pub admin: Address,
+    /// system precision, zero if no user was given
+    pub user_lp_amount: u128,
+    /// token precision, zero if no user was given
+    pub user_pending_rewards: (u128, u128, u128),
+}
+
+#[contracttype]
+#[derive(Debug)]
+pub struct SwapQuote {
+    /// token precision
+    pub output: u128,
+    /// token precision
+    pub fee: u128,
+    /// Output shortfall against the spot price of the curve, fee excluded
+    pub price_impact_bp: u128,
+    /// token precision
+    pub new_token_balances: (u128, u128, u128),
+}
+
+#[contracttype]
+#[derive(Debug)]
+pub struct DepositPreview {
+    /// system precision
+    pub lp_amount: u128,
+    /// Imbalance fees, token precision
+    pub fees: (u128, u128, u128),
+    /// Rewards claimed along with the deposit, token precision
+    pub rewards: (u128, u128, u128),
+    /// system precision
+    pub user_lp_amount: u128,
+}
+
+#[contracttype]
+#[derive(Debug)]
+pub struct WithdrawPreview {
+    /// system precision
+    pub lp_amount: u128,
+    /// Withdrawn tokens after fees and without rewards, token precision
+    pub amounts: (u128, u128, u128),
+    /// token precision
+    pub fees: (u128, u128, u128),
+    /// Rewards claimed along with the withdrawal, token precision
+    pub rewards: (u128, u128, u128),
+    /// system precision
+    pub user_lp_amount: u128,
+}
+
 pub struct DepositAmount {
     pub lp_amount: u128,
+    /// token precision
+    pub fees: TripleU128,
     pub new_token_balances: TripleU128,
 }
 
 impl Pool {
+    pub fn get_virtual_price(&self) -> Result<u128, Error> {
+        if self.total_lp_amount == 0 {
+            return Ok(0);
+        }
+
+        Ok(self.get_current_d()? * Self::VIRTUAL_PRICE_PRECISION / self.total_lp_amount)
+    }
+
+    pub fn get_token_balances(&self) -> TripleU128 {
+        self.amounts_from_system_precision(&self.token_balances)
+    }
+
+    pub(crate) fn amounts_from_system_precision(&self, amounts_sp: &TripleU128) -> TripleU128 {
+        let mut amounts = TripleU128::default();
+
+        for (index, amount_sp) in amounts_sp.to_array().into_iter().enumerate() {
+            amounts[index] =
+                self.amount_from_system_precision(amount_sp, self.tokens_decimals[index]);
+        }
+
+        amounts
+    }
+
+    pub fn quote_swap(
+        &self,
+        input: u128,
+        token_from: Token,
+        token_to: Token,
+    ) -> Result<SwapQuote, Error> {
+        require!(token_from as usize != token_to as usize, Error::InvalidArg);
+        require!(input > 0, Error::ZeroAmount);
+        require!(self.total_lp_amount > 0, Error::ZeroOutput);
+
+        let receive_amount = self.get_receive_amount(input, token_from, token_to)?;
+        require!(receive_amount.output > 0, Error::ZeroOutput);
+
+        let input_sp = receive_amount.token_from_new_balance - self.token_balances[token_from];
+        let output_sp = self.token_balances[token_to] - receive_amount.token_to_new_balance;
+        let spot_output_sp = self.get_spot_output(input_sp, token_from, token_to)?;
+
+        let mut new_token_balances = self.token_balances.clone();
+        new_token_balances[token_from] = receive_amount.token_from_new_balance;
+        new_token_balances[token_to] = receive_amount.token_to_new_balance;
+
+        Ok(SwapQuote {
+            output: receive_amount.output,
+            fee: receive_amount.fee,
+            price_impact_bp: num::price_impact_bp(spot_output_sp, output_sp),
+            new_token_balances: self.amounts_from_system_precision(&new_token_balances).data,
+        })
+    }
+
+    /// Output for `input_sp` at the marginal price of the curve, system precision
+    pub fn get_spot_output(
+        &self,
+        input_sp: u128,
+        token_from: Token,
+        token_to: Token,
+    ) -> Result<u128, Error> {
+        let balances = self.get_scaled_balances();
+        let x = balances[token_from];
+        let y = balances[token_to];
+        let axyz = U256::new(balances[0]) * balances[1] * balances[2] * (self.a * 729);
+        let dddd = num::square(num::square(U256::new(self.get_d0()?))?)?;
+
+        // dy/dx = y(729Axxyz + D⁴) / x(729Ayxyz + D⁴)
+        let output = num::spot_output(
+            self.scale_by_rate(input_sp, token_from as usize),
+            x,
+            y,
+            axyz.checked_mul(U256::new(x)).ok_or(Error::U256Overflow)? + dddd,
+            axyz.checked_mul(U256::new(y)).ok_or(Error::U256Overflow)? + dddd,
+        )?;
+
+        Ok(self.unscale_by_rate(output, token_to as usize))
+    }
+
+    #[inline]
+    pub fn is_offpeg_fee_enabled(&self) -> bool {
+        num::is_offpeg_fee_enabled(self.swap_fee_share, self.offpeg_fee_multiplier_bp)
+    }
+
+    /// Upper bound of the swap fee share, approached as one of the balances goes to zero
+    pub fn get_max_swap_fee_share(&self) -> u128 {
+        num::max_swap_fee_share(self.swap_fee_share, self.offpeg_fee_multiplier_bp)
+    }
+
+    /// Swap fee share as a `(numerator, denominator)` fraction for the balances of the swapped
+    /// tokens after the swap
+    pub fn get_swap_fee_share(&self, balance_sp: u128, other_balance_sp: u128) -> (U256, U256) {
+        num::swap_fee_share(
+            self.swap_fee_share,
+            self.offpeg_fee_multiplier_bp,
+            balance_sp,
+            other_balance_sp,
+        )
+    }
+
     pub fn get_receive_amount(
         &self,
         input: u128,
         token_from: Token,
         token_to: Token,
     ) -> Result<ReceiveAmount, Error> {
-        let d0 = self.total_lp_amount;
+        let d0 = self.get_d0()?;
+        let balances = self.get_scaled_balances();
         let input_sp = self.amount_to_system_precision(input, self.tokens_decimals[token_from]);
-        let mut output = 0;
+        let mut output_sp = 0;
 
         let token_from_new_balance = self.token_balances[token_from] + input_sp;
+        let scaled_from_new_balance =
+            self.scale_by_rate(token_from_new_balance, token_from as usize);
         let token_third = token_from.third(token_to);
 
-        let token_to_new_balance = self.get_y(token_from_new_balance, self.token_balances[token_third], d0)?;
-        if self.token_balances[token_to] > token_to_new_balance {
-            output = self.amount_from_system_precision(
-                self.token_balances[token_to] - token_to_new_balance,
-                self.tokens_decimals[token_to],
+        let scaled_to_new_balance =
+            self.get_y(scaled_from_new_balance, balances[token_third], d0)?;
+        if balances[token_to] > scaled_to_new_balance {
+            output_sp = self.unscale_by_rate(
+                balances[token_to] - scaled_to_new_balance,
+                token_to as usize,
             );
         }
-        let fee = output * self.fee_share_bp / Self::BP;
+        let token_to_new_balance = self.token_balances[token_to] - output_sp;
+        let mut output =
+            self.amount_from_system_precision(output_sp, self.tokens_decimals[token_to]);
+        let (fee_share, fee_share_denominator) =
+            self.get_swap_fee_share(scaled_from_new_balance, scaled_to_new_balance);
+        let fee = safe_cast(U256::new(output) * fee_share / fee_share_denominator)?;
 
         output -= fee;
 
@@ -74,21 +264,54 @@ impl Pool {
         })
     }
 
-    pub fn get_send_amount(&self, output: u128, token_from: Token, token_to: Token) -> Result<(u128, u128), Error> {
-        let d0 = self.total_lp_amount;
-        let fee = output * self.fee_share_bp / (Self::BP - self.fee_share_bp);
+    pub fn get_send_amount(
+        &self,
+        output: u128,
+        token_from: Token,
+        token_to: Token,
+    ) -> Result<(u128, u128), Error> {
+        let d0 = self.get_d0()?;
+        let balances = self.get_scaled_balances();
+        let token_third = token_from.third(token_to);
+        // Rounds up by at most one unit against the fee of `get_receive_amount`
+        let mut fee = output * self.swap_fee_share / (Self::FEE_DENOMINATOR - self.swap_fee_share);
+        if self.is_offpeg_fee_enabled() {
+            // The fee depends on the balances after the swap, which depend on the fee,
+            // so it is refined starting from the flat one until it settles
+            for _ in 0..Self::MAX_OFFPEG_FEE_ITERATIONS {
+                let output_sp =
+                    self.amount_to_system_precision(output + fee, self.tokens_decimals[token_to]);
+                let scaled_to_new_balance =
+                    balances[token_to] - self.scale_by_rate(output_sp, token_to as usize);
+                let scaled_from_new_balance =
+                    self.get_y(scaled_to_new_balance, balances[token_third], d0)?;
+                let (fee_share, fee_share_denominator) =
+                    self.get_swap_fee_share(scaled_from_new_balance, scaled_to_new_balance);
+                let new_fee: u128 =
+                    safe_cast(U256::new(output) * fee_share / (fee_share_denominator - fee_share))?;
+
+                if new_fee == fee {
+                    break;
+                }
+                fee = new_fee;
+            }
+        }
         let output_with_fee = output + fee;
         let output_sp =
             self.amount_to_system_precision(output_with_fee, self.tokens_decimals[token_to]);
         let mut input = 0;
 
-        let token_to_new_balance = self.token_balances[token_to] - output_sp;
-        let token_third = token_from.third(token_to);
+        let scaled_to_new_balance =
+            balances[token_to] - self.scale_by_rate(output_sp, token_to as usize);
 
-        let token_from_new_amount = self.get_y(token_to_new_balance, self.token_balances[token_third], d0)?;
-        if self.token_balances[token_from] < token_from_new_amount {
+        let scaled_from_new_balance =
+            self.get_y(scaled_to_new_balance, balances[token_third], d0)?;
+        if balances[token_from] < scaled_from_new_balance {
             input = self.amount_from_system_precision(
-                token_from_new_amount - self.token_balances[token_from],
+                self.unscale_by_rate(
+                    scaled_from_new_balance - balances[token_from],
+                    token_from as usize,
+                ),
                 self.tokens_decimals[token_from],
             );
         }
@@ -96,35 +319,84 @@ impl Pool {
         Ok((input, fee))
     }
 
+    pub fn preview_deposit(
+        &self,
+        user_deposit: &UserDeposit,
+        amounts: TripleU128,
+    ) -> Result<DepositPreview, Error> {
+        let deposit_amount = self.get_deposit_amount(amounts)?;
+
+        // Imbalance fees are distributed before the deposit, the user gets a share of them
+        let mut pool = self.clone();
+        for (index, fee) in deposit_amount.fees.to_array().into_iter().enumerate() {
+            pool.add_rewards(fee, index.into());
+        }
+
+        Ok(DepositPreview {
+            lp_amount: deposit_amount.lp_amount,
+            fees: deposit_amount.fees.data,
+            rewards: pool.get_pending(user_deposit).data,
+            user_lp_amount: user_deposit.lp_amount + deposit_amount.lp_amount,
+        })
+    }
+
+    pub fn preview_withdraw(
+        &self,
+        user_deposit: &UserDeposit,
+        lp_amount: u128,
+    ) -> Result<WithdrawPreview, Error> {
+        require!(user_deposit.lp_amount >= lp_amount, Error::NotEnoughAmount);
+
+        let withdraw_amount = self.get_withdraw_amount(lp_amount)?;
+
+        Ok(WithdrawPreview {
+            lp_amount,
+            amounts: self
+                .amounts_from_system_precision(&withdraw_amount.amounts)
+                .data,
+            fees: withdraw_amount.fees.data,
+            rewards: self.get_pending(user_deposit).data,
+            user_lp_amount: user_deposit.lp_amount - lp_amount,
+        })
+    }
+
     pub fn get_withdraw_amount(&self, lp_amount: u128) -> Result<WithdrawAmount, Error> {
-        let d0 = self.total_lp_amount;
+        let d0 = self.get_d0()?;
+        let balances = self.get_scaled_balances();
         let mut amounts = TripleU128::default();
 
-        let d1 = d0 - lp_amount;
+        let d1 = d0 - self.lp_amount_to_d(lp_amount, d0);
         let mut indices = [0, 1, 2];
         // Bubble sort implementation for indices
         for i in 0..indices.len() {
             for j in 0..indices.len() - 1 - i {
-                if self.token_balances[indices[j]] < self.token_balances[indices[j + 1]] {
+                if balances[indices[j]] < balances[indices[j + 1]] {
                     indices.swap(j, j + 1);
                 }
             }
         }
         let [more, less, mid] = indices;
 
-        let more_token_amount_sp = self.token_balances[more] * lp_amount / d0;
-        let mid_token_amount_sp = self.token_balances[mid] * lp_amount / d0;
-        let y = self.get_y(self.token_balances[more] - more_token_amount_sp, self.token_balances[mid] - mid_token_amount_sp, d1)?;
-        let less_token_amount_sp = self.token_balances[less] - y;
+        let more_token_amount_sp = self.token_balances[more] * lp_amount / self.total_lp_amount;
+        let mid_token_amount_sp = self.token_balances[mid] * lp_amount / self.total_lp_amount;
+        let y = self.get_y(
+            self.scale_by_rate(self.token_balances[more] - more_token_amount_sp, more),
+            self.scale_by_rate(self.token_balances[mid] - mid_token_amount_sp, mid),
+            d1,
+        )?;
+        let less_token_amount_sp = self.unscale_by_rate(balances[less] - y, less);
 
         let mut new_token_balances = self.token_balances.clone();
         let mut fees = TripleU128::default();
 
-        for (index, token_amount_sp) in [(more, more_token_amount_sp), (mid, mid_token_amount_sp), (less, less_token_amount_sp)]
-        {
+        for (index, token_amount_sp) in [
+            (more, more_token_amount_sp),
+            (mid, mid_token_amount_sp),
+            (less, less_token_amount_sp),
+        ] {
             let token_amount =
                 self.amount_from_system_precision(token_amount_sp, self.tokens_decimals[index]);
-            let fee = token_amount * self.fee_share_bp / Self::BP;
+            let fee = token_amount * self.withdraw_fee_share / Self::FEE_DENOMINATOR;
 
             let token_amount_sp =
                 self.amount_to_system_precision(token_amount - fee, self.tokens_decimals[index]);
@@ -143,7 +415,14 @@ impl Pool {
     }
 
     pub fn get_deposit_amount(&self, amounts: TripleU128) -> Result<DepositAmount, Error> {
-        let d0 = self.total_lp_amount;
+        if self.total_lp_amount == 0 {
+            require!(
+                amounts.data.0 == amounts.data.1 && amounts.data.1 == amounts.data.2,
+                Error::InvalidFirstDeposit
+            );
+        }
+
+        let d0 = self.get_d0()?;
 
         let amounts_sp = TripleU128::from((
             self.amount_to_system_precision(amounts[0], self.tokens_decimals[0]),
@@ -164,7 +443,7 @@ impl Pool {
             new_token_balances_sp[index] += amounts_sp[index];
         }
 
-        let d1 = self.get_d(new_token_balances_sp[0], new_token_balances_sp[1], new_token_balances_sp[2])?;
+        let d1 = self.get_d_for_balances(&new_token_balances_sp)?;
 
         require!(d1 > d0, Error::Forbidden);
         require!(
@@ -172,13 +451,68 @@ impl Pool {
             Error::PoolOverflow
         );
 
-        let lp_amount = d1 - d0;
+        let fees = self.charge_imbalance_fees(
+            &mut new_token_balances_sp,
+            self.get_imbalance_fee_d0()?,
+            d1,
+        );
+        let d2 = self.get_d_for_balances(&new_token_balances_sp)?;
+
+        require!(d2 > d0, Error::Forbidden);
 
         Ok(DepositAmount {
-            lp_amount,
+            lp_amount: self.d_to_lp_amount(d2 - d0, d0),
+            fees,
             new_token_balances: new_token_balances_sp,
         })
     }
+
+    /// LP amount worth `d_amount` of the invariant, equal to it until the rates move
+    fn d_to_lp_amount(&self, d_amount: u128, d0: u128) -> u128 {
+        if self.total_lp_amount == 0 {
+            return d_amount;
+        }
+
+        d_amount * self.total_lp_amount / d0
+    }
+
+    fn lp_amount_to_d(&self, lp_amount: u128, d0: u128) -> u128 {
+        lp_amount * d0 / self.total_lp_amount
+    }
+
+    /// Charges the StableSwap imbalance fee on the deviation of every balance from its
+    /// proportional share of `d1` and removes it from the balances, returns fees in token precision
+    fn charge_imbalance_fees(
+        &self,
+        new_token_balances_sp: &mut TripleU128,
+        d0: u128,
+        d1: u128,
+    ) -> TripleU128 {
+        let mut fees = TripleU128::default();
+
+        if d0 == 0 {
+            return fees;
+        }
+
+        let old_balances = self.get_scaled_balances();
+
+        for (index, old_balance) in old_balances.to_array().into_iter().enumerate() {
+            let fee = num::imbalance_fee(
+                old_balance,
+                self.scale_by_rate(new_token_balances_sp[index], index),
+                d0,
+                d1,
+                self.deposit_fee_share,
+                3,
+            );
+            let fee_sp = self.unscale_by_rate(fee, index);
+
+            new_token_balances_sp[index] -= fee_sp;
+            fees[index] = self.amount_from_system_precision(fee_sp, self.tokens_decimals[index]);
+        }
+
+        fees
+    }
 }
 
 #[allow(clippy::inconsistent_digit_grouping)]
@@ -190,8 +524,8 @@ mod tests {
     use shared::{soroban_data::SimpleSorobanData, Error};
     use soroban_sdk::{contract, contractimpl, testutils::Address as _, Address, Env};
 
-    use crate::storage::{common::Token, pool::Pool};
     use crate::storage::triple_values::TripleU128;
+    use crate::storage::{common::Token, pool::Pool};
 
     #[contract]
     pub struct TestPool;
@@ -213,13 +547,21 @@ mod tests {
             })
         }
 
+        pub fn set_swap_fee_share(env: Env, swap_fee_share: u128) -> Result<(), Error> {
+            Pool::update(&env, |pool| {
+                pool.swap_fee_share = swap_fee_share;
+                Ok(())
+            })
+        }
+
         pub fn get_receive_amount(
             env: Env,
             amount: u128,
             token_from: Token,
             token_to: Token,
         ) -> Result<(u128, u128), Error> {
-            let receive_amount = Pool::get(&env)?.get_receive_amount(amount, token_from, token_to)?;
+            let receive_amount =
+                Pool::get(&env)?.get_receive_amount(amount, token_from, token_to)?;
             Ok((receive_amount.output, receive_amount.fee))
         }
 
@@ -274,4 +616,26 @@ mod tests {
         assert_eq!(input, calc_input);
         assert_eq!(fee, calc_fee);
     }
+
+    #[test]
+    fn test_sub_bp_fee() {
+        let env = Env::default();
+
+        let test_pool_id = env.register_contract(None, TestPool);
+        let pool = TestPoolClient::new(&env, &test_pool_id);
+        pool.init();
+        pool.set_balances(&(200_000_000, 500_000_000, 200_000_000));
+        // 0.004%
+        pool.set_swap_fee_share(&40);
+
+        let input = 10_000_0000000_u128;
+        let (output, fee) = pool.get_receive_amount(&input, &Token::A, &Token::B);
+        let (calc_input, calc_fee) = pool.get_send_amount(&output, &Token::A, &Token::B);
+
+        assert_eq!(output, 100_625_664_813);
+        assert_eq!(fee, 4_025_187);
+        assert_eq!(fee, (output + fee) * 40 / Pool::FEE_DENOMINATOR);
+        assert_eq!(input, calc_input);
+        assert_eq!(fee, calc_fee);
+    }
 }
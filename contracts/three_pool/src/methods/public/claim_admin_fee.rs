@@ -1,14 +1,18 @@
-use shared::{soroban_data::SimpleSorobanData, utils::safe_cast, Error};
+use shared::{soroban_data::SimpleSorobanData, utils::safe_cast, Error, Event};
 use soroban_sdk::Env;
-use storage::Admin;
+use storage::{Admin, OperationNonce};
 
-use crate::storage::pool::Pool;
+use crate::{
+    events::{AdminFeeClaimed, Sync},
+    storage::pool::Pool,
+};
 
 pub fn claim_admin_fee(env: Env) -> Result<(), Error> {
     let admin = Admin::get(&env)?;
     admin.require_auth();
 
     let mut pool = Pool::get(&env)?;
+    let fees = pool.admin_fee_amount.clone();
 
     for (index, _) in pool.tokens.to_array().into_iter().enumerate() {
         if pool.admin_fee_amount[index] > 0 {
@@ -22,5 +26,15 @@ pub fn claim_admin_fee(env: Env) -> Result<(), Error> {
         }
     }
 
+    if !fees.is_zero() {
+        AdminFeeClaimed {
+            admin: admin.0,
+            fees: fees.data,
+        }
+        .publish(&env);
+    }
+
+    Sync::from_pool(&pool, OperationNonce::next(&env)).publish(&env);
+
     Ok(())
 }
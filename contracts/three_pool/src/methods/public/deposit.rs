@@ -1,23 +1,25 @@
 use shared::{soroban_data::SimpleSorobanData, Error, Event};
 use soroban_sdk::{Address, Env};
+use storage::OperationNonce;
 
 use crate::{
-    events::{Deposit, RewardsClaimed},
-    storage::{triple_values::TripleU128, pool::Pool, user_deposit::UserDeposit},
+    events::{Deposit, RewardsClaimed, Sync},
+    storage::{pool::Pool, triple_values::TripleU128, user_deposit::UserDeposit},
 };
 
+/// Returns the minted LP amount
 pub fn deposit(
     env: Env,
     sender: Address,
     amounts: (u128, u128, u128),
     min_lp_amount: u128,
-) -> Result<(), Error> {
+) -> Result<u128, Error> {
     sender.require_auth();
-    let mut pool = Pool::get(&env)?;
+    let mut pool = Pool::get_with_rates(&env)?;
     let mut user_deposit = UserDeposit::get(&env, sender.clone());
     let amounts = TripleU128::from(amounts);
 
-    let (rewards, lp_amount) = pool.deposit(
+    let (deposit_amount, rewards) = pool.deposit(
         &env,
         amounts.clone(),
         sender.clone(),
@@ -30,8 +32,9 @@ pub fn deposit(
 
     Deposit {
         user: sender.clone(),
-        lp_amount,
+        lp_amount: deposit_amount.lp_amount,
         amounts: amounts.data,
+        fees: deposit_amount.fees.data,
     }
     .publish(&env);
 
@@ -43,5 +46,7 @@ pub fn deposit(
         .publish(&env);
     }
 
-    Ok(())
+    Sync::from_pool(&pool, OperationNonce::next(&env)).publish(&env);
+
+    Ok(deposit_amount.lp_amount)
 }
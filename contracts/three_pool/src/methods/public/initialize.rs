@@ -1,6 +1,6 @@
 use shared::{require, soroban_data::SimpleSorobanData, Error};
 use soroban_sdk::{token, Address, Env};
-use storage::Admin;
+use storage::{Admin, Upgrader};
 
 use crate::storage::pool::Pool;
 
@@ -14,6 +14,7 @@ pub fn initialize(
     token_c: Address,
     fee_share_bp: u128,
     admin_fee_share_bp: u128,
+    upgrader: Address,
 ) -> Result<(), Error> {
     require!(!Pool::has(&env), Error::Initialized);
 
@@ -36,6 +37,7 @@ pub fn initialize(
     )
     .save(&env);
     Admin(admin).save(&env);
+    Upgrader(upgrader).save(&env);
 
     Ok(())
 }
@@ -1,15 +1,19 @@
+mod admin;
 mod claim_admin_fee;
 mod claim_rewards;
 mod deposit;
 mod initialize;
-mod admin;
+mod migrate;
 mod swap;
+mod transfer_lp;
 mod withdraw;
 
+pub use admin::*;
 pub use claim_admin_fee::*;
 pub use claim_rewards::*;
 pub use deposit::*;
 pub use initialize::*;
-pub use admin::*;
+pub use migrate::*;
 pub use swap::*;
+pub use transfer_lp::*;
 pub use withdraw::*;
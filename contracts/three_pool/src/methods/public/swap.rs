@@ -1,11 +1,12 @@
 use shared::{soroban_data::SimpleSorobanData, Error, Event};
 use soroban_sdk::{Address, Env};
+use storage::OperationNonce;
 
+use crate::storage::common::Token;
 use crate::{
-    events::Swapped,
-    storage::{pool::Pool},
+    events::{Swapped, Sync},
+    storage::pool::Pool,
 };
-use crate::storage::common::Token;
 
 pub fn swap(
     env: Env,
@@ -17,7 +18,7 @@ pub fn swap(
     token_to: Token,
 ) -> Result<u128, Error> {
     sender.require_auth();
-    let mut pool = Pool::get(&env)?;
+    let mut pool = Pool::get_with_rates(&env)?;
 
     let (to_amount, fee) = pool.swap(
         &env,
@@ -26,7 +27,7 @@ pub fn swap(
         from_amount,
         receive_amount_min,
         token_from,
-        token_to
+        token_to,
     )?;
 
     pool.save(&env);
@@ -42,5 +43,7 @@ pub fn swap(
     }
     .publish(&env);
 
+    Sync::from_pool(&pool, OperationNonce::next(&env)).publish(&env);
+
     Ok(to_amount)
 }
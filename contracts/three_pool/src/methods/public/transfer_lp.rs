@@ -0,0 +1,52 @@
+use shared::{require, soroban_data::SimpleSorobanData, Error, Event};
+use soroban_sdk::{Address, Env};
+
+use crate::{
+    events::{LpTransferred, RewardsClaimed},
+    storage::{pool::Pool, user_deposit::UserDeposit},
+};
+
+pub fn transfer_lp(
+    env: Env,
+    sender: Address,
+    recipient: Address,
+    lp_amount: u128,
+) -> Result<(), Error> {
+    sender.require_auth();
+    require!(sender != recipient, Error::InvalidArg);
+    require!(lp_amount > 0, Error::ZeroAmount);
+
+    let mut pool = Pool::get(&env)?;
+    let mut sender_deposit = UserDeposit::get(&env, sender.clone());
+    let mut recipient_deposit = UserDeposit::get(&env, recipient.clone());
+
+    let (sender_rewards, recipient_rewards) = pool.transfer_lp(
+        &env,
+        (&sender, &mut sender_deposit),
+        (&recipient, &mut recipient_deposit),
+        lp_amount,
+    )?;
+
+    pool.save(&env);
+    sender_deposit.save(&env, sender.clone());
+    recipient_deposit.save(&env, recipient.clone());
+
+    LpTransferred {
+        sender: sender.clone(),
+        recipient: recipient.clone(),
+        lp_amount,
+    }
+    .publish(&env);
+
+    for (user, rewards) in [(sender, sender_rewards), (recipient, recipient_rewards)] {
+        if !rewards.is_zero() {
+            RewardsClaimed {
+                user,
+                rewards: rewards.data,
+            }
+            .publish(&env);
+        }
+    }
+
+    Ok(())
+}
@@ -0,0 +1,54 @@
+use proc_macros::{extend_ttl_info_instance, Instance, SorobanData, SorobanSimpleData};
+use shared::{rate_provider::RATE_PRECISION, soroban_data::SymbolKey};
+use soroban_sdk::contracttype;
+
+use super::{
+    pool::Pool,
+    triple_values::{TripleAddress, TripleRateSource, TripleU128, TripleU32},
+};
+
+/// `Pool` layout of the pools deployed before the fee was split into swap, withdraw and deposit
+/// fees, stored under the same key
+#[contracttype]
+#[derive(Debug, Clone, SorobanData, SorobanSimpleData, Instance)]
+#[extend_ttl_info_instance]
+pub struct LegacyPool {
+    pub a: u128,
+
+    pub fee_share_bp: u128,
+    pub admin_fee_share_bp: u128,
+    pub total_lp_amount: u128,
+
+    pub tokens: TripleAddress,
+    pub tokens_decimals: TripleU32,
+    pub token_balances: TripleU128,
+    pub acc_rewards_per_share_p: TripleU128,
+    pub admin_fee_amount: TripleU128,
+}
+
+impl SymbolKey for LegacyPool {
+    const STORAGE_KEY: &'static str = Pool::STORAGE_KEY;
+}
+
+impl From<LegacyPool> for Pool {
+    fn from(legacy_pool: LegacyPool) -> Self {
+        Pool {
+            a: legacy_pool.a,
+
+            swap_fee_share: Pool::fee_share_from_bp(legacy_pool.fee_share_bp),
+            withdraw_fee_share: Pool::fee_share_from_bp(legacy_pool.fee_share_bp),
+            deposit_fee_share: 0,
+            admin_fee_share_bp: legacy_pool.admin_fee_share_bp,
+            offpeg_fee_multiplier_bp: 0,
+            total_lp_amount: legacy_pool.total_lp_amount,
+
+            tokens: legacy_pool.tokens,
+            tokens_decimals: legacy_pool.tokens_decimals,
+            rate_sources: TripleRateSource::default(),
+            rates: TripleU128::from((RATE_PRECISION, RATE_PRECISION, RATE_PRECISION)),
+            token_balances: legacy_pool.token_balances,
+            acc_rewards_per_share_p: legacy_pool.acc_rewards_per_share_p,
+            admin_fee_amount: legacy_pool.admin_fee_amount,
+        }
+    }
+}
@@ -1,4 +1,5 @@
 pub mod common;
+pub mod legacy_pool;
 pub mod pool;
-pub mod user_deposit;
 pub mod triple_values;
+pub mod user_deposit;
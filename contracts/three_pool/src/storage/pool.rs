@@ -1,4 +1,11 @@
 use proc_macros::{extend_ttl_info_instance, Instance, SorobanData, SorobanSimpleData, SymbolKey};
+use shared::{
+    price_feed::read_price_rate,
+    rate_provider::{read_provider_rate, RATE_PRECISION},
+    require,
+    soroban_data::SimpleSorobanData,
+    Error,
+};
 use soroban_sdk::{
     contracttype,
     token::{self, TokenClient},
@@ -6,8 +13,8 @@ use soroban_sdk::{
 };
 
 use super::{
-    common::Token,
-    triple_values::{TripleAddress, TripleU128, TripleU32},
+    common::{RateSource, Token},
+    triple_values::{TripleAddress, TripleRateSource, TripleU128, TripleU32},
 };
 
 #[contracttype]
@@ -16,12 +23,23 @@ use super::{
 pub struct Pool {
     pub a: u128,
 
-    pub fee_share_bp: u128,
+    /// `Pool::FEE_DENOMINATOR` precision
+    pub swap_fee_share: u128,
+    /// `Pool::FEE_DENOMINATOR` precision
+    pub withdraw_fee_share: u128,
+    /// `Pool::FEE_DENOMINATOR` precision, charged on the imbalanced part of a deposit, zero
+    /// until the admin sets it so deposits keep their previous cost
+    pub deposit_fee_share: u128,
     pub admin_fee_share_bp: u128,
+    /// Scales the swap fee up as the pool gets imbalanced, `Pool::BP` and below keep the fee flat
+    pub offpeg_fee_multiplier_bp: u128,
     pub total_lp_amount: u128,
 
     pub tokens: TripleAddress,
     pub tokens_decimals: TripleU32,
+    pub rate_sources: TripleRateSource,
+    /// Rates last read from `rate_sources`, `RATE_PRECISION` precision
+    pub rates: TripleU128,
     pub token_balances: TripleU128,
     pub acc_rewards_per_share_p: TripleU128,
     pub admin_fee_amount: TripleU128,
@@ -40,18 +58,99 @@ impl Pool {
         Pool {
             a,
 
-            fee_share_bp,
+            swap_fee_share: Self::fee_share_from_bp(fee_share_bp),
+            withdraw_fee_share: Self::fee_share_from_bp(fee_share_bp),
+            deposit_fee_share: 0,
             admin_fee_share_bp,
+            offpeg_fee_multiplier_bp: 0,
             total_lp_amount: 0,
 
             tokens: TripleAddress::from((token_a, token_b, token_c)),
             tokens_decimals: TripleU32::from(decimals),
+            rate_sources: TripleRateSource::default(),
+            rates: TripleU128::from((RATE_PRECISION, RATE_PRECISION, RATE_PRECISION)),
             token_balances: TripleU128::default(),
             acc_rewards_per_share_p: TripleU128::default(),
             admin_fee_amount: TripleU128::default(),
         }
     }
 
+    /// Loads the pool with the rates refreshed from its rate providers and oracles
+    pub fn get_with_rates(env: &Env) -> Result<Self, Error> {
+        let mut pool = Self::get(env)?;
+        pool.update_rates(env)?;
+
+        Ok(pool)
+    }
+
+    /// Loads the pool for withdrawals and views, a rate source that cannot be read keeps
+    /// the last rate stored in the pool so a failing provider or oracle does not lock funds
+    pub fn get_with_last_rates(env: &Env) -> Result<Self, Error> {
+        let mut pool = Self::get(env)?;
+
+        for (index, rate_source) in pool.rate_sources.to_array().into_iter().enumerate() {
+            if let Ok(rate) = Self::read_rate(env, &rate_source) {
+                pool.rates[index] = rate;
+            }
+        }
+
+        Ok(pool)
+    }
+
+    pub fn update_rates(&mut self, env: &Env) -> Result<(), Error> {
+        for (index, rate_source) in self.rate_sources.to_array().into_iter().enumerate() {
+            self.rates[index] = Self::read_rate(env, &rate_source)?;
+        }
+
+        Ok(())
+    }
+
+    /// `RATE_PRECISION` precision
+    pub fn read_rate(env: &Env, rate_source: &RateSource) -> Result<u128, Error> {
+        let rate = match rate_source {
+            RateSource::Fixed => RATE_PRECISION,
+            RateSource::Provider(rate_provider) => read_provider_rate(env, rate_provider)?,
+            RateSource::Oracle(oracle) => {
+                let rate = read_price_rate(env, &oracle.price_feed, oracle.max_price_age)?;
+
+                let max_deviation = oracle
+                    .peg_rate
+                    .checked_mul(oracle.max_price_deviation_bp)
+                    .ok_or(Error::U256Overflow)?;
+                require!(
+                    rate.abs_diff(oracle.peg_rate)
+                        .checked_mul(Self::BP)
+                        .is_some_and(|deviation| deviation <= max_deviation),
+                    Error::PriceDeviation
+                );
+
+                rate
+            }
+        };
+
+        require!(rate > 0, Error::InvalidRate);
+
+        Ok(rate)
+    }
+
+    #[inline]
+    pub fn has_dynamic_rates(&self) -> bool {
+        self.rate_sources
+            .to_array()
+            .iter()
+            .any(|rate_source| !matches!(rate_source, RateSource::Fixed))
+    }
+
+    #[inline]
+    pub fn fee_share_from_bp(fee_share_bp: u128) -> u128 {
+        fee_share_bp * (Self::FEE_DENOMINATOR / Self::BP)
+    }
+
+    #[inline]
+    pub fn fee_share_to_bp(fee_share: u128) -> u128 {
+        fee_share / (Self::FEE_DENOMINATOR / Self::BP)
+    }
+
     #[inline]
     pub fn get_token_by_index(&self, env: &Env, index: usize) -> TokenClient<'_> {
         token::Client::new(env, &self.tokens[index])
@@ -5,7 +5,7 @@ use core::{
     ops::{Index, IndexMut},
 };
 
-use super::common::Token;
+use super::common::{RateSource, Token};
 
 #[macro_export]
 macro_rules! triple_value {
@@ -18,7 +18,11 @@ macro_rules! triple_value {
 
         impl $name {
             pub fn to_array(&self) -> [$inner_type; 3] {
-                [self.data.0.clone(), self.data.1.clone(), self.data.2.clone()]
+                [
+                    self.data.0.clone(),
+                    self.data.1.clone(),
+                    self.data.2.clone(),
+                ]
             }
         }
 
@@ -81,6 +85,15 @@ macro_rules! triple_value {
 triple_value!(TripleAddress, Address);
 triple_value!(TripleU128, u128);
 triple_value!(TripleU32, u32);
+triple_value!(TripleRateSource, RateSource);
+
+impl Default for TripleRateSource {
+    fn default() -> Self {
+        Self {
+            data: (RateSource::Fixed, RateSource::Fixed, RateSource::Fixed),
+        }
+    }
+}
 
 #[allow(clippy::derivable_impls)]
 impl Default for TripleU128 {
@@ -6,6 +6,16 @@ pub mod three_pool {
     soroban_sdk::contractimport!(file = "../target/wasm32-unknown-unknown/release/three_pool.wasm");
 }
 
+pub mod stable_pool {
+    soroban_sdk::contractimport!(
+        file = "../target/wasm32-unknown-unknown/release/stable_pool.wasm"
+    );
+}
+
+pub mod metapool {
+    soroban_sdk::contractimport!(file = "../target/wasm32-unknown-unknown/release/metapool.wasm");
+}
+
 pub mod factory {
     #![allow(clippy::too_many_arguments)]
     soroban_sdk::contractimport!(file = "../target/wasm32-unknown-unknown/release/factory.wasm");
@@ -1,6 +1,68 @@
-use soroban_sdk::{testutils::Address as _, testutils::BytesN as _, Address, BytesN};
+use soroban_sdk::{
+    testutils::Address as _, testutils::BytesN as _, testutils::Ledger, vec, Address, BytesN, Map,
+    Symbol, Vec,
+};
 
-use crate::utils::TestingEnv;
+use crate::{
+    contracts::{
+        factory::{
+            AdminChanged, CreationFee, CreationFeeInfo, CreationFeeUpdated, LegacyFactoryInfo,
+            PairCreated, ParamRange, PermissionlessConfig, PermissionlessConfigUpdated, PoolAdmin,
+            PoolFeeShare, PoolStatus, PoolStatusUpdated, PoolType, PoolsUpgraded,
+            StablePoolCreated, StablePoolWasmHashUpdated, TokenAllowlistEnabled,
+            TokenAllowlistUpdated, TwoPoolWasmHashUpdated,
+        },
+        pool, three_pool,
+    },
+    utils::{
+        desoroban_result, float_to_uint, float_to_uint_sp, get_latest_event, unwrap_call_result,
+        MockToken, PoolFactory, TestingEnv, Token,
+    },
+};
+
+fn generate_tokens(testing_env: &TestingEnv, count: usize) -> Vec<Address> {
+    let mut tokens = Vec::new(&testing_env.env);
+    for _ in 0..count {
+        tokens.push_back(Token::create(&testing_env.env, testing_env.admin.as_ref()).id);
+    }
+
+    tokens
+}
+
+fn permissionless_config(testing_env: &TestingEnv, pool_admin: PoolAdmin) -> PermissionlessConfig {
+    PermissionlessConfig {
+        enabled: true,
+        a: ParamRange { min: 10, max: 50 },
+        fee_share_bp: ParamRange { min: 1, max: 30 },
+        admin_fee_share_bp: ParamRange { min: 0, max: 5_000 },
+        pool_admin,
+        tiers: vec![&testing_env.env, 1, 2],
+    }
+}
+
+fn create_pool_permissionless(
+    testing_env: &TestingEnv,
+    config: &PermissionlessConfig,
+    a: u128,
+    fee_share_bp: u128,
+) -> Address {
+    let client = &testing_env.factory.client;
+    client.set_permissionless_config(config);
+    let (first_token, second_token) =
+        TestingEnv::generate_token_pair(&testing_env.env, testing_env.admin.as_ref());
+
+    unwrap_call_result(
+        &testing_env.env,
+        desoroban_result(client.try_create_pool_permissionless(
+            testing_env.bob.as_ref(),
+            &a,
+            &vec![&testing_env.env, first_token.id, second_token.id],
+            &fee_share_bp,
+            &0,
+            &1,
+        )),
+    )
+}
 
 #[test]
 #[should_panic = "Context(InvalidAction)"]
@@ -37,6 +99,9 @@ fn set_admin() {
 
     testing_env.factory.set_admin(new_admin.clone());
     assert_eq!(testing_env.factory.client.get_admin(), new_admin);
+
+    let event = get_latest_event::<AdminChanged>(&testing_env.env).expect("Expected AdminChanged");
+    assert_eq!(event.new_admin, new_admin);
 }
 
 #[test]
@@ -47,7 +112,14 @@ fn update_wasm_hash() {
 
     testing_env.factory.update_wasm_hash(&new_wasm_hash);
 
-    assert_eq!(testing_env.factory.client.get_two_pool_wasm_hash(), new_wasm_hash);
+    assert_eq!(
+        testing_env.factory.client.get_two_pool_wasm_hash(),
+        new_wasm_hash
+    );
+
+    let event = get_latest_event::<TwoPoolWasmHashUpdated>(&testing_env.env)
+        .expect("Expected TwoPoolWasmHashUpdated");
+    assert_eq!(event.new_wasm_hash, new_wasm_hash);
 }
 
 #[test]
@@ -173,6 +245,17 @@ fn add_new_pair() {
     let pool = testing_env.factory.pool(&yellow_token.id, &duck_token.id);
 
     assert_eq!(deployed_pool, pool);
+
+    let event = get_latest_event::<PairCreated>(&testing_env.env).expect("Expected PairCreated");
+    let (token0, token1) = if yellow_token.id < duck_token.id {
+        (yellow_token.id, duck_token.id)
+    } else {
+        (duck_token.id, yellow_token.id)
+    };
+
+    assert_eq!(event.token0, token0);
+    assert_eq!(event.token1, token1);
+    assert_eq!(event.pool, deployed_pool);
 }
 
 #[test]
@@ -192,15 +275,15 @@ fn get_pool() {
 }
 
 #[test]
-#[should_panic = "DexContract(MaxPoolsNumReached)"]
 fn add_new_pairs() {
     let testing_env = TestingEnv::default();
 
-    for _ in 0..20 {
+    // Past the number of pools the factory used to be capped at
+    for _ in 0..25 {
         let (first_token, second_token) =
             TestingEnv::generate_token_pair(&testing_env.env, testing_env.admin.as_ref());
 
-        let _ = testing_env.factory.create_pool(
+        let pool = testing_env.factory.create_pool(
             testing_env.admin.as_ref(),
             10,
             &first_token.id,
@@ -208,17 +291,1513 @@ fn add_new_pairs() {
             10,
             10,
         );
+        assert_eq!(
+            testing_env.factory.pool(&first_token.id, &second_token.id),
+            pool
+        );
     }
 
+    assert_eq!(testing_env.factory.client.pools().len(), 26);
+}
+
+fn create_tiered_pool(testing_env: &TestingEnv, a: u128, tier: u32) -> Address {
+    let admin = testing_env.admin.as_ref();
+
+    unwrap_call_result(
+        &testing_env.env,
+        desoroban_result(testing_env.factory.client.try_create_pool(
+            admin,
+            admin,
+            &a,
+            &vec![
+                &testing_env.env,
+                testing_env.yusd_token.id.clone(),
+                testing_env.yaro_token.id.clone(),
+            ],
+            &1,
+            &0,
+            &tier,
+        )),
+    )
+}
+
+#[test]
+fn add_pool_tier() {
+    let testing_env = TestingEnv::default();
+    let tokens = vec![
+        &testing_env.env,
+        testing_env.yaro_token.id.clone(),
+        testing_env.yusd_token.id.clone(),
+    ];
+
+    let tiered_pool = create_tiered_pool(&testing_env, 60, 1);
+
+    let client = &testing_env.factory.client;
+    assert_ne!(tiered_pool, testing_env.pool.id);
+    assert_eq!(client.pool(&tokens, &None), testing_env.pool.id);
+    assert_eq!(client.pool(&tokens, &Some(0)), testing_env.pool.id);
+    assert_eq!(client.pool(&tokens, &Some(1)), tiered_pool);
+    assert_eq!(
+        client.pool_variants(&tokens),
+        vec![
+            &testing_env.env,
+            testing_env.pool.id.clone(),
+            tiered_pool.clone()
+        ]
+    );
+    assert_eq!(client.pool_info(&tiered_pool).tier, 1);
+    assert_eq!(
+        pool::Client::new(&testing_env.env, &tiered_pool)
+            .get_pool()
+            .a,
+        60
+    );
+}
+
+#[test]
+#[should_panic = "DexContract(PoolExist)"]
+fn pool_tier_exist() {
+    let testing_env = TestingEnv::default();
+
+    create_tiered_pool(&testing_env, 60, 1);
+    create_tiered_pool(&testing_env, 10, 1);
+}
+
+#[test]
+#[should_panic = "DexContract(NotFound)"]
+fn pool_unknown_tier() {
+    let testing_env = TestingEnv::default();
+    let tokens = vec![
+        &testing_env.env,
+        testing_env.yaro_token.id.clone(),
+        testing_env.yusd_token.id.clone(),
+    ];
+
+    unwrap_call_result(
+        &testing_env.env,
+        desoroban_result(testing_env.factory.client.try_pool(&tokens, &Some(1))),
+    );
+}
+
+fn create_pool_with_liquidity(
+    testing_env: &TestingEnv,
+    tokens: &Vec<Address>,
+    amounts: Vec<u128>,
+    min_lp_amount: u128,
+) -> Address {
+    let alice = testing_env.alice.as_ref();
+
+    unwrap_call_result(
+        &testing_env.env,
+        desoroban_result(testing_env.factory.client.try_create_pool_with_liquidity(
+            alice,
+            testing_env.admin.as_ref(),
+            &10,
+            tokens,
+            &1,
+            &0,
+            &0,
+            &amounts,
+            &min_lp_amount,
+        )),
+    )
+}
+
+fn generate_funded_token_pair(testing_env: &TestingEnv) -> (Token, Token) {
     let (first_token, second_token) =
         TestingEnv::generate_token_pair(&testing_env.env, testing_env.admin.as_ref());
+    first_token.airdrop(&testing_env.alice, 1_000.0);
+    second_token.airdrop(&testing_env.alice, 1_000.0);
+
+    (first_token, second_token)
+}
+
+#[test]
+fn create_pool_with_liquidity_deposit() {
+    let testing_env = TestingEnv::default();
+    let (first_token, second_token) = generate_funded_token_pair(&testing_env);
+    let tokens = vec![
+        &testing_env.env,
+        second_token.id.clone(),
+        first_token.id.clone(),
+    ];
+    let amount = float_to_uint(100.0, 7);
+
+    let pool = create_pool_with_liquidity(
+        &testing_env,
+        &tokens,
+        vec![&testing_env.env, amount, amount],
+        0,
+    );
+
+    assert_eq!(testing_env.factory.client.pool(&tokens, &None), pool);
+    assert_eq!(first_token.balance_of(&pool), amount);
+    assert_eq!(second_token.balance_of(&pool), amount);
+
+    let created = get_latest_event::<PairCreated>(&testing_env.env).expect("Expected PairCreated");
+    assert_eq!(created.pool, pool);
+
+    let deposit = get_latest_event::<pool::Deposit>(&testing_env.env).expect("Expected Deposit");
+    let user_deposit =
+        pool::Client::new(&testing_env.env, &pool).get_user_deposit(testing_env.alice.as_ref());
+    assert_eq!(deposit.user, testing_env.alice.as_address());
+    assert_eq!(deposit.amounts, (amount, amount));
+    assert!(deposit.lp_amount > 0);
+    assert_eq!(user_deposit.lp_amount, deposit.lp_amount);
+}
+
+#[test]
+#[should_panic = "DexContract(Slippage)"]
+fn create_pool_with_liquidity_slippage() {
+    let testing_env = TestingEnv::default();
+    let (first_token, second_token) = generate_funded_token_pair(&testing_env);
+    let tokens = vec![&testing_env.env, first_token.id, second_token.id];
+    let amount = float_to_uint(100.0, 7);
+
+    create_pool_with_liquidity(
+        &testing_env,
+        &tokens,
+        vec![&testing_env.env, amount, amount],
+        float_to_uint_sp(1_000.0),
+    );
+}
+
+#[test]
+#[should_panic = "DexContract(InvalidFirstDeposit)"]
+fn create_pool_with_liquidity_unbalanced() {
+    let testing_env = TestingEnv::default();
+    let (first_token, second_token) = generate_funded_token_pair(&testing_env);
+    let tokens = vec![&testing_env.env, first_token.id, second_token.id];
+
+    create_pool_with_liquidity(
+        &testing_env,
+        &tokens,
+        vec![
+            &testing_env.env,
+            float_to_uint(100.0, 7),
+            float_to_uint(50.0, 7),
+        ],
+        0,
+    );
+}
+
+#[test]
+#[should_panic = "DexContract(InvalidArg)"]
+fn create_pool_with_liquidity_amounts_mismatch() {
+    let testing_env = TestingEnv::default();
+    let (first_token, second_token) = generate_funded_token_pair(&testing_env);
+    let tokens = vec![&testing_env.env, first_token.id, second_token.id];
+
+    create_pool_with_liquidity(
+        &testing_env,
+        &tokens,
+        vec![&testing_env.env, float_to_uint(100.0, 7)],
+        0,
+    );
+}
+
+#[test]
+fn predict_pool_address() {
+    let testing_env = TestingEnv::default();
+    let admin = testing_env.admin.as_ref();
+    let (first_token, second_token) = TestingEnv::generate_token_pair(&testing_env.env, admin);
+    let tokens = vec![
+        &testing_env.env,
+        first_token.id.clone(),
+        second_token.id.clone(),
+    ];
+    let reversed_tokens = vec![
+        &testing_env.env,
+        second_token.id.clone(),
+        first_token.id.clone(),
+    ];
+
+    let client = &testing_env.factory.client;
+    let predicted_pool = client.predict_pool_address(admin, &tokens, &None);
+    assert_eq!(
+        client.predict_pool_address(admin, &reversed_tokens, &None),
+        predicted_pool
+    );
+
+    let deployed_pool =
+        testing_env
+            .factory
+            .create_pool(admin, 10, &second_token.id, &first_token.id, 10, 10);
+    assert_eq!(deployed_pool, predicted_pool);
+}
+
+#[test]
+fn predict_pool_address_tier() {
+    let testing_env = TestingEnv::default();
+    let admin = testing_env.admin.as_ref();
+    let tokens = vec![
+        &testing_env.env,
+        testing_env.yaro_token.id.clone(),
+        testing_env.yusd_token.id.clone(),
+    ];
+
+    let client = &testing_env.factory.client;
+    assert_eq!(
+        client.predict_pool_address(admin, &tokens, &None),
+        testing_env.pool.id
+    );
+
+    let predicted_pool = client.predict_pool_address(admin, &tokens, &Some(1));
+    assert_ne!(predicted_pool, testing_env.pool.id);
+    assert_eq!(create_tiered_pool(&testing_env, 60, 1), predicted_pool);
+}
+
+#[test]
+fn predict_stable_pool_address() {
+    let testing_env = TestingEnv::default();
+    let admin = testing_env.admin.as_ref();
+    let tokens = generate_tokens(&testing_env, 4);
+    let mut reversed_tokens = Vec::new(&testing_env.env);
+    for token in tokens.iter() {
+        reversed_tokens.push_front(token);
+    }
+
+    let predicted_pool =
+        testing_env
+            .factory
+            .client
+            .predict_pool_address(admin, &reversed_tokens, &None);
+
+    let deployed_pool = testing_env
+        .factory
+        .create_stable_pool(admin, 20, &tokens, 10, 10);
+    assert_eq!(deployed_pool, predicted_pool);
+}
+
+#[test]
+fn pools_page() {
+    let testing_env = TestingEnv::default();
+    let mut pools = vec![&testing_env.env, testing_env.pool.id.clone()];
+
+    for _ in 0..4 {
+        let (first_token, second_token) =
+            TestingEnv::generate_token_pair(&testing_env.env, testing_env.admin.as_ref());
+
+        pools.push_back(testing_env.factory.create_pool(
+            testing_env.admin.as_ref(),
+            10,
+            &first_token.id,
+            &second_token.id,
+            10,
+            10,
+        ));
+    }
+
+    let client = &testing_env.factory.client;
+    assert_eq!(client.pools_count(), 5);
+    assert_eq!(client.pools_page(&0, &2, &None), pools.slice(0..2));
+    assert_eq!(client.pools_page(&2, &10, &None), pools.slice(2..5));
+    assert_eq!(
+        client.pools_page(&5, &10, &None),
+        Vec::new(&testing_env.env)
+    );
+    assert_eq!(
+        client.pools_page(&u32::MAX, &u32::MAX, &None),
+        Vec::new(&testing_env.env)
+    );
+}
+
+#[test]
+fn pools_page_status() {
+    let testing_env = TestingEnv::default();
+    let mut pools = vec![&testing_env.env, testing_env.pool.id.clone()];
+
+    for _ in 0..2 {
+        let (first_token, second_token) =
+            TestingEnv::generate_token_pair(&testing_env.env, testing_env.admin.as_ref());
+
+        pools.push_back(testing_env.factory.create_pool(
+            testing_env.admin.as_ref(),
+            10,
+            &first_token.id,
+            &second_token.id,
+            10,
+            10,
+        ));
+    }
+
+    set_pool_status(&testing_env, &pools.get_unchecked(0), PoolStatus::Delisted);
+    set_pool_status(
+        &testing_env,
+        &pools.get_unchecked(2),
+        PoolStatus::Deprecated,
+    );
+
+    let client = &testing_env.factory.client;
+    assert_eq!(client.pools_page(&0, &10, &None), pools.slice(1..3));
+    assert_eq!(client.pools_page(&0, &1, &None), Vec::new(&testing_env.env));
+    assert_eq!(
+        client.pools_page(&0, &10, &Some(PoolStatus::Active)),
+        pools.slice(1..2)
+    );
+    assert_eq!(
+        client.pools_page(&0, &10, &Some(PoolStatus::Deprecated)),
+        pools.slice(2..3)
+    );
+    assert_eq!(
+        client.pools_page(&0, &10, &Some(PoolStatus::Delisted)),
+        pools.slice(0..1)
+    );
+}
+
+#[test]
+fn pools_by_token() {
+    let testing_env = TestingEnv::default();
+    let TestingEnv {
+        ref yaro_token,
+        ref yusd_token,
+        ..
+    } = testing_env;
+    let tokens = generate_tokens(&testing_env, 2);
+    let mut stable_tokens = tokens.clone();
+    stable_tokens.push_back(yaro_token.id.clone());
 
-    let _ = testing_env.factory.create_pool(
+    let stable_pool = testing_env.factory.create_stable_pool(
         testing_env.admin.as_ref(),
+        20,
+        &stable_tokens,
         10,
-        &first_token.id,
-        &second_token.id,
         10,
+    );
+
+    let client = &testing_env.factory.client;
+    assert_eq!(
+        client.pools_by_token(&yaro_token.id, &0, &10),
+        vec![
+            &testing_env.env,
+            testing_env.pool.id.clone(),
+            stable_pool.clone()
+        ]
+    );
+    assert_eq!(
+        client.pools_by_token(&yusd_token.id, &0, &10),
+        vec![&testing_env.env, testing_env.pool.id.clone()]
+    );
+    assert_eq!(
+        client.pools_by_token(&tokens.get_unchecked(0), &0, &10),
+        vec![&testing_env.env, stable_pool]
+    );
+    assert_eq!(
+        client.pools_by_token(&Address::generate(&testing_env.env), &0, &10),
+        Vec::new(&testing_env.env)
+    );
+}
+
+#[test]
+fn pools_by_token_page() {
+    let testing_env = TestingEnv::default();
+    let TestingEnv {
+        ref env,
+        ref yaro_token,
+        ..
+    } = testing_env;
+    let admin = testing_env.admin.as_ref();
+    let mut pools = vec![env, testing_env.pool.id.clone()];
+
+    for token in generate_tokens(&testing_env, 3).iter() {
+        pools.push_back(
+            testing_env
+                .factory
+                .create_pool(admin, 10, &yaro_token.id, &token, 10, 10),
+        );
+    }
+
+    let client = &testing_env.factory.client;
+    assert_eq!(
+        client.pools_by_token(&yaro_token.id, &0, &2),
+        pools.slice(0..2)
+    );
+    assert_eq!(
+        client.pools_by_token(&yaro_token.id, &2, &10),
+        pools.slice(2..4)
+    );
+    assert_eq!(
+        client.pools_by_token(&yaro_token.id, &4, &10),
+        Vec::new(env)
+    );
+    assert_eq!(
+        client.pools_by_token(&yaro_token.id, &u32::MAX, &u32::MAX),
+        Vec::new(env)
+    );
+
+    // A delisted pool keeps its position, so the page gets shorter
+    set_pool_status(&testing_env, &pools.get_unchecked(1), PoolStatus::Delisted);
+    assert_eq!(
+        client.pools_by_token(&yaro_token.id, &0, &2),
+        pools.slice(0..1)
+    );
+}
+
+#[test]
+fn pool_info() {
+    let testing_env = TestingEnv::default();
+    testing_env
+        .env
+        .ledger()
+        .with_mut(|ledger| ledger.sequence_number = 100);
+    let tokens = generate_tokens(&testing_env, 3);
+
+    let deployed_pool =
+        testing_env
+            .factory
+            .create_stable_pool(testing_env.admin.as_ref(), 20, &tokens, 10, 10);
+
+    let pool_info = testing_env.factory.client.pool_info(&deployed_pool);
+    let mut sorted_tokens: std::vec::Vec<Address> = tokens.iter().collect();
+    sorted_tokens.sort();
+
+    assert_eq!(pool_info.pool, deployed_pool);
+    assert_eq!(
+        pool_info.tokens.iter().collect::<std::vec::Vec<_>>(),
+        sorted_tokens
+    );
+    assert_eq!(pool_info.pool_type, PoolType::StablePool);
+    assert_eq!(pool_info.creator, testing_env.admin.as_address());
+    assert_eq!(pool_info.created_ledger, 100);
+    assert_eq!(pool_info.status, PoolStatus::Active);
+
+    let pool_info = testing_env.factory.client.pool_info(&testing_env.pool.id);
+    assert_eq!(pool_info.pool_type, PoolType::TwoPool);
+}
+
+#[test]
+#[should_panic = "DexContract(NotFound)"]
+fn pool_info_unknown_pool() {
+    let testing_env = TestingEnv::default();
+
+    unwrap_call_result(
+        &testing_env.env,
+        desoroban_result(
+            testing_env
+                .factory
+                .client
+                .try_pool_info(&Address::generate(&testing_env.env)),
+        ),
+    );
+}
+
+#[test]
+fn migrate_legacy_factory() {
+    let testing_env = TestingEnv::default();
+    let TestingEnv {
+        ref env, ref admin, ..
+    } = testing_env;
+    let factory = PoolFactory::create(env, admin.as_ref());
+    let two_pool_tokens = generate_tokens(&testing_env, 2);
+    let three_pool_tokens = generate_tokens(&testing_env, 3);
+    let two_pool = Address::generate(env);
+    let three_pool = Address::generate(env);
+    let stable_pool_wasm_hash = factory.client.get_stable_pool_wasm_hash();
+    let metapool_wasm_hash = factory.client.get_metapool_wasm_hash();
+    let two_pool_wasm_hash = factory.client.get_two_pool_wasm_hash();
+    let three_pool_wasm_hash = factory.client.get_three_pool_wasm_hash();
+
+    // The layout stored by the previous contract version
+    env.as_contract(&factory.id, || {
+        let mut pools = Map::new(env);
+        pools.set(two_pool_tokens.clone(), two_pool.clone());
+        pools.set(three_pool_tokens.clone(), three_pool.clone());
+        let legacy_factory_info = LegacyFactoryInfo {
+            two_pool_wasm_hash,
+            three_pool_wasm_hash: three_pool_wasm_hash.clone(),
+            pools,
+        };
+        env.storage()
+            .instance()
+            .set(&Symbol::new(env, "FactoryInfo"), &legacy_factory_info);
+    });
+
+    factory.migrate(&stable_pool_wasm_hash, &metapool_wasm_hash);
+
+    let client = &factory.client;
+    assert_eq!(client.pools_count(), 2);
+    assert_eq!(client.pool(&two_pool_tokens, &None), two_pool);
+    assert_eq!(client.pool(&three_pool_tokens, &None), three_pool);
+    assert_eq!(
+        client.pools_by_token(&three_pool_tokens.get_unchecked(0), &0, &10),
+        vec![env, three_pool.clone()]
+    );
+    assert_eq!(client.get_stable_pool_wasm_hash(), stable_pool_wasm_hash);
+    assert_eq!(client.get_metapool_wasm_hash(), metapool_wasm_hash);
+
+    let pool_info = client.pool_info(&three_pool);
+    assert_eq!(pool_info.pool_type, PoolType::ThreePool);
+    assert_eq!(pool_info.tier, 0);
+    assert_eq!(pool_info.wasm_hash, three_pool_wasm_hash);
+    assert_eq!(pool_info.status, PoolStatus::Active);
+    assert_eq!(client.pool_info(&two_pool).pool_type, PoolType::TwoPool);
+}
+
+#[test]
+#[should_panic = "Context(InvalidAction)"]
+fn migrate_no_auth() {
+    let testing_env = TestingEnv::default();
+    let wasm_hash = BytesN::random(&testing_env.env);
+
+    testing_env
+        .clear_mock_auth()
+        .factory
+        .migrate(&wasm_hash, &wasm_hash);
+}
+
+#[test]
+fn set_permissionless_config() {
+    let testing_env = TestingEnv::default();
+    let config = permissionless_config(&testing_env, PoolAdmin::Factory);
+
+    testing_env
+        .factory
+        .client
+        .set_permissionless_config(&config);
+
+    assert_eq!(
+        testing_env.factory.client.get_permissionless_config(),
+        config
+    );
+
+    let event = get_latest_event::<PermissionlessConfigUpdated>(&testing_env.env)
+        .expect("Expected PermissionlessConfigUpdated");
+    assert_eq!(event.config, config);
+}
+
+#[test]
+#[should_panic = "Context(InvalidAction)"]
+fn set_permissionless_config_no_auth() {
+    let testing_env = TestingEnv::default();
+
+    unwrap_call_result(
+        &testing_env.env,
+        desoroban_result(
+            testing_env
+                .clear_mock_auth()
+                .factory
+                .client
+                .try_set_permissionless_config(&permissionless_config(
+                    &testing_env,
+                    PoolAdmin::Factory,
+                )),
+        ),
+    );
+}
+
+#[test]
+#[should_panic = "DexContract(InvalidArg)"]
+fn set_permissionless_config_invalid_range() {
+    let testing_env = TestingEnv::default();
+    let mut config = permissionless_config(&testing_env, PoolAdmin::Factory);
+    config.a = ParamRange { min: 50, max: 10 };
+
+    unwrap_call_result(
+        &testing_env.env,
+        desoroban_result(
+            testing_env
+                .factory
+                .client
+                .try_set_permissionless_config(&config),
+        ),
+    );
+}
+
+#[test]
+fn create_pool_permissionless_factory_admin() {
+    let testing_env = TestingEnv::default();
+
+    let deployed_pool = create_pool_permissionless(
+        &testing_env,
+        &permissionless_config(&testing_env, PoolAdmin::Factory),
+        20,
         10,
     );
+
+    let pool_info = testing_env.factory.client.pool_info(&deployed_pool);
+    assert_eq!(pool_info.creator, testing_env.bob.as_address());
+    assert_eq!(
+        pool::Client::new(&testing_env.env, &deployed_pool).get_admin(),
+        testing_env.factory.id
+    );
+}
+
+#[test]
+fn create_pool_permissionless_protocol_admin() {
+    let testing_env = TestingEnv::default();
+    let protocol = Address::generate(&testing_env.env);
+
+    let deployed_pool = create_pool_permissionless(
+        &testing_env,
+        &permissionless_config(&testing_env, PoolAdmin::Protocol(protocol.clone())),
+        50,
+        1,
+    );
+
+    assert_eq!(
+        pool::Client::new(&testing_env.env, &deployed_pool).get_admin(),
+        protocol
+    );
+}
+
+#[test]
+#[should_panic = "DexContract(ParamOutOfBounds)"]
+fn create_pool_permissionless_out_of_bounds() {
+    let testing_env = TestingEnv::default();
+
+    create_pool_permissionless(
+        &testing_env,
+        &permissionless_config(&testing_env, PoolAdmin::Factory),
+        20,
+        31,
+    );
+}
+
+#[test]
+#[should_panic = "DexContract(Forbidden)"]
+fn create_pool_permissionless_disabled() {
+    let testing_env = TestingEnv::default();
+    let mut config = permissionless_config(&testing_env, PoolAdmin::Factory);
+    config.enabled = false;
+
+    create_pool_permissionless(&testing_env, &config, 20, 10);
+}
+
+#[test]
+#[should_panic = "DexContract(Forbidden)"]
+fn create_pool_permissionless_not_configured() {
+    let testing_env = TestingEnv::default();
+    let (first_token, second_token) =
+        TestingEnv::generate_token_pair(&testing_env.env, testing_env.admin.as_ref());
+
+    unwrap_call_result(
+        &testing_env.env,
+        desoroban_result(testing_env.factory.client.try_create_pool_permissionless(
+            testing_env.bob.as_ref(),
+            &20,
+            &vec![&testing_env.env, first_token.id, second_token.id],
+            &10,
+            &0,
+            &1,
+        )),
+    );
+}
+
+#[test]
+#[should_panic = "DexContract(InvalidArg)"]
+fn set_permissionless_config_default_tier() {
+    let testing_env = TestingEnv::default();
+    let mut config = permissionless_config(&testing_env, PoolAdmin::Factory);
+    config.tiers.push_back(0);
+
+    unwrap_call_result(
+        &testing_env.env,
+        desoroban_result(
+            testing_env
+                .factory
+                .client
+                .try_set_permissionless_config(&config),
+        ),
+    );
+}
+
+#[test]
+#[should_panic = "DexContract(Forbidden)"]
+fn create_pool_permissionless_closed_tier() {
+    let testing_env = TestingEnv::default();
+    let client = &testing_env.factory.client;
+    client.set_permissionless_config(&permissionless_config(&testing_env, PoolAdmin::Factory));
+
+    unwrap_call_result(
+        &testing_env.env,
+        desoroban_result(client.try_create_pool_permissionless(
+            testing_env.bob.as_ref(),
+            &20,
+            &generate_tokens(&testing_env, 2),
+            &10,
+            &0,
+            &3,
+        )),
+    );
+}
+
+#[test]
+#[should_panic = "DexContract(Forbidden)"]
+fn create_pool_permissionless_default_tier() {
+    let testing_env = TestingEnv::default();
+    let client = &testing_env.factory.client;
+    client.set_permissionless_config(&permissionless_config(&testing_env, PoolAdmin::Factory));
+
+    unwrap_call_result(
+        &testing_env.env,
+        desoroban_result(client.try_create_pool_permissionless(
+            testing_env.bob.as_ref(),
+            &20,
+            &generate_tokens(&testing_env, 2),
+            &10,
+            &0,
+            &0,
+        )),
+    );
+}
+
+fn create_factory_admin_pool(testing_env: &TestingEnv) -> (pool::Client<'static>, Token, Token) {
+    let client = &testing_env.factory.client;
+    client.set_permissionless_config(&permissionless_config(testing_env, PoolAdmin::Factory));
+    let (first_token, second_token) = generate_funded_token_pair(testing_env);
+
+    let pool = client.create_pool_permissionless(
+        testing_env.bob.as_ref(),
+        &20,
+        &vec![
+            &testing_env.env,
+            first_token.id.clone(),
+            second_token.id.clone(),
+        ],
+        &10,
+        &0,
+        &1,
+    );
+
+    (
+        pool::Client::new(&testing_env.env, &pool),
+        first_token,
+        second_token,
+    )
+}
+
+#[test]
+fn claim_pool_admin_fee() {
+    let testing_env = TestingEnv::default();
+    let admin = testing_env.admin.as_address();
+    let alice = testing_env.alice.as_address();
+    let factory = &testing_env.factory;
+    let (pool, first_token, second_token) = create_factory_admin_pool(&testing_env);
+
+    factory
+        .client
+        .set_pool_fee_share(&pool.address, &PoolFeeShare::Admin, &5_000);
+    assert_eq!(pool.get_pool().admin_fee_share_bp, 5_000);
+
+    let amount = float_to_uint(500.0, 7);
+    pool.deposit(&alice, &(amount, amount), &0);
+    for direction in [pool::Direction::A2B, pool::Direction::B2A] {
+        pool.swap(&alice, &alice, &float_to_uint(100.0, 7), &0, &direction);
+    }
+
+    let admin_balances = (
+        first_token.balance_of(&admin),
+        second_token.balance_of(&admin),
+    );
+    factory.client.claim_pool_admin_fee(&pool.address);
+
+    assert!(first_token.balance_of(&admin) > admin_balances.0);
+    assert!(second_token.balance_of(&admin) > admin_balances.1);
+    assert_eq!(first_token.balance_of(&factory.id), 0);
+    assert_eq!(second_token.balance_of(&factory.id), 0);
+    assert_eq!(pool.get_pool().admin_fee_amount.data, (0, 0));
+}
+
+#[test]
+#[should_panic = "Context(InvalidAction)"]
+fn claim_pool_admin_fee_no_auth() {
+    let testing_env = TestingEnv::default();
+    let (pool, _, _) = create_factory_admin_pool(&testing_env);
+
+    unwrap_call_result(
+        &testing_env.env,
+        desoroban_result(
+            testing_env
+                .clear_mock_auth()
+                .factory
+                .client
+                .try_claim_pool_admin_fee(&pool.address),
+        ),
+    );
+}
+
+#[test]
+fn set_pool_admin() {
+    let testing_env = TestingEnv::default();
+    let client = &testing_env.factory.client;
+    let (pool, _, _) = create_factory_admin_pool(&testing_env);
+    let new_admin = Address::generate(&testing_env.env);
+
+    client.set_pool_fee_share(&pool.address, &PoolFeeShare::Swap, &2_000);
+    client.set_pool_admin(&pool.address, &new_admin);
+
+    assert_eq!(pool.get_pool().swap_fee_share, 2_000);
+    assert_eq!(pool.get_admin(), new_admin);
+}
+
+#[test]
+#[should_panic = "DexContract(Forbidden)"]
+fn set_pool_fee_share_not_pool_admin() {
+    let testing_env = TestingEnv::default();
+
+    unwrap_call_result(
+        &testing_env.env,
+        desoroban_result(testing_env.factory.client.try_set_pool_fee_share(
+            &testing_env.pool.id,
+            &PoolFeeShare::Swap,
+            &2_000,
+        )),
+    );
+}
+
+/// Any uploaded wasm stands in for a new version of the pools
+#[test]
+fn set_token_allowlist() {
+    let testing_env = TestingEnv::default();
+    let client = &testing_env.factory.client;
+    let token = testing_env.yaro_token.id.clone();
+    assert!(!client.is_token_allowlist_enabled());
+    assert!(!client.is_token_allowed(&token));
+
+    client.set_token_allowlist_enabled(&true);
+    let event = get_latest_event::<TokenAllowlistEnabled>(&testing_env.env)
+        .expect("Expected TokenAllowlistEnabled");
+    assert!(event.enabled);
+    assert!(client.is_token_allowlist_enabled());
+
+    client.set_token_allowed(&token, &true);
+    let event = get_latest_event::<TokenAllowlistUpdated>(&testing_env.env)
+        .expect("Expected TokenAllowlistUpdated");
+    assert_eq!(event.token, token);
+    assert!(event.allowed);
+    assert!(client.is_token_allowed(&token));
+
+    client.set_token_allowed(&token, &false);
+    assert!(!client.is_token_allowed(&token));
+}
+
+#[test]
+#[should_panic = "Context(InvalidAction)"]
+fn set_token_allowed_no_auth() {
+    let testing_env = TestingEnv::default();
+
+    unwrap_call_result(
+        &testing_env.env,
+        desoroban_result(
+            testing_env
+                .clear_mock_auth()
+                .factory
+                .client
+                .try_set_token_allowed(&testing_env.yaro_token.id, &true),
+        ),
+    );
+}
+
+#[test]
+fn create_pool_allowed_tokens() {
+    let testing_env = TestingEnv::default();
+    let (first_token, second_token) =
+        TestingEnv::generate_token_pair(&testing_env.env, testing_env.admin.as_ref());
+    let client = &testing_env.factory.client;
+    client.set_token_allowlist_enabled(&true);
+    client.set_token_allowed(&first_token.id, &true);
+    client.set_token_allowed(&second_token.id, &true);
+
+    let pool = testing_env.factory.create_pool(
+        testing_env.admin.as_ref(),
+        10,
+        &first_token.id,
+        &second_token.id,
+        10,
+        10,
+    );
+
+    assert_eq!(
+        testing_env.factory.pool(&first_token.id, &second_token.id),
+        pool
+    );
+}
+
+#[test]
+#[should_panic = "DexContract(TokenNotAllowed)"]
+fn create_pool_token_not_allowed() {
+    let testing_env = TestingEnv::default();
+    let (first_token, second_token) =
+        TestingEnv::generate_token_pair(&testing_env.env, testing_env.admin.as_ref());
+    let client = &testing_env.factory.client;
+    client.set_token_allowlist_enabled(&true);
+    client.set_token_allowed(&first_token.id, &true);
+
+    testing_env.factory.create_pool(
+        testing_env.admin.as_ref(),
+        10,
+        &first_token.id,
+        &second_token.id,
+        10,
+        10,
+    );
+}
+
+#[test]
+#[should_panic = "DexContract(InvalidTokenDecimals)"]
+fn create_pool_invalid_token_decimals() {
+    let testing_env = TestingEnv::default();
+    let token = MockToken::create(&testing_env.env, 39);
+
+    testing_env.factory.create_pool(
+        testing_env.admin.as_ref(),
+        10,
+        &testing_env.yaro_token.id,
+        &token.id,
+        10,
+        10,
+    );
+}
+
+#[test]
+#[should_panic = "DexContract(InvalidTokenDecimals)"]
+fn create_stable_pool_invalid_token_decimals() {
+    let testing_env = TestingEnv::default();
+    let mut tokens = generate_tokens(&testing_env, 3);
+    tokens.push_back(MockToken::create(&testing_env.env, 19).id);
+
+    testing_env
+        .factory
+        .create_stable_pool(testing_env.admin.as_ref(), 10, &tokens, 10, 10);
+}
+
+fn creation_fee(testing_env: &TestingEnv, treasury: &Address) -> CreationFee {
+    CreationFee::Charged(CreationFeeInfo {
+        token: testing_env.yusd_token.id.clone(),
+        amount: float_to_uint(10.0, 7),
+        treasury: treasury.clone(),
+    })
+}
+
+#[test]
+fn set_creation_fee() {
+    let testing_env = TestingEnv::default();
+    let client = &testing_env.factory.client;
+    let fee = creation_fee(&testing_env, &Address::generate(&testing_env.env));
+    assert_eq!(client.get_creation_fee(), CreationFee::Free);
+
+    client.set_creation_fee(&fee);
+
+    let event = get_latest_event::<CreationFeeUpdated>(&testing_env.env)
+        .expect("Expected CreationFeeUpdated");
+    assert_eq!(event.fee, fee);
+    assert_eq!(client.get_creation_fee(), fee);
+
+    client.set_creation_fee(&CreationFee::Free);
+    assert_eq!(client.get_creation_fee(), CreationFee::Free);
+}
+
+#[test]
+#[should_panic = "Context(InvalidAction)"]
+fn set_creation_fee_no_auth() {
+    let testing_env = TestingEnv::default();
+    let fee = creation_fee(&testing_env, &Address::generate(&testing_env.env));
+
+    unwrap_call_result(
+        &testing_env.env,
+        desoroban_result(
+            testing_env
+                .clear_mock_auth()
+                .factory
+                .client
+                .try_set_creation_fee(&fee),
+        ),
+    );
+}
+
+#[test]
+#[should_panic = "DexContract(InvalidArg)"]
+fn set_creation_fee_zero_amount() {
+    let testing_env = TestingEnv::default();
+    let fee = CreationFee::Charged(CreationFeeInfo {
+        token: testing_env.yusd_token.id.clone(),
+        amount: 0,
+        treasury: Address::generate(&testing_env.env),
+    });
+
+    unwrap_call_result(
+        &testing_env.env,
+        desoroban_result(testing_env.factory.client.try_set_creation_fee(&fee)),
+    );
+}
+
+#[test]
+fn create_pool_creation_fee() {
+    let testing_env = TestingEnv::default();
+    let (first_token, second_token) =
+        TestingEnv::generate_token_pair(&testing_env.env, testing_env.admin.as_ref());
+    let treasury = Address::generate(&testing_env.env);
+    let client = &testing_env.factory.client;
+    client.set_creation_fee(&creation_fee(&testing_env, &treasury));
+    let alice_balance = testing_env
+        .yusd_token
+        .balance_of(&testing_env.alice.as_address());
+
+    client.create_pool(
+        testing_env.alice.as_ref(),
+        testing_env.admin.as_ref(),
+        &10,
+        &vec![&testing_env.env, first_token.id, second_token.id],
+        &10,
+        &10,
+        &0,
+    );
+
+    let fee_amount = float_to_uint(10.0, 7);
+    assert_eq!(testing_env.yusd_token.balance_of(&treasury), fee_amount);
+    assert_eq!(
+        testing_env
+            .yusd_token
+            .balance_of(&testing_env.alice.as_address()),
+        alice_balance - fee_amount
+    );
+}
+
+#[test]
+fn create_pool_creation_fee_factory_deployer() {
+    let testing_env = TestingEnv::default();
+    let (first_token, second_token) =
+        TestingEnv::generate_token_pair(&testing_env.env, testing_env.admin.as_ref());
+    let treasury = Address::generate(&testing_env.env);
+    let client = &testing_env.factory.client;
+    client.set_creation_fee(&creation_fee(&testing_env, &treasury));
+
+    client.create_pool(
+        &testing_env.factory.id,
+        testing_env.admin.as_ref(),
+        &10,
+        &vec![&testing_env.env, first_token.id, second_token.id],
+        &10,
+        &10,
+        &0,
+    );
+
+    assert_eq!(testing_env.yusd_token.balance_of(&treasury), 0);
+}
+
+fn set_pool_status(testing_env: &TestingEnv, pool: &Address, status: PoolStatus) {
+    unwrap_call_result(
+        &testing_env.env,
+        desoroban_result(
+            testing_env
+                .factory
+                .client
+                .try_set_pool_status(pool, &status),
+        ),
+    );
+}
+
+#[test]
+fn deprecate_pool() {
+    let testing_env = TestingEnv::default();
+    let client = &testing_env.factory.client;
+    let pool = testing_env.pool.id.clone();
+
+    set_pool_status(&testing_env, &pool, PoolStatus::Deprecated);
+
+    let event = get_latest_event::<PoolStatusUpdated>(&testing_env.env)
+        .expect("Expected PoolStatusUpdated");
+    assert_eq!(event.pool, pool);
+    assert_eq!(event.status, PoolStatus::Deprecated);
+    assert_eq!(client.pool_info(&pool).status, PoolStatus::Deprecated);
+    assert_eq!(
+        testing_env
+            .factory
+            .pool(&testing_env.yaro_token.id, &testing_env.yusd_token.id),
+        pool
+    );
+    assert!(client.pools().contains_key(pool));
+}
+
+#[test]
+fn delist_pool() {
+    let testing_env = TestingEnv::default();
+    let client = &testing_env.factory.client;
+    let pool = testing_env.pool.id.clone();
+    let tokens = vec![
+        &testing_env.env,
+        testing_env.yaro_token.id.clone(),
+        testing_env.yusd_token.id.clone(),
+    ];
+
+    set_pool_status(&testing_env, &pool, PoolStatus::Delisted);
+
+    assert_eq!(client.pool_info(&pool).status, PoolStatus::Delisted);
+    assert!(!client.pools().contains_key(pool.clone()));
+    assert!(client.pool_variants(&tokens).is_empty());
+    assert!(client
+        .pools_by_token(&testing_env.yaro_token.id, &0, &10)
+        .is_empty());
+    assert!(client.pools_page(&0, &10, &None).is_empty());
+    assert_eq!(
+        client.pools_page(&0, &10, &Some(PoolStatus::Delisted)),
+        vec![&testing_env.env, pool.clone()]
+    );
+
+    let admin = testing_env.admin.as_ref();
+    let predicted_pool = client.predict_pool_address(admin, &tokens, &None);
+    let new_pool = testing_env.factory.create_pool(
+        admin,
+        10,
+        &testing_env.yaro_token.id,
+        &testing_env.yusd_token.id,
+        10,
+        10,
+    );
+
+    assert_ne!(new_pool, pool);
+    assert_eq!(new_pool, predicted_pool);
+    assert_eq!(client.pool(&tokens, &None), new_pool);
+    assert_eq!(
+        client.pool_variants(&tokens),
+        vec![&testing_env.env, new_pool]
+    );
+}
+
+#[test]
+#[should_panic = "DexContract(NotFound)"]
+fn delisted_pool_lookup() {
+    let testing_env = TestingEnv::default();
+
+    set_pool_status(&testing_env, &testing_env.pool.id, PoolStatus::Delisted);
+
+    testing_env
+        .factory
+        .pool(&testing_env.yaro_token.id, &testing_env.yusd_token.id);
+}
+
+#[test]
+fn relist_pool() {
+    let testing_env = TestingEnv::default();
+    let pool = testing_env.pool.id.clone();
+
+    set_pool_status(&testing_env, &pool, PoolStatus::Delisted);
+    set_pool_status(&testing_env, &pool, PoolStatus::Active);
+
+    assert_eq!(
+        testing_env
+            .factory
+            .pool(&testing_env.yaro_token.id, &testing_env.yusd_token.id),
+        pool
+    );
+    assert!(testing_env.factory.client.pools().contains_key(pool));
+}
+
+#[test]
+#[should_panic = "DexContract(PoolExist)"]
+fn relist_replaced_pool() {
+    let testing_env = TestingEnv::default();
+    let pool = testing_env.pool.id.clone();
+
+    set_pool_status(&testing_env, &pool, PoolStatus::Delisted);
+    testing_env.factory.create_pool(
+        testing_env.admin.as_ref(),
+        10,
+        &testing_env.yaro_token.id,
+        &testing_env.yusd_token.id,
+        10,
+        10,
+    );
+
+    set_pool_status(&testing_env, &pool, PoolStatus::Active);
+}
+
+#[test]
+#[should_panic = "Context(InvalidAction)"]
+fn set_pool_status_no_auth() {
+    let testing_env = TestingEnv::default();
+
+    set_pool_status(
+        testing_env.clear_mock_auth(),
+        &testing_env.pool.id,
+        PoolStatus::Deprecated,
+    );
+}
+
+fn upload_new_wasm(testing_env: &TestingEnv) -> BytesN<32> {
+    testing_env
+        .env
+        .deployer()
+        .upload_contract_wasm(three_pool::WASM)
+}
+
+#[test]
+fn upgrade_pools() {
+    let testing_env = TestingEnv::default();
+    let admin = testing_env.admin.as_ref();
+    let (first_token, second_token) = TestingEnv::generate_token_pair(&testing_env.env, admin);
+    let two_pool =
+        testing_env
+            .factory
+            .create_pool(admin, 10, &first_token.id, &second_token.id, 10, 10);
+    let stable_pool = testing_env.factory.create_stable_pool(
+        admin,
+        20,
+        &generate_tokens(&testing_env, 3),
+        10,
+        10,
+    );
+    let client = &testing_env.factory.client;
+    assert_eq!(
+        client.pool_wasm_hash(&two_pool),
+        client.get_two_pool_wasm_hash()
+    );
+
+    let new_wasm_hash = upload_new_wasm(&testing_env);
+    client.upgrade_pools(&PoolType::TwoPool, &new_wasm_hash, &None, &0);
+
+    assert_eq!(client.pool_wasm_hash(&testing_env.pool.id), new_wasm_hash);
+    assert_eq!(client.pool_wasm_hash(&two_pool), new_wasm_hash);
+    assert_eq!(
+        client.pool_wasm_hash(&stable_pool),
+        client.get_stable_pool_wasm_hash()
+    );
+
+    let event =
+        get_latest_event::<PoolsUpgraded>(&testing_env.env).expect("Expected PoolsUpgraded");
+    assert_eq!(event.pool_type, PoolType::TwoPool);
+    assert_eq!(event.new_wasm_hash, new_wasm_hash);
+    assert_eq!(
+        event.pools,
+        vec![&testing_env.env, testing_env.pool.id.clone(), two_pool]
+    );
+}
+
+#[test]
+fn upgrade_listed_pools() {
+    let testing_env = TestingEnv::default();
+    let admin = testing_env.admin.as_ref();
+    let (first_token, second_token) = TestingEnv::generate_token_pair(&testing_env.env, admin);
+    let two_pool =
+        testing_env
+            .factory
+            .create_pool(admin, 10, &first_token.id, &second_token.id, 10, 10);
+    let client = &testing_env.factory.client;
+
+    let new_wasm_hash = upload_new_wasm(&testing_env);
+    client.upgrade_pools(
+        &PoolType::TwoPool,
+        &new_wasm_hash,
+        &Some(vec![&testing_env.env, two_pool.clone()]),
+        &0,
+    );
+
+    assert_eq!(client.pool_wasm_hash(&two_pool), new_wasm_hash);
+    assert_eq!(
+        client.pool_wasm_hash(&testing_env.pool.id),
+        client.get_two_pool_wasm_hash()
+    );
+}
+
+#[test]
+fn upgrade_pools_page_skips_delisted() {
+    let testing_env = TestingEnv::default();
+    let admin = testing_env.admin.as_ref();
+    let (first_token, second_token) = TestingEnv::generate_token_pair(&testing_env.env, admin);
+    let two_pool =
+        testing_env
+            .factory
+            .create_pool(admin, 10, &first_token.id, &second_token.id, 10, 10);
+    set_pool_status(&testing_env, &testing_env.pool.id, PoolStatus::Delisted);
+    let client = &testing_env.factory.client;
+
+    let new_wasm_hash = upload_new_wasm(&testing_env);
+    client.upgrade_pools(&PoolType::TwoPool, &new_wasm_hash, &None, &0);
+
+    assert_eq!(client.pool_wasm_hash(&two_pool), new_wasm_hash);
+    assert_eq!(
+        client.pool_wasm_hash(&testing_env.pool.id),
+        client.get_two_pool_wasm_hash()
+    );
+}
+
+#[test]
+fn upgrade_pools_page_start() {
+    let testing_env = TestingEnv::default();
+    let admin = testing_env.admin.as_ref();
+    let (first_token, second_token) = TestingEnv::generate_token_pair(&testing_env.env, admin);
+    let two_pool =
+        testing_env
+            .factory
+            .create_pool(admin, 10, &first_token.id, &second_token.id, 10, 10);
+    let client = &testing_env.factory.client;
+
+    let new_wasm_hash = upload_new_wasm(&testing_env);
+    client.upgrade_pools(&PoolType::TwoPool, &new_wasm_hash, &None, &1);
+    client.upgrade_pools(&PoolType::TwoPool, &new_wasm_hash, &None, &5);
+
+    assert_eq!(client.pool_wasm_hash(&two_pool), new_wasm_hash);
+    assert_eq!(
+        client.pool_wasm_hash(&testing_env.pool.id),
+        client.get_two_pool_wasm_hash()
+    );
+}
+
+#[test]
+#[should_panic = "DexContract(InvalidArg)"]
+fn upgrade_delisted_pool() {
+    let testing_env = TestingEnv::default();
+    set_pool_status(&testing_env, &testing_env.pool.id, PoolStatus::Delisted);
+
+    unwrap_call_result(
+        &testing_env.env,
+        desoroban_result(testing_env.factory.client.try_upgrade_pools(
+            &PoolType::TwoPool,
+            &upload_new_wasm(&testing_env),
+            &Some(vec![&testing_env.env, testing_env.pool.id.clone()]),
+            &0,
+        )),
+    );
+}
+
+#[test]
+#[should_panic = "DexContract(InvalidArg)"]
+fn upgrade_pools_type_mismatch() {
+    let testing_env = TestingEnv::default();
+
+    unwrap_call_result(
+        &testing_env.env,
+        desoroban_result(testing_env.factory.client.try_upgrade_pools(
+            &PoolType::StablePool,
+            &upload_new_wasm(&testing_env),
+            &Some(vec![&testing_env.env, testing_env.pool.id.clone()]),
+            &0,
+        )),
+    );
+}
+
+#[test]
+#[should_panic = "DexContract(NotFound)"]
+fn upgrade_unknown_pool() {
+    let testing_env = TestingEnv::default();
+
+    unwrap_call_result(
+        &testing_env.env,
+        desoroban_result(testing_env.factory.client.try_upgrade_pools(
+            &PoolType::TwoPool,
+            &upload_new_wasm(&testing_env),
+            &Some(vec![&testing_env.env, Address::generate(&testing_env.env)]),
+            &0,
+        )),
+    );
+}
+
+#[test]
+#[should_panic = "DexContract(InvalidArg)"]
+fn upgrade_pools_over_page_limit() {
+    let testing_env = TestingEnv::default();
+    let mut pools = Vec::new(&testing_env.env);
+    for _ in 0..31 {
+        pools.push_back(Address::generate(&testing_env.env));
+    }
+
+    unwrap_call_result(
+        &testing_env.env,
+        desoroban_result(testing_env.factory.client.try_upgrade_pools(
+            &PoolType::TwoPool,
+            &upload_new_wasm(&testing_env),
+            &Some(pools),
+            &0,
+        )),
+    );
+}
+
+#[test]
+#[should_panic = "Context(InvalidAction)"]
+fn upgrade_pools_no_auth() {
+    let testing_env = TestingEnv::default();
+    let new_wasm_hash = upload_new_wasm(&testing_env);
+    let pools = Some(vec![&testing_env.env, testing_env.pool.id.clone()]);
+
+    unwrap_call_result(
+        &testing_env.env,
+        desoroban_result(
+            testing_env
+                .clear_mock_auth()
+                .factory
+                .client
+                .try_upgrade_pools(&PoolType::TwoPool, &new_wasm_hash, &pools, &0),
+        ),
+    );
+}
+
+#[test]
+fn add_new_stable_pool() {
+    let testing_env = TestingEnv::default();
+    let tokens = generate_tokens(&testing_env, 4);
+
+    let deployed_pool =
+        testing_env
+            .factory
+            .create_stable_pool(testing_env.admin.as_ref(), 20, &tokens, 10, 10);
+
+    assert_eq!(
+        testing_env.factory.client.pool(&tokens, &None),
+        deployed_pool
+    );
+
+    let event = get_latest_event::<StablePoolCreated>(&testing_env.env)
+        .expect("Expected StablePoolCreated");
+    let mut sorted_tokens: std::vec::Vec<Address> = tokens.iter().collect();
+    sorted_tokens.sort();
+
+    assert_eq!(
+        event.tokens.iter().collect::<std::vec::Vec<_>>(),
+        sorted_tokens
+    );
+    assert_eq!(event.pool, deployed_pool);
+}
+
+#[test]
+#[should_panic = "DexContract(InvalidNumberOfTokens)"]
+fn stable_pool_too_few_tokens() {
+    let testing_env = TestingEnv::default();
+    let tokens = generate_tokens(&testing_env, 1);
+
+    testing_env
+        .factory
+        .create_stable_pool(testing_env.admin.as_ref(), 20, &tokens, 10, 10);
+}
+
+#[test]
+#[should_panic = "DexContract(InvalidNumberOfTokens)"]
+fn stable_pool_too_many_tokens() {
+    let testing_env = TestingEnv::default();
+    let tokens = generate_tokens(&testing_env, 9);
+
+    testing_env
+        .factory
+        .create_stable_pool(testing_env.admin.as_ref(), 20, &tokens, 10, 10);
+}
+
+#[test]
+#[should_panic = "DexContract(IdenticalAddresses)"]
+fn stable_pool_identical_addresses() {
+    let testing_env = TestingEnv::default();
+    let mut tokens = generate_tokens(&testing_env, 3);
+    tokens.push_back(tokens.get_unchecked(1));
+
+    testing_env
+        .factory
+        .create_stable_pool(testing_env.admin.as_ref(), 20, &tokens, 10, 10);
+}
+
+#[test]
+fn update_stable_pool_wasm_hash() {
+    let testing_env = TestingEnv::default();
+
+    let new_wasm_hash = BytesN::<32>::random(&testing_env.env);
+
+    testing_env
+        .factory
+        .update_stable_pool_wasm_hash(&new_wasm_hash);
+
+    assert_eq!(
+        testing_env.factory.client.get_stable_pool_wasm_hash(),
+        new_wasm_hash
+    );
+
+    let event = get_latest_event::<StablePoolWasmHashUpdated>(&testing_env.env)
+        .expect("Expected StablePoolWasmHashUpdated");
+    assert_eq!(event.new_wasm_hash, new_wasm_hash);
 }
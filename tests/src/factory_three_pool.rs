@@ -1,6 +1,12 @@
-use soroban_sdk::{testutils::Address as _, testutils::BytesN as _, Address, BytesN};
+use soroban_sdk::{testutils::Address as _, testutils::BytesN as _, vec, Address, BytesN};
 
-use crate::three_pool_utils::TestingEnv;
+use crate::{
+    contracts::{
+        factory::{ThreePoolCreated, ThreePoolWasmHashUpdated},
+        three_pool,
+    },
+    three_pool_utils::{float_to_uint, get_latest_event, TestingEnv},
+};
 
 #[test]
 #[should_panic = "Context(InvalidAction)"]
@@ -48,7 +54,14 @@ fn update_two_pool_wasm_hash() {
 
     testing_env.factory.update_wasm_hash(&new_wasm_hash);
 
-    assert_eq!(testing_env.factory.client.get_three_pool_wasm_hash(), new_wasm_hash);
+    assert_eq!(
+        testing_env.factory.client.get_three_pool_wasm_hash(),
+        new_wasm_hash
+    );
+
+    let event = get_latest_event::<ThreePoolWasmHashUpdated>(&testing_env.env)
+        .expect("Expected ThreePoolWasmHashUpdated");
+    assert_eq!(event.new_wasm_hash, new_wasm_hash);
 }
 
 #[test]
@@ -178,9 +191,19 @@ fn add_new_pair() {
         10,
     );
 
-    let pool = testing_env.factory.pool(&token_a.id, &token_b.id, &token_c.id);
+    let pool = testing_env
+        .factory
+        .pool(&token_a.id, &token_b.id, &token_c.id);
 
     assert_eq!(deployed_pool, pool);
+
+    let event =
+        get_latest_event::<ThreePoolCreated>(&testing_env.env).expect("Expected ThreePoolCreated");
+    let mut tokens = [token_a.id, token_b.id, token_c.id];
+    tokens.sort();
+
+    assert_eq!([event.token0, event.token1, event.token2], tokens);
+    assert_eq!(event.pool, deployed_pool);
 }
 
 #[test]
@@ -193,23 +216,27 @@ fn get_pool() {
         ..
     } = testing_env;
 
-    let pool = testing_env.factory.pool(&token_b.id, &token_a.id, &token_c.id);
+    let pool = testing_env
+        .factory
+        .pool(&token_b.id, &token_a.id, &token_c.id);
     assert_eq!(pool, testing_env.pool.id);
 
-    let pool = testing_env.factory.pool(&token_a.id, &token_c.id, &token_b.id);
+    let pool = testing_env
+        .factory
+        .pool(&token_a.id, &token_c.id, &token_b.id);
     assert_eq!(pool, testing_env.pool.id);
 }
 
 #[test]
-#[should_panic = "DexContract(MaxPoolsNumReached)"]
 fn add_new_pools() {
     let testing_env = TestingEnv::default();
 
-    for _ in 0..20 {
+    // Past the number of pools the factory used to be capped at
+    for _ in 0..25 {
         let (token_a, token_b, token_c) =
             TestingEnv::generate_tokens(&testing_env.env, testing_env.admin.as_ref());
 
-        let _ = testing_env.factory.create_pool(
+        let pool = testing_env.factory.create_pool(
             testing_env.admin.as_ref(),
             10,
             &token_a.id,
@@ -218,18 +245,95 @@ fn add_new_pools() {
             10,
             10,
         );
+        assert_eq!(
+            testing_env
+                .factory
+                .pool(&token_a.id, &token_b.id, &token_c.id),
+            pool
+        );
     }
 
+    assert_eq!(testing_env.factory.client.pools().len(), 26);
+}
+
+#[test]
+fn predict_pool_address() {
+    let testing_env = TestingEnv::default();
+    let admin = testing_env.admin.as_ref();
+    let (token_a, token_b, token_c) = TestingEnv::generate_tokens(&testing_env.env, admin);
+    let client = &testing_env.factory.client;
+
+    let predicted_pool = client.predict_pool_address(
+        admin,
+        &vec![
+            &testing_env.env,
+            token_c.id.clone(),
+            token_a.id.clone(),
+            token_b.id.clone(),
+        ],
+        &None,
+    );
+    assert_eq!(
+        client.predict_pool_address(
+            admin,
+            &vec![
+                &testing_env.env,
+                token_b.id.clone(),
+                token_c.id.clone(),
+                token_a.id.clone(),
+            ],
+            &None,
+        ),
+        predicted_pool
+    );
+
+    let deployed_pool =
+        testing_env
+            .factory
+            .create_pool(admin, 10, &token_a.id, &token_b.id, &token_c.id, 10, 10);
+    assert_eq!(deployed_pool, predicted_pool);
+}
+
+#[test]
+fn create_pool_with_liquidity() {
+    let testing_env = TestingEnv::default();
     let (token_a, token_b, token_c) =
         TestingEnv::generate_tokens(&testing_env.env, testing_env.admin.as_ref());
-
-    let _ = testing_env.factory.create_pool(
+    for token in [&token_a, &token_b, &token_c] {
+        token.airdrop(&testing_env.alice, 1_000.0);
+    }
+    let tokens = vec![
+        &testing_env.env,
+        token_c.id.clone(),
+        token_a.id.clone(),
+        token_b.id.clone(),
+    ];
+    let amount = float_to_uint(100.0, 7);
+
+    let pool = testing_env.factory.client.create_pool_with_liquidity(
+        testing_env.alice.as_ref(),
         testing_env.admin.as_ref(),
-        10,
-        &token_a.id,
-        &token_b.id,
-        &token_c.id,
-        10,
-        10,
+        &10,
+        &tokens,
+        &10,
+        &10,
+        &0,
+        &vec![&testing_env.env, amount, amount, amount],
+        &0,
     );
+
+    assert_eq!(testing_env.factory.client.pool(&tokens, &None), pool);
+    for token in [&token_a, &token_b, &token_c] {
+        assert_eq!(token.balance_of(&pool), amount);
+    }
+
+    let created =
+        get_latest_event::<ThreePoolCreated>(&testing_env.env).expect("Expected ThreePoolCreated");
+    assert_eq!(created.pool, pool);
+
+    let deposit =
+        get_latest_event::<three_pool::Deposit>(&testing_env.env).expect("Expected Deposit");
+    assert_eq!(deposit.user, testing_env.alice.as_address());
+    assert_eq!(deposit.amounts, (amount, amount, amount));
+    assert!(deposit.lp_amount > 0);
 }
@@ -1,6 +1,6 @@
 pub mod contracts;
-pub mod utils;
 pub mod three_pool_utils;
+pub mod utils;
 
 #[cfg(test)]
 mod factory;
@@ -10,6 +10,14 @@ mod pool;
 
 #[cfg(test)]
 mod three_pool;
+
+#[cfg(test)]
+mod stable_pool;
+
+#[cfg(test)]
+mod metapool;
+
+#[cfg(test)]
+mod factory_three_pool;
 pub mod fuzzing;
 pub mod three_pool_fuzzing;
-pub mod factory_three_pool;
@@ -0,0 +1,451 @@
+use soroban_sdk::{vec, Address, Env};
+
+use crate::{
+    contracts::{
+        factory::{MetapoolCreated, PoolStatus},
+        metapool::{self, BaseRewardsAdded, Direction, SwappedUnderlying},
+        three_pool::Token as PoolToken,
+    },
+    three_pool_utils::{
+        assert_rel_eq, desoroban_result, float_to_uint, float_to_uint_sp, get_latest_event,
+        unwrap_call_result, TestingEnv, TestingEnvConfig, Token, User,
+    },
+    utils::RateProvider,
+};
+
+struct Metapool {
+    env: Env,
+    id: Address,
+    client: metapool::Client<'static>,
+    token: Token,
+}
+
+impl Metapool {
+    fn create(testing_env: &TestingEnv, fee_share_bp: u128) -> Metapool {
+        let env = testing_env.env.clone();
+        let admin = testing_env.admin.as_ref();
+        let token = Token::create(&env, admin, PoolToken::A, "meta");
+        token.default_airdrop(&testing_env.alice);
+        token.default_airdrop(&testing_env.bob);
+
+        let id = unwrap_call_result(
+            &env,
+            desoroban_result(testing_env.factory.client.try_create_metapool(
+                admin,
+                admin,
+                &20,
+                &token.id,
+                &testing_env.pool.id,
+                &fee_share_bp,
+                &0,
+                &0,
+            )),
+        );
+        let client = metapool::Client::new(&env, &id);
+
+        let metapool = Metapool {
+            env,
+            id,
+            client,
+            token,
+        };
+
+        let alice = &testing_env.alice;
+        testing_env
+            .pool
+            .deposit(alice, (20_000.0, 20_000.0, 20_000.0), 0.0);
+        metapool.deposit(alice, 20_000.0);
+
+        metapool
+    }
+
+    /// Deposits `amount` of the pool token along with the same amount of the base pool LP
+    fn deposit(&self, user: &User, amount: f64) {
+        unwrap_call_result(
+            &self.env,
+            desoroban_result(self.client.try_deposit(
+                &user.as_address(),
+                &(float_to_uint(amount, 7), float_to_uint_sp(amount)),
+                &0,
+            )),
+        );
+    }
+
+    fn swap_underlying(&self, sender: &User, amount: u128, token_from: u32, token_to: u32) -> u128 {
+        unwrap_call_result(
+            &self.env,
+            desoroban_result(self.client.try_swap_underlying(
+                &sender.as_address(),
+                &sender.as_address(),
+                &amount,
+                &0,
+                &token_from,
+                &token_to,
+            )),
+        )
+    }
+}
+
+#[test]
+fn create_metapool() {
+    let testing_env = TestingEnv::default();
+    let metapool = Metapool::create(&testing_env, 0);
+
+    let event =
+        get_latest_event::<MetapoolCreated>(&testing_env.env).expect("Expected MetapoolCreated");
+    assert_eq!(event.token, metapool.token.id);
+    assert_eq!(event.base_pool, testing_env.pool.id);
+    assert_eq!(event.pool, metapool.id);
+
+    let pool = testing_env.factory.client.pool(
+        &vec![
+            &testing_env.env,
+            metapool.token.id.clone(),
+            testing_env.pool.id.clone(),
+        ],
+        &None,
+    );
+    assert_eq!(pool, metapool.id);
+
+    let pool_info = metapool.client.get_pool();
+    assert_eq!(pool_info.tokens.data.0, metapool.token.id);
+    assert_eq!(pool_info.tokens.data.1, testing_env.pool.id);
+    let (token_0, token_1, token_2) = testing_env.pool.client.get_tokens();
+    assert_eq!(
+        pool_info.base_tokens,
+        vec![&testing_env.env, token_0, token_1, token_2]
+    );
+}
+
+#[test]
+#[should_panic = "DexContract(NotFound)"]
+fn create_metapool_unknown_base_pool() {
+    let testing_env = TestingEnv::default();
+    let admin = testing_env.admin.as_ref();
+
+    unwrap_call_result(
+        &testing_env.env,
+        desoroban_result(testing_env.factory.client.try_create_metapool(
+            admin,
+            admin,
+            &20,
+            &testing_env.native_token.id,
+            &testing_env.token_a.id,
+            &0,
+            &0,
+            &0,
+        )),
+    );
+}
+
+#[test]
+#[should_panic = "DexContract(IdenticalAddresses)"]
+fn create_metapool_with_base_token() {
+    let testing_env = TestingEnv::default();
+    let admin = testing_env.admin.as_ref();
+
+    unwrap_call_result(
+        &testing_env.env,
+        desoroban_result(testing_env.factory.client.try_create_metapool(
+            admin,
+            admin,
+            &20,
+            &testing_env.token_b.id,
+            &testing_env.pool.id,
+            &0,
+            &0,
+            &0,
+        )),
+    );
+}
+
+#[test]
+#[should_panic = "DexContract(InvalidArg)"]
+fn create_metapool_on_stable_pool() {
+    let testing_env = TestingEnv::default();
+    let admin = testing_env.admin.as_ref();
+    let tokens = vec![
+        &testing_env.env,
+        testing_env.token_a.id.clone(),
+        testing_env.token_b.id.clone(),
+        testing_env.token_c.id.clone(),
+    ];
+    let stable_pool = unwrap_call_result(
+        &testing_env.env,
+        desoroban_result(
+            testing_env
+                .factory
+                .client
+                .try_create_stable_pool(admin, admin, &20, &tokens, &0, &0, &1),
+        ),
+    );
+
+    unwrap_call_result(
+        &testing_env.env,
+        desoroban_result(testing_env.factory.client.try_create_metapool(
+            admin,
+            admin,
+            &20,
+            &testing_env.native_token.id,
+            &stable_pool,
+            &0,
+            &0,
+            &0,
+        )),
+    );
+}
+
+#[test]
+#[should_panic = "DexContract(InvalidArg)"]
+fn create_metapool_on_delisted_pool() {
+    let testing_env = TestingEnv::default();
+    let admin = testing_env.admin.as_ref();
+    unwrap_call_result(
+        &testing_env.env,
+        desoroban_result(
+            testing_env
+                .factory
+                .client
+                .try_set_pool_status(&testing_env.pool.id, &PoolStatus::Delisted),
+        ),
+    );
+
+    unwrap_call_result(
+        &testing_env.env,
+        desoroban_result(testing_env.factory.client.try_create_metapool(
+            admin,
+            admin,
+            &20,
+            &testing_env.native_token.id,
+            &testing_env.pool.id,
+            &0,
+            &0,
+            &0,
+        )),
+    );
+}
+
+#[test]
+fn deposit_holds_base_lp() {
+    let testing_env = TestingEnv::default();
+    let metapool = Metapool::create(&testing_env, 0);
+
+    assert_eq!(
+        testing_env
+            .pool
+            .client
+            .get_user_deposit(&metapool.id)
+            .lp_amount,
+        float_to_uint_sp(20_000.0)
+    );
+    assert_eq!(
+        metapool
+            .client
+            .get_user_deposit(testing_env.alice.as_ref())
+            .lp_amount,
+        float_to_uint_sp(40_000.0)
+    );
+}
+
+#[test]
+fn swap_for_base_lp() {
+    let testing_env = TestingEnv::default();
+    let metapool = Metapool::create(&testing_env, 0);
+    let bob = &testing_env.bob;
+
+    let output = unwrap_call_result(
+        &testing_env.env,
+        desoroban_result(metapool.client.try_swap(
+            bob.as_ref(),
+            bob.as_ref(),
+            &float_to_uint(100.0, 7),
+            &0,
+            &Direction::A2B,
+        )),
+    );
+
+    assert!(output > float_to_uint_sp(99.0) && output < float_to_uint_sp(100.0));
+    assert_eq!(testing_env.pool.user_deposit(bob).lp_amount, output);
+}
+
+#[test]
+fn swap_underlying_to_base_token() {
+    let testing_env = TestingEnv::default();
+    let metapool = Metapool::create(&testing_env, 0);
+    let bob = &testing_env.bob;
+    let token_to = &testing_env.token_c;
+    let token_to_before = token_to.balance_of(bob.as_ref());
+
+    let amount = float_to_uint(100.0, 7);
+    let output = metapool.swap_underlying(bob, amount, 0, 3);
+
+    assert!(output > amount * 99 / 100 && output < amount);
+    assert_eq!(token_to.balance_of(bob.as_ref()), token_to_before + output);
+    assert_eq!(
+        testing_env
+            .pool
+            .client
+            .get_user_deposit(&metapool.id)
+            .lp_amount,
+        metapool.client.get_pool().token_balances.data.1
+    );
+
+    let event = get_latest_event::<SwappedUnderlying>(&testing_env.env)
+        .expect("Expected SwappedUnderlying");
+    assert_eq!(event.from_token, metapool.token.id);
+    assert_eq!(event.to_token, token_to.id);
+    assert_eq!(event.from_amount, amount);
+    assert_eq!(event.to_amount, output);
+}
+
+#[test]
+fn swap_underlying_from_base_token() {
+    let testing_env = TestingEnv::default();
+    let metapool = Metapool::create(&testing_env, 0);
+    let bob = &testing_env.bob;
+    let meta_before = metapool.token.balance_of(bob.as_ref());
+
+    let amount = float_to_uint(100.0, 7);
+    let output = metapool.swap_underlying(bob, amount, 2, 0);
+
+    assert!(output > amount * 99 / 100 && output < amount);
+    assert_eq!(
+        metapool.token.balance_of(bob.as_ref()),
+        meta_before + output
+    );
+
+    let event = get_latest_event::<SwappedUnderlying>(&testing_env.env)
+        .expect("Expected SwappedUnderlying");
+    assert_eq!(event.from_token, testing_env.token_b.id);
+    assert_eq!(event.to_token, metapool.token.id);
+    assert_eq!(event.to_amount, output);
+}
+
+#[test]
+#[should_panic = "DexContract(InvalidArg)"]
+fn swap_underlying_between_base_tokens() {
+    let testing_env = TestingEnv::default();
+    let metapool = Metapool::create(&testing_env, 0);
+
+    metapool.swap_underlying(&testing_env.bob, float_to_uint(100.0, 7), 1, 2);
+}
+
+#[test]
+#[should_panic = "DexContract(InvalidArg)"]
+fn swap_underlying_invalid_token_index() {
+    let testing_env = TestingEnv::default();
+    let metapool = Metapool::create(&testing_env, 0);
+
+    metapool.swap_underlying(&testing_env.bob, float_to_uint(100.0, 7), 0, 4);
+}
+
+#[test]
+#[should_panic = "DexContract(InvalidArg)"]
+fn set_rate_provider_for_base_lp() {
+    let testing_env = TestingEnv::default();
+    let metapool = Metapool::create(&testing_env, 0);
+
+    unwrap_call_result(
+        &testing_env.env,
+        desoroban_result(
+            metapool
+                .client
+                .try_set_rate_provider(&metapool::Token::B, &None),
+        ),
+    );
+}
+
+#[test]
+#[should_panic = "DexContract(Forbidden)"]
+fn set_rate_provider_with_liquidity() {
+    let testing_env = TestingEnv::default();
+    let metapool = Metapool::create(&testing_env, 0);
+    let rate_provider = RateProvider::create(&testing_env.env, 1.1);
+
+    unwrap_call_result(
+        &testing_env.env,
+        desoroban_result(
+            metapool
+                .client
+                .try_set_rate_provider(&metapool::Token::A, &Some(rate_provider.id)),
+        ),
+    );
+}
+
+#[test]
+fn harvest_base_rewards() {
+    let testing_env = TestingEnv::create(TestingEnvConfig::default().with_pool_fee_share(1.0));
+    let metapool = Metapool::create(&testing_env, 0);
+    let TestingEnv {
+        ref env,
+        ref pool,
+        ref admin,
+        ref alice,
+        ref bob,
+        ref token_a,
+        ref token_b,
+        ..
+    } = testing_env;
+
+    unwrap_call_result(
+        env,
+        desoroban_result(metapool.client.try_set_admin_fee_share(&2_000)),
+    );
+    pool.swap(bob, bob, 1_000.0, 0.0, token_a, token_b);
+
+    testing_env.clear_mock_auth();
+    unwrap_call_result(env, desoroban_result(metapool.client.try_harvest(&0)));
+    env.mock_all_auths();
+
+    let event = get_latest_event::<BaseRewardsAdded>(env).expect("Expected BaseRewardsAdded");
+    assert!(event.rewards.1 > 0);
+    assert!(event.lp_amount > 0);
+    // Only the pool's share of the imbalance fee of the deposit is left for the next harvest
+    assert!(token_b.balance_of(&metapool.id) < event.rewards.1 / 100);
+
+    // The admin takes the 20% admin fee share of the rewards as base pool LP, the LPs the rest
+    let admin_lp_before = pool.client.get_user_deposit(admin.as_ref()).lp_amount;
+    unwrap_call_result(env, desoroban_result(metapool.client.try_claim_admin_fee()));
+    let admin_lp = pool.client.get_user_deposit(admin.as_ref()).lp_amount - admin_lp_before;
+    assert_eq!(admin_lp, event.lp_amount * 2_000 / 10_000);
+
+    let pending = metapool
+        .client
+        .pending_rewards(&vec![env, alice.as_address()])
+        .get_unchecked(0);
+    assert_rel_eq(pending.1, event.lp_amount - admin_lp, 1);
+}
+
+#[test]
+#[should_panic = "DexContract(ZeroAmount)"]
+fn harvest_without_base_rewards() {
+    let testing_env = TestingEnv::default();
+    let metapool = Metapool::create(&testing_env, 0);
+
+    unwrap_call_result(
+        &testing_env.env,
+        desoroban_result(metapool.client.try_harvest(&0)),
+    );
+}
+
+#[test]
+#[should_panic = "DexContract(Slippage)"]
+fn harvest_base_deposit_slippage() {
+    let testing_env = TestingEnv::create(TestingEnvConfig::default().with_pool_fee_share(1.0));
+    let metapool = Metapool::create(&testing_env, 0);
+    let TestingEnv {
+        ref env,
+        ref pool,
+        ref bob,
+        ref token_a,
+        ref token_b,
+        ..
+    } = testing_env;
+
+    pool.swap(bob, bob, 1_000.0, 0.0, token_a, token_b);
+
+    unwrap_call_result(
+        env,
+        desoroban_result(metapool.client.try_harvest(&u128::MAX)),
+    );
+}
@@ -1,8 +1,11 @@
 use test_case::test_case;
 
 use crate::{
-    contracts::pool::Direction,
-    utils::{Snapshot, TestingEnv, TestingEnvConfig, DOUBLE_ZERO},
+    contracts::pool::{Deposit, Direction, Token},
+    utils::{
+        assert_rel_eq, float_to_uint, float_to_uint_sp, get_latest_event, RateProvider, Snapshot,
+        TestingEnv, TestingEnvConfig, DOUBLE_ZERO,
+    },
 };
 
 #[test]
@@ -35,10 +38,10 @@ fn deposit_with_overflow() {
         ..
     } = testing_env;
 
-    yusd_token.airdrop(alice, 10_000_000_000.0);
-    yaro_token.airdrop(alice, 10_000_000_000.0);
+    yusd_token.airdrop(alice, 1_000_000_000_000_000.0);
+    yaro_token.airdrop(alice, 1_000_000_000_000_000.0);
 
-    pool.deposit(alice, (600_000_000.0, 600_000_000.0), 0.0);
+    pool.deposit(alice, (600_000_000_000_000.0, 600_000_000_000_000.0), 0.0);
 }
 
 #[test]
@@ -50,11 +53,11 @@ fn deposit_invalid_first_deposit() {
         .deposit(&testing_env.alice, (100.0, 25.0), 0.0);
 }
 
-#[test_case((100.0, 50.0), DOUBLE_ZERO, 150.0 ; "base")]
-#[test_case((50_000_000.0, 5_000.0), DOUBLE_ZERO, 31_492_001.072 ; "deposit_disbalance")]
+#[test_case((100.0, 50.0), DOUBLE_ZERO, 149.999 ; "base")]
+#[test_case((50_000_000.0, 5_000.0), DOUBLE_ZERO, 31_492_001.073 ; "deposit_disbalance")]
 #[test_case((0.001, 0.001), DOUBLE_ZERO, 0.002 ; "smallest_deposit")]
-#[test_case((100.0, 0.0), DOUBLE_ZERO, 99.998 ; "deposit_only_yusd")]
-#[test_case((0.0, 100.0), DOUBLE_ZERO, 99.998 ; "deposit_only_yaro")]
+#[test_case((100.0, 0.0), DOUBLE_ZERO, 99.999 ; "deposit_only_yusd")]
+#[test_case((0.0, 100.0), DOUBLE_ZERO, 99.999 ; "deposit_only_yaro")]
 fn deposit(deposit: (f64, f64), expected_rewards: (f64, f64), expected_lp: f64) {
     let testing_env = TestingEnv::default();
     testing_env.do_deposit(&testing_env.alice, deposit, expected_rewards, expected_lp);
@@ -116,3 +119,95 @@ fn get_reward_after_second_deposit() {
         expected_lp_amount,
     );
 }
+
+#[test]
+fn deposit_imbalance_fee() {
+    let testing_env = TestingEnv::create(TestingEnvConfig::default().with_pool_fee_share(1.0));
+    let TestingEnv {
+        ref pool,
+        ref admin,
+        ref alice,
+        ..
+    } = testing_env;
+
+    let (lp_amount, fees) = pool
+        .client
+        .get_deposit_amount(&(float_to_uint(100.0, 7), 0));
+
+    // Half of the 1% fee on the ~50 token deviation of each balance
+    assert_rel_eq(fees.0, float_to_uint(0.25, 7), float_to_uint(0.001, 7));
+    assert_rel_eq(fees.1, float_to_uint(0.25, 7), float_to_uint(0.001, 7));
+    assert_rel_eq(lp_amount, float_to_uint_sp(99.498), 2);
+
+    pool.deposit(alice, (100.0, 0.0), 0.0);
+
+    let deposit = get_latest_event::<Deposit>(&testing_env.env).expect("Expected Deposit");
+    assert_eq!(deposit.lp_amount, lp_amount);
+    assert_eq!(deposit.fees, fees);
+    assert_eq!(pool.user_deposit(alice).lp_amount, lp_amount);
+
+    // Fees are distributed to the existing liquidity providers
+    let admin_rewards = pool.client.pending_reward(&admin.as_address());
+    assert_rel_eq(admin_rewards.0, fees.0, 1);
+    assert_rel_eq(admin_rewards.1, fees.1, 1);
+}
+
+#[test]
+fn deposit_balanced_without_imbalance_fee() {
+    let testing_env = TestingEnv::create(TestingEnvConfig::default().with_pool_fee_share(1.0));
+    let (lp_amount, fees) = testing_env
+        .pool
+        .client
+        .get_deposit_amount(&(float_to_uint(100.0, 7), float_to_uint(100.0, 7)));
+
+    assert_eq!(fees, (0, 0));
+    assert_eq!(lp_amount, float_to_uint_sp(200.0));
+}
+
+#[test]
+fn deposit_with_rate_provider() {
+    let testing_env = TestingEnv::create(TestingEnvConfig::default().with_admin_init_deposit(0.0));
+    let TestingEnv {
+        ref env,
+        ref pool,
+        ref admin,
+        ref alice,
+        ..
+    } = testing_env;
+
+    let rate_provider = RateProvider::create(env, 1.0);
+    pool.set_rate_provider(Token::A, Some(&rate_provider));
+    pool.deposit(admin, (100_000.0, 100_000.0), 0.0);
+    rate_provider.set_rate(1.1);
+
+    // The accrued value goes to the LPs through the virtual price
+    let virtual_price = pool.client.get_pool_info(&None).virtual_price;
+    assert_rel_eq(
+        virtual_price,
+        1_050_000_000_000_000_000,
+        1_000_000_000_000_000,
+    );
+
+    // A proportional deposit mints the same share of the LP supply
+    pool.deposit(alice, (1_000.0, 1_000.0), 0.0);
+    assert_rel_eq(
+        pool.user_deposit(alice).lp_amount,
+        float_to_uint_sp(2_000.0),
+        float_to_uint_sp(0.01),
+    );
+
+    let (yusd_before, yaro_before, _) = Snapshot::take(&testing_env).get_user_balances(alice);
+    pool.withdraw(alice, pool.user_lp_amount_f64(alice));
+    let (yusd_after, yaro_after, _) = Snapshot::take(&testing_env).get_user_balances(alice);
+
+    assert_rel_eq(
+        yusd_after - yusd_before,
+        float_to_uint(1_000.0, 7),
+        float_to_uint(0.1, 7),
+    );
+    assert_rel_eq(
+        yaro_after - yaro_before,
+        float_to_uint(1_000.0, 7),
+        float_to_uint(0.1, 7),
+    );
+}
@@ -2,6 +2,7 @@ pub mod admin;
 pub mod claims;
 pub mod deposit;
 pub mod swap;
+pub mod view;
 pub mod withdraw;
 
 pub struct DepositArgs {
@@ -1,8 +1,15 @@
+use soroban_sdk::{
+    testutils::{Events, Ledger},
+    IntoVal, Symbol, TryFromVal, Val, Vec,
+};
 use test_case::test_case;
 
 use crate::{
-    contracts::pool::Direction,
-    utils::{Snapshot, TestingEnv, TestingEnvConfig},
+    contracts::pool::{Direction, Swapped, Sync, Token},
+    utils::{
+        assert_rel_eq, float_to_uint, get_latest_event, PriceFeed, RateProvider, Snapshot,
+        TestingEnv, TestingEnvConfig,
+    },
 };
 
 use super::DepositArgs;
@@ -48,8 +55,8 @@ fn simple_swaps(
     );
 }
 
-#[test_case(DepositArgs { amounts: (0.0, 250_000.0), min_lp: 249_000.0 }, 10_000.0, 10090.0, Direction::A2B, 10_091.038_86, 10.101_140 ; "swap_more_yaro")]
-#[test_case(DepositArgs { amounts: (250_000.0, 0.0), min_lp: 249_000.0 }, 10_000.0, 995.0, Direction::A2B, 9_880.313_796, 9.890_204 ; "swap_more_yusd")]
+#[test_case(DepositArgs { amounts: (0.0, 250_000.0), min_lp: 249_000.0 }, 10_000.0, 10090.0, Direction::A2B, 10_091.049_849, 10.101_151 ; "swap_more_yaro")]
+#[test_case(DepositArgs { amounts: (250_000.0, 0.0), min_lp: 249_000.0 }, 10_000.0, 995.0, Direction::A2B, 9_880.301_808, 9.890_192 ; "swap_more_yusd")]
 fn swap_disbalance(
     deposit_args: DepositArgs,
     amount: f64,
@@ -111,3 +118,306 @@ fn swap_more_than_pool_balance(direction: Direction) {
 
     assert!(alice_balance_after <= alice_balance_before);
 }
+
+#[test]
+fn swap_event_topics() {
+    let testing_env = TestingEnv::default();
+    let TestingEnv {
+        ref env,
+        ref pool,
+        ref alice,
+        ref bob,
+        ref yusd_token,
+        ref yaro_token,
+        ..
+    } = testing_env;
+
+    pool.swap(alice, bob, 100.0, 98.0, Direction::A2B);
+
+    let swapped_symbol = Symbol::new(env, "Swapped");
+    let (_, topics, _) = env
+        .events()
+        .all()
+        .iter()
+        .rev()
+        .find(|(_, topics, _)| {
+            topics
+                .first()
+                .and_then(|topic| Symbol::try_from_val(env, &topic).ok())
+                == Some(swapped_symbol.clone())
+        })
+        .unwrap();
+    let expected_topics: Vec<Val> = (
+        swapped_symbol,
+        alice.as_address(),
+        yusd_token.as_address(),
+        yaro_token.as_address(),
+    )
+        .into_val(env);
+
+    assert_eq!(topics, expected_topics);
+}
+
+#[test]
+fn swap_sync_event() {
+    let testing_env = TestingEnv::default();
+    let TestingEnv {
+        ref env,
+        ref pool,
+        ref alice,
+        ref bob,
+        ..
+    } = testing_env;
+
+    pool.swap(alice, bob, 100.0, 98.0, Direction::A2B);
+    let previous_nonce = get_latest_event::<Sync>(env).expect("Expected Sync").nonce;
+
+    pool.swap(alice, bob, 100.0, 98.0, Direction::B2A);
+    let sync = get_latest_event::<Sync>(env).expect("Expected Sync");
+    let pool_info = pool.client.get_pool();
+
+    assert_eq!(sync.nonce, previous_nonce + 1);
+    assert_eq!(sync.token_balances, pool_info.token_balances.data);
+    assert_eq!(sync.total_lp_amount, pool_info.total_lp_amount);
+    assert_eq!(
+        sync.acc_rewards_per_share_p,
+        pool_info.acc_rewards_per_share_p.data
+    );
+}
+
+#[test]
+fn swap_offpeg_fee() {
+    let testing_env = TestingEnv::create(TestingEnvConfig::default().with_pool_fee_share(0.1));
+    let TestingEnv {
+        ref env,
+        ref pool,
+        ref alice,
+        ..
+    } = testing_env;
+
+    let small_amount = float_to_uint(1.0, 7);
+    let large_amount = float_to_uint(90_000.0, 7);
+    let (_, flat_small_fee) = pool.client.get_receive_amount(&small_amount, &Token::A);
+    let (_, flat_large_fee) = pool.client.get_receive_amount(&large_amount, &Token::A);
+
+    pool.set_offpeg_fee_multiplier(100_000);
+
+    // A balanced pool keeps the fee close to the flat one
+    let (_, small_fee) = pool.client.get_receive_amount(&small_amount, &Token::A);
+    assert_rel_eq(small_fee, flat_small_fee, 1);
+
+    let quote = pool.client.quote_swap(&large_amount, &Token::A);
+    assert!(quote.fee > flat_large_fee * 2);
+
+    let (send_amount, send_fee) = pool.client.get_send_amount(&quote.output, &Token::B);
+    assert_rel_eq(send_amount, large_amount, large_amount / 1000);
+    assert_rel_eq(send_fee, quote.fee, quote.fee / 1000);
+
+    pool.swap(alice, alice, 90_000.0, 0.0, Direction::A2B);
+
+    let swapped = get_latest_event::<Swapped>(env).expect("Expected Swapped");
+    assert_eq!(swapped.to_amount, quote.output);
+    assert_eq!(swapped.fee, quote.fee);
+}
+
+#[test]
+fn swap_sub_bp_fee() {
+    let testing_env = TestingEnv::default();
+    let TestingEnv {
+        ref env,
+        ref pool,
+        ref alice,
+        ..
+    } = testing_env;
+
+    // 0.004%
+    pool.set_swap_fee_share(0.004);
+    pool.swap(alice, alice, 1_000.0, 0.0, Direction::A2B);
+
+    let swapped = get_latest_event::<Swapped>(env).expect("Expected Swapped");
+    assert_rel_eq(
+        swapped.fee,
+        float_to_uint(0.04, 7),
+        float_to_uint(0.000_1, 7),
+    );
+}
+
+#[test]
+fn swap_with_rate_provider() {
+    let testing_env = TestingEnv::create(TestingEnvConfig::default().with_admin_init_deposit(0.0));
+    let TestingEnv {
+        ref env,
+        ref pool,
+        ref admin,
+        ref alice,
+        ..
+    } = testing_env;
+
+    let rate_provider = RateProvider::create(env, 1.0);
+    pool.set_rate_provider(Token::A, Some(&rate_provider));
+    pool.deposit(admin, (100_000.0, 100_000.0), 0.0);
+
+    let amount = float_to_uint(1_000.0, 7);
+    let (flat_output, _) = pool.client.get_receive_amount(&amount, &Token::A);
+
+    rate_provider.set_rate(1.1);
+
+    // One yUSD is worth 1.1 yaro, the pool values its balance accordingly and the imbalance
+    // in value costs less than a percent
+    let (output, _) = pool.client.get_receive_amount(&amount, &Token::A);
+    assert_rel_eq(output, flat_output * 11 / 10, flat_output / 100);
+    let (reverse_output, _) = pool.client.get_receive_amount(&amount, &Token::B);
+    assert_rel_eq(reverse_output, flat_output * 10 / 11, flat_output / 100);
+
+    // The rate is read on every call
+    rate_provider.set_rate(1.2);
+    let quote = pool.client.quote_swap(&amount, &Token::A);
+    assert!(quote.output > output);
+    assert_rel_eq(quote.output, flat_output * 12 / 10, flat_output / 100);
+
+    pool.swap(alice, alice, 1_000.0, 0.0, Direction::A2B);
+
+    let swapped = get_latest_event::<Swapped>(env).expect("Expected Swapped");
+    assert_eq!(swapped.to_amount, quote.output);
+}
+
+#[test]
+#[should_panic = "DexContract(InvalidRate)"]
+fn swap_with_failing_rate_provider() {
+    let testing_env = TestingEnv::create(TestingEnvConfig::default().with_admin_init_deposit(0.0));
+    let TestingEnv {
+        ref env,
+        ref pool,
+        ref admin,
+        ref alice,
+        ..
+    } = testing_env;
+
+    let rate_provider = RateProvider::create(env, 1.1);
+    pool.set_rate_provider(Token::A, Some(&rate_provider));
+    pool.deposit(admin, (100_000.0, 100_000.0), 0.0);
+
+    rate_provider.client.set_failing(&true);
+    pool.swap(alice, alice, 1_000.0, 0.0, Direction::A2B);
+}
+
+#[test]
+#[should_panic = "DexContract(InvalidRate)"]
+fn swap_with_zero_rate() {
+    let testing_env = TestingEnv::create(TestingEnvConfig::default().with_admin_init_deposit(0.0));
+    let TestingEnv {
+        ref env,
+        ref pool,
+        ref admin,
+        ref alice,
+        ..
+    } = testing_env;
+
+    let rate_provider = RateProvider::create(env, 1.1);
+    pool.set_rate_provider(Token::A, Some(&rate_provider));
+    pool.deposit(admin, (100_000.0, 100_000.0), 0.0);
+
+    rate_provider.set_rate(0.0);
+    pool.swap(alice, alice, 1_000.0, 0.0, Direction::A2B);
+}
+
+#[test]
+fn swap_with_oracle() {
+    let amount = float_to_uint(1_000.0, 7);
+    let (flat_output, _) = TestingEnv::default()
+        .pool
+        .client
+        .get_receive_amount(&amount, &Token::A);
+
+    let testing_env = TestingEnv::create(TestingEnvConfig::default().with_admin_init_deposit(0.0));
+    let TestingEnv {
+        ref env,
+        ref pool,
+        ref admin,
+        ref alice,
+        ..
+    } = testing_env;
+
+    // One yUSD is priced at 1.08 yaro, the curve centres on the price instead of 1:1
+    let price_feed = PriceFeed::create(env, 1.08);
+    pool.set_oracle(Token::A, &price_feed, 60, 5.0);
+    pool.deposit(admin, (100_000.0, 100_000.0), 0.0);
+
+    let quote = pool.client.quote_swap(&amount, &Token::A);
+    assert_rel_eq(quote.output, flat_output * 108 / 100, flat_output / 100);
+
+    // Small moves of a fresh price are followed
+    env.ledger().with_mut(|ledger| ledger.timestamp += 30);
+    price_feed.set_price(1.1);
+    let (output, _) = pool.client.get_receive_amount(&amount, &Token::A);
+    assert_rel_eq(output, flat_output * 110 / 100, flat_output / 100);
+
+    pool.swap(alice, alice, 1_000.0, 0.0, Direction::A2B);
+
+    let swapped = get_latest_event::<Swapped>(env).expect("Expected Swapped");
+    assert_eq!(swapped.to_amount, output);
+}
+
+#[test]
+#[should_panic = "DexContract(StalePrice)"]
+fn swap_with_oracle_stale_price() {
+    let testing_env = TestingEnv::create(TestingEnvConfig::default().with_admin_init_deposit(0.0));
+    let TestingEnv {
+        ref env,
+        ref pool,
+        ref admin,
+        ref alice,
+        ..
+    } = testing_env;
+
+    let price_feed = PriceFeed::create(env, 1.08);
+    pool.set_oracle(Token::A, &price_feed, 60, 5.0);
+    pool.deposit(admin, (100_000.0, 100_000.0), 0.0);
+
+    env.ledger().with_mut(|ledger| ledger.timestamp += 61);
+    pool.swap(alice, alice, 1_000.0, 0.0, Direction::A2B);
+}
+
+#[test]
+#[should_panic = "DexContract(PriceDeviation)"]
+fn swap_with_oracle_price_drift() {
+    let testing_env = TestingEnv::create(TestingEnvConfig::default().with_admin_init_deposit(0.0));
+    let TestingEnv {
+        ref env,
+        ref pool,
+        ref admin,
+        ref alice,
+        ..
+    } = testing_env;
+
+    let price_feed = PriceFeed::create(env, 1.08);
+    pool.set_oracle(Token::A, &price_feed, 60, 5.0);
+    pool.deposit(admin, (100_000.0, 100_000.0), 0.0);
+
+    price_feed.set_price(1.11);
+    pool.swap(alice, alice, 1_000.0, 0.0, Direction::A2B);
+
+    // Each step is within 5% of the previous price, the drift from the peg is not
+    price_feed.set_price(1.14);
+    pool.swap(alice, alice, 1_000.0, 0.0, Direction::A2B);
+}
+
+#[test]
+#[should_panic = "DexContract(PriceDeviation)"]
+fn swap_with_oracle_price_deviation() {
+    let testing_env = TestingEnv::create(TestingEnvConfig::default().with_admin_init_deposit(0.0));
+    let TestingEnv {
+        ref env,
+        ref pool,
+        ref admin,
+        ref alice,
+        ..
+    } = testing_env;
+
+    let price_feed = PriceFeed::create(env, 1.08);
+    pool.set_oracle(Token::A, &price_feed, 60, 5.0);
+    pool.deposit(admin, (100_000.0, 100_000.0), 0.0);
+
+    price_feed.set_price(1.2);
+    pool.swap(alice, alice, 1_000.0, 0.0, Direction::A2B);
+}
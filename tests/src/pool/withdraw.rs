@@ -1,8 +1,12 @@
+use soroban_sdk::testutils::Ledger;
 use test_case::test_case;
 
 use crate::{
-    contracts::pool::Direction,
-    utils::{assert_rel_eq, float_to_uint, Snapshot, TestingEnv, TestingEnvConfig, DOUBLE_ZERO},
+    contracts::pool::{Direction, Token},
+    utils::{
+        assert_rel_eq, float_to_uint, PriceFeed, RateProvider, Snapshot, TestingEnv,
+        TestingEnvConfig, DOUBLE_ZERO,
+    },
 };
 
 use super::{DepositArgs, DoWithdrawArgs};
@@ -32,31 +36,31 @@ fn withdraw_zero_change() {
 #[test_case(
     TestingEnvConfig::default(),
     DepositArgs { amounts: (4_000.0, 5_000.0), min_lp: 8_999.0 },
-    DoWithdrawArgs { amount: 8999.942, expected_amounts: (4_478.442, 4_521.503), expected_fee: DOUBLE_ZERO, expected_rewards: DOUBLE_ZERO, expected_user_lp_diff: 8_999.942, expected_admin_fee: DOUBLE_ZERO }
+    DoWithdrawArgs { amount: 8999.941, expected_amounts: (4_478.443, 4_521.502), expected_fee: DOUBLE_ZERO, expected_rewards: DOUBLE_ZERO, expected_user_lp_diff: 8_999.941, expected_admin_fee: DOUBLE_ZERO }
     ; "base_withdraw"
 )]
 #[test_case(
     TestingEnvConfig::default().with_pool_fee_share(0.1).with_pool_admin_fee(20.0),
     DepositArgs { amounts: (4_000.0, 5_000.0), min_lp: 8_999.0 },
-    DoWithdrawArgs { amount: 8999.942, expected_amounts: (4_473.963, 4_516.981), expected_fee: (4.478_442, 4.521_503), expected_rewards: DOUBLE_ZERO, expected_user_lp_diff: 8_999.942, expected_admin_fee: (0.895_688_4, 0.904_300_6) }
+    DoWithdrawArgs { amount: 8999.442, expected_amounts: (4_473.715, 4_516.730_000_01), expected_fee: (4.478_194, 4.521_252), expected_rewards: DOUBLE_ZERO, expected_user_lp_diff: 8_999.442, expected_admin_fee: (0.895_638_8, 0.904_250_4) }
     ; "withdraw_with_fee"
 )]
 #[test_case(
     TestingEnvConfig::default(),
     DepositArgs { amounts: (15_000.0, 25_000.0), min_lp: 39_950.0 },
-    DoWithdrawArgs { amount: 0.002, expected_amounts: (0.002, 0.001), expected_fee: DOUBLE_ZERO, expected_rewards: DOUBLE_ZERO, expected_user_lp_diff: 0.002, expected_admin_fee: DOUBLE_ZERO }
+    DoWithdrawArgs { amount: 0.002, expected_amounts: (0.001, 0.001), expected_fee: DOUBLE_ZERO, expected_rewards: DOUBLE_ZERO, expected_user_lp_diff: 0.002, expected_admin_fee: DOUBLE_ZERO }
     ; "smallest_withdraw"
 )]
 #[test_case(
     TestingEnvConfig::default().with_pool_fee_share(0.1),
     DepositArgs { amounts: (15_000.0, 25_000.0), min_lp: 39_950.0 },
-    DoWithdrawArgs { amount: 0.004, expected_amounts: (0.002, 0.001), expected_fee: (0.000_003, 0.000_002), expected_rewards: DOUBLE_ZERO, expected_user_lp_diff: 0.004, expected_admin_fee: DOUBLE_ZERO }
+    DoWithdrawArgs { amount: 0.004, expected_amounts: (0.001, 0.001), expected_fee: (0.000_002, 0.000_002), expected_rewards: DOUBLE_ZERO, expected_user_lp_diff: 0.004, expected_admin_fee: DOUBLE_ZERO }
     ; "smallest_withdraw_with_fee"
 )]
 #[test_case(
     TestingEnvConfig::default(),
     DepositArgs { amounts: (50_000_000.0, 5_000.0), min_lp: 31_250_000.0 },
-    DoWithdrawArgs { amount: 31_492_001.072, expected_amounts: (49_783_831.892, 104_337.373), expected_fee: DOUBLE_ZERO, expected_rewards: DOUBLE_ZERO, expected_user_lp_diff: 31_492_001.072, expected_admin_fee: DOUBLE_ZERO }
+    DoWithdrawArgs { amount: 31_492_001.072, expected_amounts: (49_783_831.890, 104_337.373), expected_fee: DOUBLE_ZERO, expected_rewards: DOUBLE_ZERO, expected_user_lp_diff: 31_492_001.072, expected_admin_fee: DOUBLE_ZERO }
     ; "withdraw_disbalance"
 )]
 fn withdraw(config: TestingEnvConfig, deposit_args: DepositArgs, do_withdraw_args: DoWithdrawArgs) {
@@ -77,6 +81,59 @@ fn withdraw(config: TestingEnvConfig, deposit_args: DepositArgs, do_withdraw_arg
     );
 }
 
+#[test]
+fn withdraw_with_failing_rate_provider() {
+    let testing_env = TestingEnv::create(TestingEnvConfig::default().with_admin_init_deposit(0.0));
+    let TestingEnv {
+        ref env,
+        ref pool,
+        ref admin,
+        ref alice,
+        ..
+    } = testing_env;
+
+    let rate_provider = RateProvider::create(env, 1.1);
+    pool.set_rate_provider(Token::A, Some(&rate_provider));
+    pool.deposit(admin, (100_000.0, 100_000.0), 0.0);
+    pool.deposit(alice, (4_000.0, 5_000.0), 0.0);
+
+    // A reverting provider leaves the pool on its last rate for withdrawals and views
+    rate_provider.client.set_failing(&true);
+    let pool_info = pool.client.get_pool_info(&None);
+    assert_eq!(
+        pool_info.rates.0,
+        float_to_uint(1.1, RateProvider::DECIMALS)
+    );
+
+    pool.withdraw(alice, pool.user_lp_amount_f64(alice));
+    assert_eq!(pool.user_deposit(alice).lp_amount, 0);
+}
+
+#[test]
+fn withdraw_with_stale_oracle() {
+    let testing_env = TestingEnv::create(TestingEnvConfig::default().with_admin_init_deposit(0.0));
+    let TestingEnv {
+        ref env,
+        ref pool,
+        ref admin,
+        ref alice,
+        ..
+    } = testing_env;
+
+    let price_feed = PriceFeed::create(env, 1.08);
+    pool.set_oracle(Token::A, &price_feed, 60, 5.0);
+    pool.deposit(admin, (100_000.0, 100_000.0), 0.0);
+    pool.deposit(alice, (4_000.0, 5_000.0), 0.0);
+
+    // Swaps stop on a stale price, withdrawals go on at the last rate
+    env.ledger().with_mut(|ledger| ledger.timestamp += 61);
+    let pool_info = pool.client.get_pool_info(&None);
+    assert_eq!(pool_info.rates.0, 1_080_000_000_000_000_000);
+
+    pool.withdraw(alice, pool.user_lp_amount_f64(alice));
+    assert_eq!(pool.user_deposit(alice).lp_amount, 0);
+}
+
 #[test]
 fn withdraw_with_rewards() {
     let testing_env = TestingEnv::create(TestingEnvConfig::default().with_pool_fee_share(0.1));
@@ -88,12 +145,12 @@ fn withdraw_with_rewards() {
     } = testing_env;
 
     let deposits = (4_000.0, 5_000.0);
-    let expected_user_lp_diff = 8_999.942;
+    let expected_user_lp_diff = 8_999.442;
     // Alice has around 5% of the liquidity pool, we swap 1000 USD with 0.1% fee, which is 5% of 1 USD fee total
-    let expected_rewards = (0.0430_620, 0.0430_619);
+    let expected_rewards = (0.043_059_61, 0.043_059_6);
     // Withdraw amounts sum is less than deposit amounts sum
-    let expected_withdraw_amounts = (4_473.963, 4_516.981);
-    let expected_fee = (4.478_442, 4.521_503);
+    let expected_withdraw_amounts = (4_473.714, 4_516.730_000_01);
+    let expected_fee = (4.478_193, 4.521_252);
 
     pool.deposit(alice, deposits, 8_950.0);
     pool.swap(bob, bob, 1_000.0, 995.5, Direction::A2B);
@@ -206,13 +263,13 @@ fn withdraw_alice_loss_and_bob_profit() {
 
     let deposit = (200_000.0, 0.0);
     let swap_amount = 100_000.;
-    let expected_user_withdraw_lp_diff = 198_393.264;
-    let expected_rewards = (50.598_436_60, 0.0);
+    let expected_user_withdraw_lp_diff = 198_292.624;
+    let expected_rewards = (50.585_867, 0.0);
     // Alice should withdraw less than she deposited (198_393.304)
-    let expected_withdraw_amounts = (98_697.812, 99_497.098);
-    let expected_alice_loss = 1_754.491_563_4;
-    let expected_bob_profit = 1_505.050_343;
-    let expected_fee = (98.796_609, 99.596_695);
+    let expected_withdraw_amounts = (98_647.284, 99_447.086);
+    let expected_alice_loss = 1_855.044_133;
+    let expected_bob_profit = 1_505.690_702;
+    let expected_fee = (98.746_031, 99.546_633);
 
     let snapshot_before_deposit = Snapshot::take(&testing_env);
     pool.deposit(alice, deposit, 198_000.0);
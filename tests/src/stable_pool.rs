@@ -0,0 +1,197 @@
+use soroban_sdk::{Address, Env, Vec};
+
+use crate::{
+    contracts::stable_pool::{self, Deposit, Swapped, Withdraw},
+    utils::{
+        desoroban_result, float_to_uint, float_to_uint_sp, get_latest_event, unwrap_call_result,
+        RateProvider, TestingEnv, Token,
+    },
+};
+
+struct StablePool {
+    env: Env,
+    client: stable_pool::Client<'static>,
+    tokens: std::vec::Vec<Token>,
+}
+
+impl StablePool {
+    fn create(testing_env: &TestingEnv, tokens_count: usize, fee_share_bp: u128) -> StablePool {
+        let env = testing_env.env.clone();
+        let mut tokens: std::vec::Vec<Token> = (0..tokens_count)
+            .map(|_| Token::create(&env, testing_env.admin.as_ref()))
+            .collect();
+        let mut token_addresses = Vec::new(&env);
+        for token in tokens.iter() {
+            token.default_airdrop(&testing_env.alice);
+            token.default_airdrop(&testing_env.bob);
+            token_addresses.push_back(token.as_address());
+        }
+
+        let id = testing_env.factory.create_stable_pool(
+            testing_env.admin.as_ref(),
+            20,
+            &token_addresses,
+            fee_share_bp,
+            0,
+        );
+        let client = stable_pool::Client::new(&env, &id);
+        // the pool orders its tokens by address
+        let pool_tokens = client.get_pool().tokens;
+        tokens.sort_by_key(|token| pool_tokens.first_index_of(&token.id));
+
+        StablePool {
+            env,
+            client,
+            tokens,
+        }
+    }
+
+    fn amounts(&self, amount: f64) -> Vec<u128> {
+        let mut amounts = Vec::new(&self.env);
+        for token in self.tokens.iter() {
+            amounts.push_back(float_to_uint(amount, token.client.decimals()));
+        }
+
+        amounts
+    }
+
+    fn deposit(&self, sender: &Address, amounts: &Vec<u128>) {
+        unwrap_call_result(
+            &self.env,
+            desoroban_result(self.client.try_deposit(sender, amounts, &0)),
+        );
+    }
+
+    fn swap(&self, sender: &Address, amount: u128, token_from: u32, token_to: u32) -> u128 {
+        unwrap_call_result(
+            &self.env,
+            desoroban_result(self.client.try_swap(
+                sender,
+                sender,
+                &amount,
+                &0,
+                &token_from,
+                &token_to,
+            )),
+        )
+    }
+}
+
+#[test]
+fn deposit_four_tokens() {
+    let testing_env = TestingEnv::default();
+    let pool = StablePool::create(&testing_env, 4, 0);
+
+    pool.deposit(testing_env.alice.as_ref(), &pool.amounts(1_000.0));
+
+    let user_deposit = pool.client.get_user_deposit(testing_env.alice.as_ref());
+    assert_eq!(user_deposit.lp_amount, float_to_uint_sp(4_000.0));
+    assert_eq!(
+        pool.client.get_pool().total_lp_amount,
+        float_to_uint_sp(4_000.0)
+    );
+
+    let event = get_latest_event::<Deposit>(&testing_env.env).expect("Expected Deposit");
+    assert_eq!(event.lp_amount, float_to_uint_sp(4_000.0));
+    assert_eq!(event.amounts, pool.amounts(1_000.0));
+}
+
+#[test]
+#[should_panic = "DexContract(InvalidArg)"]
+fn deposit_invalid_amounts_len() {
+    let testing_env = TestingEnv::default();
+    let pool = StablePool::create(&testing_env, 4, 0);
+
+    let mut amounts = pool.amounts(1_000.0);
+    amounts.pop_back();
+    pool.deposit(testing_env.alice.as_ref(), &amounts);
+}
+
+#[test]
+fn swap_between_any_tokens() {
+    let testing_env = TestingEnv::default();
+    let pool = StablePool::create(&testing_env, 5, 10);
+    pool.deposit(testing_env.alice.as_ref(), &pool.amounts(100_000.0));
+
+    let amount = float_to_uint(1_000.0, 7);
+    let bob = testing_env.bob.as_ref();
+    let bob_balance_before = pool.tokens[4].balance_of(bob);
+
+    let (expected_output, expected_fee) = pool.client.get_receive_amount(&amount, &1, &4);
+    let output = pool.swap(bob, amount, 1, 4);
+
+    assert_eq!(output, expected_output);
+    assert!(output < amount && output > amount * 99 / 100);
+    assert_eq!(pool.tokens[4].balance_of(bob), bob_balance_before + output);
+
+    let event = get_latest_event::<Swapped>(&testing_env.env).expect("Expected Swapped");
+    assert_eq!(event.from_token, pool.tokens[1].id);
+    assert_eq!(event.to_token, pool.tokens[4].id);
+    assert_eq!(event.to_amount, output);
+    assert_eq!(event.fee, expected_fee);
+}
+
+#[test]
+#[should_panic = "DexContract(InvalidArg)"]
+fn swap_invalid_token_index() {
+    let testing_env = TestingEnv::default();
+    let pool = StablePool::create(&testing_env, 3, 0);
+    pool.deposit(testing_env.alice.as_ref(), &pool.amounts(1_000.0));
+
+    pool.swap(testing_env.bob.as_ref(), 1_000, 0, 3);
+}
+
+#[test]
+#[should_panic = "DexContract(InvalidArg)"]
+fn swap_same_token() {
+    let testing_env = TestingEnv::default();
+    let pool = StablePool::create(&testing_env, 3, 0);
+    pool.deposit(testing_env.alice.as_ref(), &pool.amounts(1_000.0));
+
+    pool.swap(testing_env.bob.as_ref(), 1_000, 2, 2);
+}
+
+#[test]
+#[should_panic = "DexContract(Forbidden)"]
+fn set_rate_provider_with_liquidity() {
+    let testing_env = TestingEnv::default();
+    let pool = StablePool::create(&testing_env, 3, 0);
+    pool.deposit(testing_env.alice.as_ref(), &pool.amounts(1_000.0));
+    let rate_provider = RateProvider::create(&testing_env.env, 1.1);
+
+    unwrap_call_result(
+        &testing_env.env,
+        desoroban_result(
+            pool.client
+                .try_set_rate_provider(&0, &Some(rate_provider.id)),
+        ),
+    );
+}
+
+#[test]
+fn withdraw_all() {
+    let testing_env = TestingEnv::default();
+    let pool = StablePool::create(&testing_env, 8, 0);
+    let alice = testing_env.alice.as_ref();
+    let balances_before: std::vec::Vec<u128> = pool
+        .tokens
+        .iter()
+        .map(|token| token.balance_of(alice))
+        .collect();
+
+    pool.deposit(alice, &pool.amounts(1_000.0));
+    let lp_amount = pool.client.get_user_deposit(alice).lp_amount;
+    unwrap_call_result(
+        &testing_env.env,
+        desoroban_result(pool.client.try_withdraw(alice, &lp_amount)),
+    );
+
+    assert_eq!(pool.client.get_user_deposit(alice).lp_amount, 0);
+    for (token, balance_before) in pool.tokens.iter().zip(balances_before) {
+        assert_eq!(token.balance_of(alice), balance_before);
+    }
+
+    let event = get_latest_event::<Withdraw>(&testing_env.env).expect("Expected Withdraw");
+    assert_eq!(event.lp_amount, lp_amount);
+    assert_eq!(event.amounts.len(), 8);
+}
@@ -1,6 +1,17 @@
-use soroban_sdk::{testutils::Address as _, Address};
+use soroban_sdk::{testutils::Address as _, Address, Symbol};
 
-use crate::three_pool_utils::{percentage_to_bp, TestingEnv};
+use crate::{
+    contracts::three_pool::{
+        AdminChanged, AdminFeeShareUpdated, DepositFeeShareUpdated, LegacyPool,
+        OffpegFeeMultiplierUpdated, PriceOracle, RateSource, RateSourceUpdated,
+        SwapFeeShareUpdated, Token, UpgraderChanged, WithdrawFeeShareUpdated,
+    },
+    three_pool_utils::{
+        float_to_uint, get_latest_event, percentage_to_bp, percentage_to_fee_share, TestingEnv,
+        TestingEnvConfig,
+    },
+    utils::{PriceFeed, RateProvider},
+};
 
 #[test]
 #[should_panic = "DexContract(InvalidArg)"]
@@ -16,16 +27,75 @@ fn set_admin_fee_share() {
 
     testing_env.pool.set_admin_fee_share(admin_fee_share);
     assert_eq!(testing_env.pool.admin_fee_share_bp(), expected_fee_share);
+
+    let event = get_latest_event::<AdminFeeShareUpdated>(&testing_env.env)
+        .expect("Expected AdminFeeShareUpdated");
+    assert_eq!(event.admin_fee_share_bp, expected_fee_share);
+}
+
+#[test]
+fn set_swap_fee_share() {
+    let testing_env = TestingEnv::default();
+    let fee_share = 0.004;
+    let expected_fee_share = percentage_to_fee_share(fee_share);
+
+    testing_env.pool.set_swap_fee_share(fee_share);
+    assert_eq!(testing_env.pool.swap_fee_share(), expected_fee_share);
+
+    let event = get_latest_event::<SwapFeeShareUpdated>(&testing_env.env)
+        .expect("Expected SwapFeeShareUpdated");
+    assert_eq!(event.swap_fee_share, expected_fee_share);
+}
+
+#[test]
+fn set_withdraw_fee_share() {
+    let testing_env = TestingEnv::default();
+    let fee_share = 0.5;
+    let expected_fee_share = percentage_to_fee_share(fee_share);
+
+    testing_env.pool.set_withdraw_fee_share(fee_share);
+    assert_eq!(testing_env.pool.withdraw_fee_share(), expected_fee_share);
+
+    let event = get_latest_event::<WithdrawFeeShareUpdated>(&testing_env.env)
+        .expect("Expected WithdrawFeeShareUpdated");
+    assert_eq!(event.withdraw_fee_share, expected_fee_share);
 }
 
 #[test]
-fn set_fee_share() {
+fn set_deposit_fee_share() {
     let testing_env = TestingEnv::default();
-    let fee_share = 0.01;
-    let expected_fee_share = percentage_to_bp(fee_share);
+    let fee_share = 0.2;
+    let expected_fee_share = percentage_to_fee_share(fee_share);
+
+    testing_env.pool.set_deposit_fee_share(fee_share);
+    assert_eq!(testing_env.pool.deposit_fee_share(), expected_fee_share);
 
-    testing_env.pool.set_fee_share(fee_share);
-    assert_eq!(testing_env.pool.fee_share_bp(), expected_fee_share);
+    let event = get_latest_event::<DepositFeeShareUpdated>(&testing_env.env)
+        .expect("Expected DepositFeeShareUpdated");
+    assert_eq!(event.deposit_fee_share, expected_fee_share);
+}
+
+#[test]
+fn withdraw_fee_share_does_not_change_swap_fee() {
+    let testing_env = TestingEnv::create(TestingEnvConfig::default().with_pool_fee_share(0.1));
+    let pool = &testing_env.pool;
+    let amount = float_to_uint(1_000.0, 7);
+
+    let swap_fee_before = pool
+        .client
+        .get_receive_amount(&amount, &Token::A, &Token::B)
+        .1;
+    let withdraw_fees_before = pool.client.get_withdraw_amount(&1_000_000).fees;
+
+    pool.set_withdraw_fee_share(1.0);
+
+    assert_eq!(
+        pool.client
+            .get_receive_amount(&amount, &Token::A, &Token::B)
+            .1,
+        swap_fee_before
+    );
+    assert!(pool.client.get_withdraw_amount(&1_000_000).fees.0 > withdraw_fees_before.0 * 9);
 }
 
 #[test]
@@ -46,6 +116,9 @@ fn set_admin() {
 
     testing_env.pool.set_admin(new_admin.clone());
     assert_eq!(testing_env.pool.client.get_admin(), new_admin);
+
+    let event = get_latest_event::<AdminChanged>(&testing_env.env).expect("Expected AdminChanged");
+    assert_eq!(event.new_admin, new_admin);
 }
 
 #[test]
@@ -57,13 +130,249 @@ fn set_admin_fee_share_no_auth() {
 
 #[test]
 #[should_panic = "DexContract(InvalidArg)"]
-fn set_fee_share_invalid() {
-    TestingEnv::default().pool.set_fee_share(100.0);
+fn set_swap_fee_share_invalid() {
+    TestingEnv::default().pool.set_swap_fee_share(100.0);
+}
+
+#[test]
+#[should_panic = "DexContract(InvalidArg)"]
+fn set_withdraw_fee_share_invalid() {
+    TestingEnv::default().pool.set_withdraw_fee_share(100.0);
+}
+
+#[test]
+#[should_panic = "DexContract(InvalidArg)"]
+fn set_deposit_fee_share_invalid() {
+    TestingEnv::default().pool.set_deposit_fee_share(100.0);
+}
+
+#[test]
+#[should_panic = "Context(InvalidAction)"]
+fn set_swap_fee_share_no_auth() {
+    let testing_env = TestingEnv::default();
+    testing_env.clear_mock_auth().pool.set_swap_fee_share(1.0);
+}
+
+#[test]
+#[should_panic = "Context(InvalidAction)"]
+fn set_withdraw_fee_share_no_auth() {
+    let testing_env = TestingEnv::default();
+    testing_env
+        .clear_mock_auth()
+        .pool
+        .set_withdraw_fee_share(1.0);
+}
+
+#[test]
+#[should_panic = "Context(InvalidAction)"]
+fn set_deposit_fee_share_no_auth() {
+    let testing_env = TestingEnv::default();
+    testing_env
+        .clear_mock_auth()
+        .pool
+        .set_deposit_fee_share(1.0);
+}
+
+#[test]
+fn set_offpeg_fee_multiplier() {
+    let testing_env = TestingEnv::create(TestingEnvConfig::default().with_pool_fee_share(0.1));
+    let offpeg_fee_multiplier_bp = 100_000;
+
+    testing_env
+        .pool
+        .set_offpeg_fee_multiplier(offpeg_fee_multiplier_bp);
+    assert_eq!(
+        testing_env.pool.client.get_pool().offpeg_fee_multiplier_bp,
+        offpeg_fee_multiplier_bp
+    );
+
+    let event = get_latest_event::<OffpegFeeMultiplierUpdated>(&testing_env.env)
+        .expect("Expected OffpegFeeMultiplierUpdated");
+    assert_eq!(event.offpeg_fee_multiplier_bp, offpeg_fee_multiplier_bp);
+}
+
+#[test]
+#[should_panic = "DexContract(InvalidArg)"]
+fn set_offpeg_fee_multiplier_invalid() {
+    // 1% fee multiplied by 100 is the whole output
+    TestingEnv::create(TestingEnvConfig::default().with_pool_fee_share(1.0))
+        .pool
+        .set_offpeg_fee_multiplier(1_000_000);
+}
+
+#[test]
+#[should_panic = "DexContract(InvalidArg)"]
+fn set_swap_fee_share_invalid_with_offpeg_fee_multiplier() {
+    let testing_env = TestingEnv::create(TestingEnvConfig::default().with_pool_fee_share(0.1));
+
+    testing_env.pool.set_offpeg_fee_multiplier(1_000_000);
+    testing_env.pool.set_swap_fee_share(1.0);
 }
 
 #[test]
 #[should_panic = "Context(InvalidAction)"]
-fn set_fee_share_no_auth() {
+fn set_offpeg_fee_multiplier_no_auth() {
     let testing_env = TestingEnv::default();
-    testing_env.clear_mock_auth().pool.set_fee_share(1.0);
+    testing_env
+        .clear_mock_auth()
+        .pool
+        .set_offpeg_fee_multiplier(100_000);
+}
+
+#[test]
+fn set_rate_provider() {
+    let testing_env = TestingEnv::create(TestingEnvConfig::default().with_admin_init_deposit(0.0));
+    let TestingEnv {
+        ref env,
+        ref pool,
+        ref token_c,
+        ..
+    } = testing_env;
+    let rate_provider = RateProvider::create(env, 1.1);
+
+    pool.set_rate_provider(Token::C, Some(&rate_provider));
+
+    let pool_info = pool.client.get_pool();
+    assert_eq!(
+        pool_info.rate_sources.data,
+        (
+            RateSource::Fixed,
+            RateSource::Fixed,
+            RateSource::Provider(rate_provider.id.clone())
+        )
+    );
+    assert_eq!(pool_info.rates.data.2, float_to_uint(1.1, 18));
+
+    let event = get_latest_event::<RateSourceUpdated>(env).expect("Expected RateSourceUpdated");
+    assert_eq!(event.token, token_c.id);
+    assert_eq!(event.rate_source, RateSource::Provider(rate_provider.id));
+}
+
+#[test]
+#[should_panic = "DexContract(Forbidden)"]
+fn set_rate_provider_with_liquidity() {
+    let testing_env = TestingEnv::default();
+    let rate_provider = RateProvider::create(&testing_env.env, 1.1);
+
+    testing_env
+        .pool
+        .set_rate_provider(Token::A, Some(&rate_provider));
+}
+
+#[test]
+#[should_panic = "Context(InvalidAction)"]
+fn set_rate_provider_no_auth() {
+    let testing_env = TestingEnv::default();
+    let rate_provider = RateProvider::create(&testing_env.env, 1.1);
+
+    testing_env
+        .clear_mock_auth()
+        .pool
+        .set_rate_provider(Token::A, Some(&rate_provider));
+}
+
+#[test]
+fn set_oracle() {
+    let testing_env = TestingEnv::create(TestingEnvConfig::default().with_admin_init_deposit(0.0));
+    let TestingEnv {
+        ref env,
+        ref pool,
+        ref token_b,
+        ..
+    } = testing_env;
+    let price_feed = PriceFeed::create(env, 1.08);
+
+    pool.set_oracle(Token::B, &price_feed, 60, 5.0);
+
+    let expected_rate_source = RateSource::Oracle(PriceOracle {
+        price_feed: price_feed.id,
+        max_price_age: 60,
+        max_price_deviation_bp: 500,
+        peg_rate: 1_080_000_000_000_000_000,
+    });
+    let pool_info = pool.client.get_pool();
+    assert_eq!(pool_info.rate_sources.data.1, expected_rate_source);
+    assert_eq!(pool_info.rates.data.1, 1_080_000_000_000_000_000);
+
+    let event = get_latest_event::<RateSourceUpdated>(env).expect("Expected RateSourceUpdated");
+    assert_eq!(event.token, token_b.id);
+    assert_eq!(event.rate_source, expected_rate_source);
+}
+
+#[test]
+fn migrate_legacy_pool() {
+    let testing_env = TestingEnv::create(TestingEnvConfig::default().with_pool_fee_share(0.1));
+    let TestingEnv {
+        ref env, ref pool, ..
+    } = testing_env;
+    let pool_before = pool.client.get_pool();
+
+    // The layout stored by the previous contract version
+    env.as_contract(&pool.id, || {
+        let legacy_pool = LegacyPool {
+            a: pool_before.a,
+            fee_share_bp: 25,
+            admin_fee_share_bp: pool_before.admin_fee_share_bp,
+            total_lp_amount: pool_before.total_lp_amount,
+            tokens: pool_before.tokens.clone(),
+            tokens_decimals: pool_before.tokens_decimals.clone(),
+            token_balances: pool_before.token_balances.clone(),
+            acc_rewards_per_share_p: pool_before.acc_rewards_per_share_p.clone(),
+            admin_fee_amount: pool_before.admin_fee_amount.clone(),
+        };
+        env.storage()
+            .instance()
+            .set(&Symbol::new(env, "Pool"), &legacy_pool);
+    });
+
+    pool.migrate();
+
+    let pool_after = pool.client.get_pool();
+    assert_eq!(pool_after.swap_fee_share, 2_500);
+    assert_eq!(pool_after.withdraw_fee_share, 2_500);
+    assert_eq!(pool_after.deposit_fee_share, 0);
+    assert_eq!(pool_after.offpeg_fee_multiplier_bp, 0);
+    assert_eq!(pool_after.total_lp_amount, pool_before.total_lp_amount);
+    assert_eq!(pool_after.token_balances, pool_before.token_balances);
+}
+
+#[test]
+#[should_panic = "Context(InvalidAction)"]
+fn migrate_no_auth() {
+    let testing_env = TestingEnv::default();
+    testing_env.clear_mock_auth().pool.migrate();
+}
+
+#[test]
+fn set_upgrader_legacy_pool() {
+    let testing_env = TestingEnv::default();
+    let TestingEnv {
+        ref env,
+        ref pool,
+        ref factory,
+        ..
+    } = testing_env;
+
+    // Pools deployed before the factory upgrades have no upgrader
+    env.as_contract(&pool.id, || {
+        env.storage()
+            .instance()
+            .remove(&Symbol::new(env, "Upgrader"));
+    });
+    assert!(pool.client.try_get_upgrader().is_err());
+
+    pool.set_upgrader(&factory.id);
+
+    assert_eq!(pool.client.get_upgrader(), factory.id);
+    let event = get_latest_event::<UpgraderChanged>(env).expect("Expected UpgraderChanged");
+    assert_eq!(event.new_upgrader, factory.id);
+}
+
+#[test]
+#[should_panic = "Context(InvalidAction)"]
+fn set_upgrader_no_auth() {
+    let testing_env = TestingEnv::default();
+    let upgrader = Address::generate(&testing_env.env);
+
+    testing_env.clear_mock_auth().pool.set_upgrader(&upgrader);
 }
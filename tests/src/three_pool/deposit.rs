@@ -1,7 +1,11 @@
 use test_case::test_case;
 
 use crate::{
-    three_pool_utils::{Snapshot, TestingEnv, TestingEnvConfig, TRIPLE_ZERO},
+    contracts::three_pool::{Deposit, LpTransferred},
+    three_pool_utils::{
+        assert_rel_eq, float_to_uint, float_to_uint_sp, get_latest_event, Snapshot, TestingEnv,
+        TestingEnvConfig, TRIPLE_ZERO,
+    },
 };
 
 #[test]
@@ -34,30 +38,31 @@ fn deposit_with_overflow() {
         ..
     } = testing_env;
 
-    a_token.airdrop(alice, 10_000_000_000.0);
-    b_token.airdrop(alice, 10_000_000_000.0);
+    a_token.airdrop(alice, 1_000_000_000_000_000.0);
+    b_token.airdrop(alice, 1_000_000_000_000_000.0);
 
-    pool.deposit(alice, (600_000_000.0, 600_000_000.0, 600_000_000.0), 0.0);
+    pool.deposit(
+        alice,
+        (600_000_000_000_000.0, 600_000_000_000_000.0, 600_000_000.0),
+        0.0,
+    );
 }
 
-
 #[should_panic = "DexContract(InvalidFirstDeposit)"]
 #[test_case((99.0, 100.0, 100.0); "invalid_a")]
 #[test_case((100.0, 99.0, 100.0); "invalid_b")]
 #[test_case((100.0, 100.0, 99.0); "invalid_c")]
 fn deposit_invalid_first_deposit(deposit: (f64, f64, f64)) {
     let testing_env = TestingEnv::create(TestingEnvConfig::default().with_admin_init_deposit(0.0));
-    testing_env
-        .pool
-        .deposit(&testing_env.alice, deposit, 0.0);
+    testing_env.pool.deposit(&testing_env.alice, deposit, 0.0);
 }
 
-#[test_case((100.0, 50.0, 75.0), TRIPLE_ZERO, 225.0 ; "base")]
-#[test_case((50_000_000.0, 5_000.0, 5.0), TRIPLE_ZERO, 21_358_206.68 ; "deposit_disbalance")]
+#[test_case((100.0, 50.0, 75.0), TRIPLE_ZERO, 224.999 ; "base")]
+#[test_case((50_000_000.0, 5_000.0, 5.0), TRIPLE_ZERO, 21_358_206.679 ; "deposit_disbalance")]
 #[test_case((0.001, 0.001, 0.0), TRIPLE_ZERO, 0.002 ; "smallest_deposit")]
-#[test_case((100.0, 0.0, 0.0), TRIPLE_ZERO, 100.0 ; "deposit_only_a")]
-#[test_case((0.0, 100.0, 0.0), TRIPLE_ZERO, 100.0 ; "deposit_only_b")]
-#[test_case((0.0, 0.0, 100.0), TRIPLE_ZERO, 100.0 ; "deposit_only_c")]
+#[test_case((100.0, 0.0, 0.0), TRIPLE_ZERO, 99.999 ; "deposit_only_a")]
+#[test_case((0.0, 100.0, 0.0), TRIPLE_ZERO, 99.999 ; "deposit_only_b")]
+#[test_case((0.0, 0.0, 100.0), TRIPLE_ZERO, 99.999 ; "deposit_only_c")]
 fn deposit(deposit: (f64, f64, f64), expected_rewards: (f64, f64, f64), expected_lp: f64) {
     let testing_env = TestingEnv::default();
     testing_env.do_deposit(&testing_env.alice, deposit, expected_rewards, expected_lp);
@@ -124,3 +129,94 @@ fn get_reward_after_third_deposit() {
         expected_lp_amount,
     );
 }
+
+#[test]
+fn deposit_imbalance_fee() {
+    let testing_env = TestingEnv::create(TestingEnvConfig::default().with_pool_fee_share(1.0));
+    let TestingEnv {
+        ref pool,
+        ref admin,
+        ref alice,
+        ..
+    } = testing_env;
+
+    let (lp_amount, fees) = pool
+        .client
+        .get_deposit_amount(&(float_to_uint(100.0, 7), 0, 0));
+    let fee_free_pool = TestingEnv::default().pool;
+    let (fee_free_lp_amount, _) =
+        fee_free_pool
+            .client
+            .get_deposit_amount(&(float_to_uint(100.0, 7), 0, 0));
+
+    // 3/8 of the 1% fee on the deviation of each balance from the proportional one
+    let tolerance = float_to_uint(0.001, 7);
+    assert_rel_eq(fees.0, float_to_uint(0.25, 7), tolerance);
+    assert_rel_eq(fees.1, float_to_uint(0.125, 7), tolerance);
+    assert_rel_eq(fees.2, float_to_uint(0.125, 7), tolerance);
+    assert!(lp_amount < fee_free_lp_amount);
+
+    pool.deposit(alice, (100.0, 0.0, 0.0), 0.0);
+
+    let deposit = get_latest_event::<Deposit>(&testing_env.env).expect("Expected Deposit");
+    assert_eq!(deposit.lp_amount, lp_amount);
+    assert_eq!(deposit.fees, fees);
+
+    // Fees are distributed to the existing liquidity providers
+    let admin_rewards = pool.client.pending_reward(&admin.as_address());
+    assert_rel_eq(admin_rewards.0, fees.0, 1);
+    assert_rel_eq(admin_rewards.1, fees.1, 1);
+}
+
+#[test]
+fn transfer_lp() {
+    let testing_env = TestingEnv::default();
+    let TestingEnv {
+        ref pool,
+        ref alice,
+        ref bob,
+        ..
+    } = testing_env;
+
+    pool.deposit(alice, (1_000.0, 1_000.0, 1_000.0), 0.0);
+    pool.transfer_lp(alice, &bob.as_address(), 1_000.0);
+
+    assert_eq!(pool.user_lp_amount_f64(alice), 2_000.0);
+    assert_eq!(pool.user_lp_amount_f64(bob), 1_000.0);
+    assert_eq!(pool.total_lp(), float_to_uint_sp(303_000.0));
+
+    let event =
+        get_latest_event::<LpTransferred>(&testing_env.env).expect("Expected LpTransferred");
+    assert_eq!(event.sender, alice.as_address());
+    assert_eq!(event.recipient, bob.as_address());
+    assert_eq!(event.lp_amount, float_to_uint_sp(1_000.0));
+}
+
+#[test]
+#[should_panic = "DexContract(InvalidArg)"]
+fn transfer_lp_to_self() {
+    let testing_env = TestingEnv::default();
+    let alice = &testing_env.alice;
+
+    testing_env
+        .pool
+        .deposit(alice, (1_000.0, 1_000.0, 1_000.0), 0.0);
+    testing_env
+        .pool
+        .transfer_lp(alice, &alice.as_address(), 1_000.0);
+}
+
+#[test]
+#[should_panic = "DexContract(NotEnoughAmount)"]
+fn transfer_lp_not_enough() {
+    let testing_env = TestingEnv::default();
+    let TestingEnv {
+        ref pool,
+        ref alice,
+        ref bob,
+        ..
+    } = testing_env;
+
+    pool.deposit(alice, (1_000.0, 1_000.0, 1_000.0), 0.0);
+    pool.transfer_lp(alice, &bob.as_address(), 3_001.0);
+}
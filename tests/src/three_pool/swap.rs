@@ -1,8 +1,11 @@
 use test_case::test_case;
 
 use crate::{
-    three_pool_utils::{Snapshot, TestingEnv, TestingEnvConfig},
-    contracts::three_pool::Token as PoolToken
+    contracts::three_pool::{Swapped, Sync, Token as PoolToken},
+    three_pool_utils::{
+        assert_rel_eq, float_to_uint, get_latest_event, Snapshot, TestingEnv, TestingEnvConfig,
+    },
+    utils::{PriceFeed, RateProvider},
 };
 
 use super::DepositArgs;
@@ -56,16 +59,16 @@ fn simple_swaps(
 }
 
 #[test_case(
-    DepositArgs { amounts: (250_000.0, 0.0, 0.0), min_lp: 249_000.0 }, 10_000.0, 995.0, PoolToken::A, PoolToken::B, 9_966.249_774, 9.976_226; "swap_more_a"
+    DepositArgs { amounts: (250_000.0, 0.0, 0.0), min_lp: 249_000.0 }, 10_000.0, 995.0, PoolToken::A, PoolToken::B, 9_966.250_773, 9.976_227; "swap_more_a"
 )]
 #[test_case(
-    DepositArgs { amounts: (0.0, 250_000.0, 0.0), min_lp: 249_000.0 }, 10_000.0, 10010.0, PoolToken::A, PoolToken::B, 10_011.687_291, 10.021_709; "swap_more_b"
+    DepositArgs { amounts: (0.0, 250_000.0, 0.0), min_lp: 249_000.0 }, 10_000.0, 10010.0, PoolToken::A, PoolToken::B, 10_011.685_293, 10.021_707; "swap_more_b"
 )]
 #[test_case(
-    DepositArgs { amounts: (0.0, 0.0, 250_000.0), min_lp: 249_000.0 }, 10_000.0, 10010.0, PoolToken::A, PoolToken::C, 10_011.687_291, 10.021_709; "swap_more_c_a2c"
+    DepositArgs { amounts: (0.0, 0.0, 250_000.0), min_lp: 249_000.0 }, 10_000.0, 10010.0, PoolToken::A, PoolToken::C, 10_011.685_293, 10.021_707; "swap_more_c_a2c"
 )]
 #[test_case(
-    DepositArgs { amounts: (0.0, 0.0, 250_000.0), min_lp: 249_000.0 }, 10_000.0, 995.0, PoolToken::C, PoolToken::A, 9_966.249_774, 9.976_226; "swap_more_c_c2a"
+    DepositArgs { amounts: (0.0, 0.0, 250_000.0), min_lp: 249_000.0 }, 10_000.0, 995.0, PoolToken::C, PoolToken::A, 9_966.250_773, 9.976_227; "swap_more_c_c2a"
 )]
 #[test_case(
     DepositArgs { amounts: (0.0, 0.0, 250_000.0), min_lp: 249_000.0 }, 10_000.0, 995.0, PoolToken::A, PoolToken::B, 9_988.639_362, 9.998_638; "swap_more_c_a2b"
@@ -124,9 +127,23 @@ fn swap_more_than_pool_balance(token_from: PoolToken, token_to: PoolToken) {
     let snapshot_before = Snapshot::take(&testing_env);
 
     pool.deposit(alice, deposit, 1_000_000.0);
-    pool.swap(alice, alice, amount, 500_000.0, testing_env.get_token(token_from), testing_env.get_token(token_to));
+    pool.swap(
+        alice,
+        alice,
+        amount,
+        500_000.0,
+        testing_env.get_token(token_from),
+        testing_env.get_token(token_to),
+    );
     // Bring pool back to balance by Alice
-    pool.swap(alice, alice, amount, 500_000.0, testing_env.get_token(token_to), testing_env.get_token(token_from));
+    pool.swap(
+        alice,
+        alice,
+        amount,
+        500_000.0,
+        testing_env.get_token(token_to),
+        testing_env.get_token(token_from),
+    );
     pool.withdraw(alice, pool.user_lp_amount_f64(alice));
 
     let snapshot_after = Snapshot::take(&testing_env);
@@ -137,3 +154,175 @@ fn swap_more_than_pool_balance(token_from: PoolToken, token_to: PoolToken) {
 
     assert!(alice_balance_after <= alice_balance_before);
 }
+
+#[test]
+fn swap_sync_event() {
+    let testing_env = TestingEnv::default();
+    let TestingEnv {
+        ref env,
+        ref pool,
+        ref alice,
+        ref bob,
+        ref token_a,
+        ref token_b,
+        ref token_c,
+        ..
+    } = testing_env;
+
+    pool.swap(alice, bob, 100.0, 98.0, token_a, token_b);
+    let previous_nonce = get_latest_event::<Sync>(env).expect("Expected Sync").nonce;
+
+    pool.swap(alice, bob, 100.0, 98.0, token_b, token_c);
+    let sync = get_latest_event::<Sync>(env).expect("Expected Sync");
+    let pool_info = pool.client.get_pool();
+
+    assert_eq!(sync.nonce, previous_nonce + 1);
+    assert_eq!(sync.token_balances, pool_info.token_balances.data);
+    assert_eq!(sync.total_lp_amount, pool_info.total_lp_amount);
+    assert_eq!(
+        sync.acc_rewards_per_share_p,
+        pool_info.acc_rewards_per_share_p.data
+    );
+}
+
+#[test]
+fn swap_offpeg_fee() {
+    let testing_env = TestingEnv::create(TestingEnvConfig::default().with_pool_fee_share(0.1));
+    let TestingEnv {
+        ref env,
+        ref pool,
+        ref alice,
+        ref token_a,
+        ref token_b,
+        ..
+    } = testing_env;
+
+    let small_amount = float_to_uint(1.0, 7);
+    let large_amount = float_to_uint(90_000.0, 7);
+    let (_, flat_small_fee) =
+        pool.client
+            .get_receive_amount(&small_amount, &PoolToken::A, &PoolToken::B);
+    let (_, flat_large_fee) =
+        pool.client
+            .get_receive_amount(&large_amount, &PoolToken::A, &PoolToken::B);
+
+    pool.set_offpeg_fee_multiplier(100_000);
+
+    // A balanced pool keeps the fee close to the flat one
+    let (_, small_fee) =
+        pool.client
+            .get_receive_amount(&small_amount, &PoolToken::A, &PoolToken::B);
+    assert_rel_eq(small_fee, flat_small_fee, 1);
+
+    let quote = pool
+        .client
+        .quote_swap(&large_amount, &PoolToken::A, &PoolToken::B);
+    assert!(quote.fee > flat_large_fee * 2);
+
+    let (send_amount, send_fee) =
+        pool.client
+            .get_send_amount(&quote.output, &PoolToken::A, &PoolToken::B);
+    assert_rel_eq(send_amount, large_amount, large_amount / 1000);
+    assert_rel_eq(send_fee, quote.fee, quote.fee / 1000);
+
+    pool.swap(alice, alice, 90_000.0, 0.0, token_a, token_b);
+
+    let swapped = get_latest_event::<Swapped>(env).expect("Expected Swapped");
+    assert_eq!(swapped.to_amount, quote.output);
+    assert_eq!(swapped.fee, quote.fee);
+}
+
+#[test]
+fn swap_sub_bp_fee() {
+    let testing_env = TestingEnv::default();
+    let TestingEnv {
+        ref env,
+        ref pool,
+        ref alice,
+        ref token_a,
+        ref token_b,
+        ..
+    } = testing_env;
+
+    // 0.004%
+    pool.set_swap_fee_share(0.004);
+    pool.swap(alice, alice, 1_000.0, 0.0, token_a, token_b);
+
+    let swapped = get_latest_event::<Swapped>(env).expect("Expected Swapped");
+    assert_rel_eq(
+        swapped.fee,
+        float_to_uint(0.04, 7),
+        float_to_uint(0.000_1, 7),
+    );
+}
+
+#[test]
+fn swap_with_rate_provider() {
+    let testing_env = TestingEnv::create(TestingEnvConfig::default().with_admin_init_deposit(0.0));
+    let TestingEnv {
+        ref env,
+        ref pool,
+        ref admin,
+        ref alice,
+        ref token_a,
+        ref token_b,
+        ..
+    } = testing_env;
+
+    let rate_provider = RateProvider::create(env, 1.0);
+    pool.set_rate_provider(PoolToken::A, Some(&rate_provider));
+    pool.deposit(admin, (100_000.0, 100_000.0, 100_000.0), 0.0);
+
+    let amount = float_to_uint(1_000.0, 7);
+    let (flat_output, _) = pool
+        .client
+        .get_receive_amount(&amount, &PoolToken::A, &PoolToken::B);
+
+    rate_provider.set_rate(1.1);
+
+    // One token A is worth 1.1 token B, the pool values its balance accordingly
+    let quote = pool
+        .client
+        .quote_swap(&amount, &PoolToken::A, &PoolToken::B);
+    assert_rel_eq(quote.output, flat_output * 11 / 10, flat_output / 100);
+
+    pool.swap(alice, alice, 1_000.0, 0.0, token_a, token_b);
+
+    let swapped = get_latest_event::<Swapped>(env).expect("Expected Swapped");
+    assert_eq!(swapped.to_amount, quote.output);
+}
+
+#[test]
+fn swap_with_oracle() {
+    let amount = float_to_uint(1_000.0, 7);
+    let (flat_output, _) =
+        TestingEnv::default()
+            .pool
+            .client
+            .get_receive_amount(&amount, &PoolToken::C, &PoolToken::A);
+
+    let testing_env = TestingEnv::create(TestingEnvConfig::default().with_admin_init_deposit(0.0));
+    let TestingEnv {
+        ref env,
+        ref pool,
+        ref admin,
+        ref alice,
+        ref token_a,
+        ref token_c,
+        ..
+    } = testing_env;
+
+    let price_feed = PriceFeed::create(env, 1.08);
+    pool.set_oracle(PoolToken::C, &price_feed, 60, 5.0);
+    pool.deposit(admin, (100_000.0, 100_000.0, 100_000.0), 0.0);
+
+    let quote = pool
+        .client
+        .quote_swap(&amount, &PoolToken::C, &PoolToken::A);
+    assert_rel_eq(quote.output, flat_output * 108 / 100, flat_output / 100);
+
+    pool.swap(alice, alice, 1_000.0, 0.0, token_c, token_a);
+
+    let swapped = get_latest_event::<Swapped>(env).expect("Expected Swapped");
+    assert_eq!(swapped.to_amount, quote.output);
+}
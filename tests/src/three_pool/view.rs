@@ -0,0 +1,338 @@
+use soroban_sdk::vec;
+
+use crate::three_pool_utils::{
+    assert_rel_eq, desoroban_result, float_to_uint, unwrap_call_result, TestingEnv,
+    TestingEnvConfig,
+};
+
+#[test]
+fn get_pool_info() {
+    let testing_env = TestingEnv::create(TestingEnvConfig::default().with_pool_fee_share(1.0));
+    let TestingEnv {
+        ref pool,
+        ref admin,
+        ref alice,
+        ref bob,
+        ref token_a,
+        ref token_b,
+        ref token_c,
+        ..
+    } = testing_env;
+
+    pool.deposit(alice, (1_000.0, 1_000.0, 1_000.0), 0.0);
+    pool.swap(bob, bob, 100.0, 98.0, token_a, token_c);
+
+    let pool_info = pool.client.get_pool_info(&Some(alice.as_address()));
+    let pool_state = pool.client.get_pool();
+    let user_deposit = pool.user_deposit(alice);
+    let pending_reward = pool.client.pending_reward(&alice.as_address());
+
+    assert_eq!(
+        pool_info.tokens,
+        (token_a.id.clone(), token_b.id.clone(), token_c.id.clone())
+    );
+    assert_eq!(pool_info.tokens_decimals, (7, 7, 7));
+    // Pool stores balances in system precision (3 decimals)
+    assert_eq!(
+        pool_info.token_balances,
+        (
+            pool_state.token_balances.data.0 * 10_000,
+            pool_state.token_balances.data.1 * 10_000,
+            pool_state.token_balances.data.2 * 10_000
+        )
+    );
+    assert_eq!(pool_info.a, pool_state.a);
+    assert_eq!(pool_info.swap_fee_share, 10_000);
+    assert_eq!(pool_info.swap_fee_share_bp, 100);
+    assert_eq!(pool_info.withdraw_fee_share_bp, 100);
+    assert_eq!(pool_info.deposit_fee_share_bp, 100);
+    assert_eq!(pool_info.admin_fee_share_bp, 0);
+    assert_eq!(pool_info.total_lp_amount, pool_state.total_lp_amount);
+    assert_eq!(pool_info.admin, admin.as_address());
+    // The invariant of the balances after the swap can round a unit below the LP supply
+    assert_rel_eq(
+        pool_info.virtual_price,
+        pool.d() * float_to_uint(1.0, 18) / pool_state.total_lp_amount,
+        float_to_uint(1.0, 10),
+    );
+    assert_eq!(pool_info.user_lp_amount, user_deposit.lp_amount);
+    assert_eq!(pool_info.user_pending_rewards.0, pending_reward.0);
+    assert_eq!(pool_info.user_pending_rewards.1, pending_reward.1);
+    assert!(pool_info.user_pending_rewards.2 > 0);
+
+    let pool_info = pool.client.get_pool_info(&None);
+    assert_eq!(pool_info.user_lp_amount, 0);
+    assert_eq!(pool_info.user_pending_rewards, (0, 0, 0));
+}
+
+#[test]
+fn get_pool_info_empty_pool() {
+    let testing_env = TestingEnv::create(TestingEnvConfig::default().with_admin_init_deposit(0.0));
+    let pool_info = testing_env
+        .pool
+        .client
+        .get_pool_info(&Some(testing_env.alice.as_address()));
+
+    assert_eq!(pool_info.token_balances, (0, 0, 0));
+    assert_eq!(pool_info.total_lp_amount, 0);
+    assert_eq!(pool_info.virtual_price, 0);
+    assert_eq!(pool_info.user_lp_amount, 0);
+    assert_eq!(pool_info.user_pending_rewards, (0, 0, 0));
+}
+
+#[test]
+fn batch_user_views() {
+    let testing_env = TestingEnv::create(TestingEnvConfig::default().with_pool_fee_share(1.0));
+    let TestingEnv {
+        ref env,
+        ref pool,
+        ref alice,
+        ref bob,
+        ref token_a,
+        ref token_c,
+        ..
+    } = testing_env;
+
+    pool.deposit(alice, (1_000.0, 1_000.0, 1_000.0), 0.0);
+    pool.swap(bob, bob, 100.0, 98.0, token_a, token_c);
+
+    let users = vec![
+        env,
+        alice.as_address(),
+        bob.as_address(),
+        alice.as_address(),
+    ];
+
+    let user_deposits = pool.client.get_user_deposits(&users);
+    assert_eq!(user_deposits.len(), 3);
+    assert_eq!(user_deposits.get_unchecked(0), pool.user_deposit(alice));
+    assert_eq!(user_deposits.get_unchecked(1).lp_amount, 0);
+    assert_eq!(user_deposits.get_unchecked(2), pool.user_deposit(alice));
+
+    let pending_rewards = pool.client.pending_rewards(&users);
+    assert_eq!(pending_rewards.len(), 3);
+    assert_ne!(pending_rewards.get_unchecked(0), (0, 0, 0));
+    assert_eq!(pending_rewards.get_unchecked(1), (0, 0, 0));
+    assert_eq!(
+        pending_rewards.get_unchecked(0),
+        pending_rewards.get_unchecked(2)
+    );
+
+    // Missing deposits must not be created by the views
+    let has_bob_deposit = env.as_contract(&pool.id, || {
+        env.storage().persistent().has(&bob.as_address())
+    });
+    assert!(!has_bob_deposit);
+}
+
+#[test]
+fn quote_swap() {
+    let testing_env = TestingEnv::create(TestingEnvConfig::default().with_pool_fee_share(0.1));
+    let TestingEnv {
+        ref pool,
+        ref alice,
+        ref token_a,
+        ref token_c,
+        ..
+    } = testing_env;
+
+    let amount = float_to_uint(1_000.0, 7);
+    let quote = pool
+        .client
+        .quote_swap(&amount, &token_a.pool_token, &token_c.pool_token);
+    assert_eq!(
+        (quote.output, quote.fee),
+        pool.receive_amount(1_000.0, token_a, token_c)
+    );
+
+    let token_c_balance_before = token_c.balance_of(&alice.as_address());
+    pool.swap(alice, alice, 1_000.0, 0.0, token_a, token_c);
+
+    assert_eq!(
+        token_c.balance_of(&alice.as_address()) - token_c_balance_before,
+        quote.output
+    );
+    assert_eq!(
+        quote.new_token_balances,
+        pool.client.get_pool_info(&None).token_balances
+    );
+}
+
+#[test]
+fn quote_swap_price_impact() {
+    let testing_env = TestingEnv::create(TestingEnvConfig::default().with_pool_fee_share(0.1));
+    let TestingEnv {
+        ref pool,
+        ref token_a,
+        ref token_b,
+        ..
+    } = testing_env;
+
+    let quote = |amount: f64| {
+        pool.client.quote_swap(
+            &float_to_uint(amount, 7),
+            &token_a.pool_token,
+            &token_b.pool_token,
+        )
+    };
+
+    let small_quote = quote(1.0);
+    let medium_quote = quote(10_000.0);
+    let large_quote = quote(90_000.0);
+
+    assert_eq!(small_quote.price_impact_bp, 0);
+    assert!(medium_quote.price_impact_bp > small_quote.price_impact_bp);
+    assert!(large_quote.price_impact_bp > medium_quote.price_impact_bp);
+}
+
+#[test]
+fn quote_swap_max_balance() {
+    let testing_env = TestingEnv::default();
+    let TestingEnv {
+        ref pool,
+        ref alice,
+        ref token_a,
+        ref token_b,
+        ref token_c,
+        ..
+    } = testing_env;
+
+    // Just below `MAX_TOKEN_BALANCE`, 2^60 in system precision
+    for token in [token_a, token_b, token_c] {
+        token.airdrop(alice, 400_000_000_000_000.0);
+    }
+    pool.deposit(
+        alice,
+        (
+            380_000_000_000_000.0,
+            380_000_000_000_000.0,
+            380_000_000_000_000.0,
+        ),
+        0.0,
+    );
+
+    let quote = pool.client.quote_swap(
+        &float_to_uint(1_000_000_000.0, 7),
+        &token_a.pool_token,
+        &token_b.pool_token,
+    );
+
+    assert_eq!(quote.price_impact_bp, 0);
+    assert_eq!(
+        (quote.output, quote.fee),
+        pool.receive_amount(1_000_000_000.0, token_a, token_b)
+    );
+}
+
+#[test]
+#[should_panic = "DexContract(InvalidArg)"]
+fn quote_swap_same_token() {
+    let testing_env = TestingEnv::default();
+    let token_a = &testing_env.token_a.pool_token;
+    unwrap_call_result(
+        &testing_env.env,
+        desoroban_result(testing_env.pool.client.try_quote_swap(
+            &float_to_uint(1.0, 7),
+            token_a,
+            token_a,
+        )),
+    );
+}
+
+#[test]
+#[should_panic = "DexContract(ZeroOutput)"]
+fn quote_swap_zero_output() {
+    let testing_env = TestingEnv::default();
+    // Below the system precision of the pool
+    unwrap_call_result(
+        &testing_env.env,
+        desoroban_result(testing_env.pool.client.try_quote_swap(
+            &100,
+            &testing_env.token_a.pool_token,
+            &testing_env.token_b.pool_token,
+        )),
+    );
+}
+
+#[test]
+fn preview_deposit() {
+    let testing_env = TestingEnv::create(TestingEnvConfig::default().with_pool_fee_share(1.0));
+    let TestingEnv {
+        ref pool,
+        ref alice,
+        ref bob,
+        ref token_a,
+        ref token_c,
+        ..
+    } = testing_env;
+
+    pool.deposit(alice, (1_000.0, 1_000.0, 1_000.0), 0.0);
+    pool.swap(bob, bob, 100.0, 98.0, token_a, token_c);
+
+    let amounts = (
+        float_to_uint(1_000.0, 7),
+        float_to_uint(500.0, 7),
+        float_to_uint(250.0, 7),
+    );
+    let preview = pool.client.preview_deposit(&alice.as_address(), &amounts);
+    assert!(preview.rewards.2 > 0);
+
+    let balance_before = token_c.balance_of(&alice.as_address());
+    let lp_before = pool.user_deposit(alice).lp_amount;
+
+    pool.deposit(alice, (1_000.0, 500.0, 250.0), 0.0);
+
+    assert_eq!(pool.user_deposit(alice).lp_amount, preview.user_lp_amount);
+    assert_eq!(preview.user_lp_amount - lp_before, preview.lp_amount);
+    assert_eq!(
+        token_c.balance_of(&alice.as_address()),
+        balance_before - amounts.2 + preview.rewards.2
+    );
+    assert!(preview.fees.0 > 0 && preview.fees.1 > 0 && preview.fees.2 > 0);
+}
+
+#[test]
+fn preview_withdraw() {
+    let testing_env = TestingEnv::create(TestingEnvConfig::default().with_pool_fee_share(1.0));
+    let TestingEnv {
+        ref pool,
+        ref alice,
+        ref bob,
+        ref token_a,
+        ref token_b,
+        ref token_c,
+        ..
+    } = testing_env;
+
+    pool.deposit(alice, (1_000.0, 1_000.0, 1_000.0), 0.0);
+    pool.swap(bob, bob, 100.0, 98.0, token_a, token_c);
+
+    let lp_amount = pool.user_deposit(alice).lp_amount / 2;
+    let preview = pool
+        .client
+        .preview_withdraw(&alice.as_address(), &lp_amount);
+    assert_eq!(preview.lp_amount, lp_amount);
+    assert!(preview.rewards.2 > 0);
+
+    let balances_before = (
+        token_a.balance_of(&alice.as_address()),
+        token_b.balance_of(&alice.as_address()),
+        token_c.balance_of(&alice.as_address()),
+    );
+
+    pool.client.withdraw(&alice.as_address(), &lp_amount);
+
+    assert_eq!(pool.user_deposit(alice).lp_amount, preview.user_lp_amount);
+    assert_eq!(
+        token_a.balance_of(&alice.as_address()),
+        balances_before.0 + preview.amounts.0 + preview.rewards.0
+    );
+    assert_eq!(
+        token_b.balance_of(&alice.as_address()),
+        balances_before.1 + preview.amounts.1 + preview.rewards.1
+    );
+    assert_eq!(
+        token_c.balance_of(&alice.as_address()),
+        balances_before.2 + preview.amounts.2 + preview.rewards.2
+    );
+}
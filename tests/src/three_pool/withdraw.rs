@@ -1,7 +1,7 @@
 use test_case::test_case;
 
-use crate::{
-    three_pool_utils::{assert_rel_eq, float_to_uint, Snapshot, TestingEnv, TestingEnvConfig, TRIPLE_ZERO},
+use crate::three_pool_utils::{
+    assert_rel_eq, float_to_uint, Snapshot, TestingEnv, TestingEnvConfig, TRIPLE_ZERO,
 };
 
 use super::{DepositArgs, DoWithdrawArgs};
@@ -31,13 +31,13 @@ fn withdraw_zero_change() {
 #[test_case(
     TestingEnvConfig::default(),
     DepositArgs { amounts: (4_000.0, 5_000.0, 6_000.0), min_lp: 14_999.0 },
-    DoWithdrawArgs { amount: 14_999.948, expected_amounts: (4_952.364, 4_999.984, 5_047.602), expected_fee: TRIPLE_ZERO, expected_rewards: TRIPLE_ZERO, expected_user_lp_diff: 14_999.948, expected_admin_fee: TRIPLE_ZERO }
+    DoWithdrawArgs { amount: 14_999.947, expected_amounts: (4_952.364, 4_999.984, 5_047.602), expected_fee: TRIPLE_ZERO, expected_rewards: TRIPLE_ZERO, expected_user_lp_diff: 14_999.947, expected_admin_fee: TRIPLE_ZERO }
     ; "base_withdraw"
 )]
 #[test_case(
     TestingEnvConfig::default().with_pool_fee_share(0.1).with_pool_admin_fee(20.0),
     DepositArgs { amounts: (4_000.0, 5_000.0, 6_000.0), min_lp: 14_999.0 },
-    DoWithdrawArgs { amount: 14_999.948, expected_amounts: (4_947.411, 4_994.984, 5_042.554), expected_fee: (4.952_364, 4.999_984, 5.047_602), expected_rewards: TRIPLE_ZERO, expected_user_lp_diff: 14_999.948, expected_admin_fee: (0.990_472_8, 0.999_996_8, 1.009_520_4) }
+    DoWithdrawArgs { amount: 14_999.198, expected_amounts: (4_947.157, 4_994.747, 5_042.296), expected_fee: (4.952_110, 4.999_747, 5.047_344), expected_rewards: TRIPLE_ZERO, expected_user_lp_diff: 14_999.198, expected_admin_fee: (0.990_422, 0.999_949_4, 1.009_468_8) }
     ; "withdraw_with_fee"
 )]
 #[test_case(
@@ -49,13 +49,13 @@ fn withdraw_zero_change() {
 #[test_case(
     TestingEnvConfig::default().with_pool_fee_share(0.1),
     DepositArgs { amounts: (15_000.0, 25_000.0, 20_000.0), min_lp: 59_950.0 },
-    DoWithdrawArgs { amount: 0.007, expected_amounts: (0.001, 0.001, 0.002), expected_fee: (0.000_002, 0.000_002, 0.000_003), expected_rewards: TRIPLE_ZERO, expected_user_lp_diff: 0.007, expected_admin_fee: TRIPLE_ZERO }
+    DoWithdrawArgs { amount: 0.007, expected_amounts: (0.001, 0.001, 0.003), expected_fee: (0.000_002, 0.000_002, 0.000_004), expected_rewards: TRIPLE_ZERO, expected_user_lp_diff: 0.007, expected_admin_fee: TRIPLE_ZERO }
     ; "smallest_withdraw_with_fee"
 )]
 #[test_case(
     TestingEnvConfig::default(),
-    DepositArgs { amounts: (50_000_000.0, 5_000.0, 5.0), min_lp: 21_358_206.68 },
-    DoWithdrawArgs { amount: 21_358_206.68, expected_amounts: (49_406_036.726, 103_545.587, 98_619.774), expected_fee: TRIPLE_ZERO, expected_rewards: TRIPLE_ZERO, expected_user_lp_diff: 21_358_206.68, expected_admin_fee: TRIPLE_ZERO }
+    DepositArgs { amounts: (50_000_000.0, 5_000.0, 5.0), min_lp: 21_358_206.679 },
+    DoWithdrawArgs { amount: 21_358_206.679, expected_amounts: (49_406_036.726, 103_545.587, 98_619.774), expected_fee: TRIPLE_ZERO, expected_rewards: TRIPLE_ZERO, expected_user_lp_diff: 21_358_206.679, expected_admin_fee: TRIPLE_ZERO }
     ; "withdraw_disbalance"
 )]
 fn withdraw(config: TestingEnvConfig, deposit_args: DepositArgs, do_withdraw_args: DoWithdrawArgs) {
@@ -90,12 +90,12 @@ fn withdraw_with_rewards() {
     } = testing_env;
 
     let deposits = (4_000.0, 5_000.0, 6_000.0);
-    let expected_user_lp_diff = 14_999.948;
+    let expected_user_lp_diff = 14_999.198;
     // Alice has around 5% of the liquidity pool, we swap 1000 USD with 0.1% fee, which is 5% of 1 USD fee total
-    let expected_rewards = (0.047_616_4, 0.047_618_8, 0.047_621_3);
+    let expected_rewards = (0.047_614_1, 0.047_616_5, 0.047_619_2);
     // Withdraw amounts sum is less than deposit amounts sum
-    let expected_withdraw_amounts = (4_947.414, 4_994.984, 5_042.551);
-    let expected_fee = (4.952_367, 4.999_984, 5.047_599);
+    let expected_withdraw_amounts = (4_947.160, 4_994.747, 5_042.293);
+    let expected_fee = (4.952_113, 4.999_747, 5.047_341);
 
     pool.deposit(alice, deposits, 14_999.0);
     pool.swap(bob, bob, 1_000.0, 998.0, token_a, token_b);
@@ -140,7 +140,8 @@ fn withdraw_multiply_times() {
 
     let alice_balance_after = snapshot_before.get_user_balances_sum(alice);
 
-    assert_rel_eq(pool.user_deposit(alice).lp_amount, 0, 2);
+    // Each withdrawal rounds the LP share down
+    assert_rel_eq(pool.user_deposit(alice).lp_amount, 0, n as u128);
     assert_eq!(alice_balance_after, alice_balance_before);
 }
 
@@ -184,11 +185,14 @@ fn withdraw_alice_profit_and_bob_loss() {
         TRIPLE_ZERO,
     );
 
-    let bob_b_diff =
-        snapshot_after_swap.bob_b_balance - snapshot_before_swap.bob_b_balance;
+    let bob_b_diff = snapshot_after_swap.bob_b_balance - snapshot_before_swap.bob_b_balance;
     let bob_loss = float_to_uint(swap_amount, 7) - bob_b_diff;
 
-    println!("{} {}", snapshot_after.get_user_balances_sum(alice), snapshot_before.get_user_balances_sum(alice));
+    println!(
+        "{} {}",
+        snapshot_after.get_user_balances_sum(alice),
+        snapshot_before.get_user_balances_sum(alice)
+    );
 
     let alice_profit = snapshot_after.get_user_balances_sum(alice)
         - snapshot_before.get_user_balances_sum(alice)
@@ -215,13 +219,13 @@ fn withdraw_alice_loss_and_bob_profit() {
 
     let deposit = (200_000.0, 0.0, 0.0);
     let swap_amount = 100_000.;
-    let expected_user_withdraw_lp_diff = 199_501.791;
-    let expected_rewards = (40.081_508_2, 0.0, 0.0);
+    let expected_user_withdraw_lp_diff = 199_401.862;
+    let expected_rewards = (40.069_446, 0.0, 0.0);
     // Alice should withdraw less than she deposited (198_393.304)
-    let expected_withdraw_amounts = (79_659.219, 79_800.429, 39_900.214);
-    let expected_alice_loss = 600.056_491_8;
-    let expected_bob_profit = 253.558_088;
-    let expected_fee = (79.738_958, 79.880_310, 39.940_155);
+    let expected_withdraw_amounts = (79_615.283, 79_766.471, 39_878.261);
+    let expected_alice_loss = 699.915_554;
+    let expected_bob_profit = 253.553_093;
+    let expected_fee = (79.694_978, 79.846_318, 39.918_18);
 
     let snapshot_before_deposit = Snapshot::take(&testing_env);
     pool.deposit(alice, deposit, 198_000.0);
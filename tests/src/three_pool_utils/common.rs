@@ -8,12 +8,17 @@ use color_print::cformat;
 use soroban_sdk::{
     testutils::Events,
     xdr::{ScError, ScVal},
-    Address, BytesN, ConversionError, Env, Error as SorobanError, FromVal, InvokeError, Symbol,
-    TryFromVal, Val,
+    Address, BytesN, ConversionError, Env, Error as SorobanError, FromVal, InvokeError, Map,
+    Symbol, TryFromVal, Val,
 };
 
 use soroban_sdk::xdr::ScAddress;
 
+use crate::{
+    contracts::{metapool, three_pool},
+    utils::{impl_event_topics, EventTopics},
+};
+
 pub const SYSTEM_PRECISION: u32 = 3;
 
 pub fn error_code_to_error(v: u32) -> shared::Error {
@@ -82,21 +87,46 @@ fn type_name_of_event<T: FromVal<Env, Val> + ?Sized>() -> String {
         .collect()
 }
 
-pub fn get_latest_event<T: FromVal<Env, Val>>(env: &Env) -> Option<T> {
+impl_event_topics!(
+    three_pool::Swapped => ["sender", "from_token", "to_token"],
+    three_pool::Deposit => ["user"],
+    three_pool::Withdraw => ["user"],
+    three_pool::RewardsClaimed => ["user"],
+    three_pool::LpTransferred => ["sender", "recipient"],
+    three_pool::AdminFeeClaimed => ["admin"],
+    three_pool::AdminChanged => ["new_admin"],
+    three_pool::SwapFeeShareUpdated => [],
+    three_pool::WithdrawFeeShareUpdated => [],
+    three_pool::DepositFeeShareUpdated => [],
+    three_pool::AdminFeeShareUpdated => [],
+    three_pool::OffpegFeeMultiplierUpdated => [],
+    three_pool::RateSourceUpdated => ["token"],
+    three_pool::Upgraded => [],
+    three_pool::UpgraderChanged => ["new_upgrader"],
+    three_pool::Sync => [],
+    metapool::SwappedUnderlying => ["sender", "from_token", "to_token"],
+    metapool::Deposit => ["user"],
+    metapool::BaseRewardsAdded => [],
+);
+
+pub fn get_latest_event<T: FromVal<Env, Val> + EventTopics>(env: &Env) -> Option<T> {
     env.events()
         .all()
         .iter()
         .rev()
-        .find_map(|(_, topic, event_data)| {
-            Symbol::try_from_val(env, &topic.last().unwrap())
-                .map(|symbol| {
-                    symbol
-                        .to_string()
-                        .eq(&type_name_of_event::<T>())
-                        .then(|| T::from_val(env, &event_data))
-                })
-                .ok()
-                .flatten()
+        .find_map(|(_, topics, event_data)| {
+            let symbol = Symbol::try_from_val(env, &topics.first()?).ok()?;
+            if symbol.to_string() != type_name_of_event::<T>() {
+                return None;
+            }
+
+            // Topic fields are published separately from the data map
+            let mut data = Map::<Symbol, Val>::from_val(env, &event_data);
+            for (field, topic) in T::TOPICS.iter().zip(topics.iter().skip(1)) {
+                data.set(Symbol::new(env, field), topic);
+            }
+
+            Some(T::from_val(env, &data.to_val()))
         })
 }
 
@@ -131,3 +161,9 @@ pub fn percentage_to_bp(percentage: f64) -> u128 {
 
     (percentage * 100.0) as u128
 }
+
+pub fn percentage_to_fee_share(percentage: f64) -> u128 {
+    assert!((0.0..=100.0).contains(&percentage));
+
+    (percentage * 10_000.0).round() as u128
+}
@@ -1,7 +1,7 @@
-use soroban_sdk::{Address, BytesN, Env, vec};
+use soroban_sdk::{vec, Address, BytesN, Env};
 
 use crate::{
-    contracts::{factory, three_pool, pool},
+    contracts::{factory, metapool, pool, stable_pool, three_pool},
     utils::{desoroban_result, unwrap_call_result},
 };
 
@@ -15,10 +15,18 @@ impl PoolFactory {
     pub fn create(env: &Env, admin: &Address) -> PoolFactory {
         let three_pool_wasm_hash = env.deployer().upload_contract_wasm(three_pool::WASM);
         let two_pool_wasm_hash = env.deployer().upload_contract_wasm(pool::WASM);
+        let stable_pool_wasm_hash = env.deployer().upload_contract_wasm(stable_pool::WASM);
+        let metapool_wasm_hash = env.deployer().upload_contract_wasm(metapool::WASM);
         let id = env.register_contract_wasm(None, factory::WASM);
         let client = factory::Client::new(env, &id);
 
-        client.initialize(&two_pool_wasm_hash, &three_pool_wasm_hash, admin);
+        client.initialize(
+            &two_pool_wasm_hash,
+            &three_pool_wasm_hash,
+            &stable_pool_wasm_hash,
+            &metapool_wasm_hash,
+            admin,
+        );
 
         PoolFactory {
             id,
@@ -46,6 +54,7 @@ impl PoolFactory {
                 &vec![&self.env, token_a.clone(), token_b.clone(), token_c.clone()],
                 &fee_share_bp,
                 &admin_fee,
+                &0,
             )),
         )
     }
@@ -64,11 +73,13 @@ impl PoolFactory {
         );
     }
 
-
     pub fn pool(&self, token_a: &Address, token_b: &Address, token_c: &Address) -> Address {
         unwrap_call_result(
             &self.env,
-            desoroban_result(self.client.try_pool(&vec![&self.env, token_a.clone(), token_b.clone(), token_c.clone()])),
+            desoroban_result(self.client.try_pool(
+                &vec![&self.env, token_a.clone(), token_b.clone(), token_c.clone()],
+                &None,
+            )),
         )
     }
 }
@@ -4,9 +4,10 @@ use super::User;
 use crate::{
     contracts::three_pool::{self, UserDeposit},
     three_pool_utils::{
-        desoroban_result, float_to_uint, float_to_uint_sp, percentage_to_bp, uint_to_float_sp,
-        unwrap_call_result, CallResult, Token
+        desoroban_result, float_to_uint, float_to_uint_sp, percentage_to_bp,
+        percentage_to_fee_share, uint_to_float_sp, unwrap_call_result, CallResult, Token,
     },
+    utils::{PriceFeed, RateProvider},
 };
 
 pub struct Pool {
@@ -25,11 +26,16 @@ impl Pool {
         }
     }
 
-    pub fn receive_amount(&self, amount: f64, token_from: &Token, token_to: &Token) -> (u128, u128) {
+    pub fn receive_amount(
+        &self,
+        amount: f64,
+        token_from: &Token,
+        token_to: &Token,
+    ) -> (u128, u128) {
         self.client.get_receive_amount(
             &float_to_uint(amount, 7),
             &token_from.pool_token,
-            &token_to.pool_token
+            &token_to.pool_token,
         )
     }
 
@@ -54,7 +60,9 @@ impl Pool {
         let pool_info = self.client.get_pool();
 
         assert_eq!(pool_info.a, expected_a);
-        assert_eq!(pool_info.fee_share_bp, expected_fee_share_bp);
+        assert_eq!(pool_info.swap_fee_share, expected_fee_share_bp * 100);
+        assert_eq!(pool_info.withdraw_fee_share, expected_fee_share_bp * 100);
+        assert_eq!(pool_info.deposit_fee_share, 0);
         assert_eq!(pool_info.admin_fee_share_bp, expected_admin_fee_share_bp);
 
         assert_eq!(pool_info.total_lp_amount, 0);
@@ -75,8 +83,16 @@ impl Pool {
         uint_to_float_sp(self.user_deposit(user).lp_amount)
     }
 
-    pub fn fee_share_bp(&self) -> u128 {
-        self.client.get_pool().fee_share_bp
+    pub fn swap_fee_share(&self) -> u128 {
+        self.client.get_pool().swap_fee_share
+    }
+
+    pub fn withdraw_fee_share(&self) -> u128 {
+        self.client.get_pool().withdraw_fee_share
+    }
+
+    pub fn deposit_fee_share(&self) -> u128 {
+        self.client.get_pool().deposit_fee_share
     }
 
     pub fn admin_fee_share_bp(&self) -> u128 {
@@ -104,10 +120,86 @@ impl Pool {
         );
     }
 
-    pub fn set_fee_share(&self, fee_share: f64) {
+    pub fn set_swap_fee_share(&self, fee_share: f64) {
+        unwrap_call_result(
+            &self.env,
+            desoroban_result(
+                self.client
+                    .try_set_swap_fee_share(&percentage_to_fee_share(fee_share)),
+            ),
+        );
+    }
+
+    pub fn set_withdraw_fee_share(&self, fee_share: f64) {
         unwrap_call_result(
             &self.env,
-            desoroban_result(self.client.try_set_fee_share(&percentage_to_bp(fee_share))),
+            desoroban_result(
+                self.client
+                    .try_set_withdraw_fee_share(&percentage_to_fee_share(fee_share)),
+            ),
+        );
+    }
+
+    pub fn set_deposit_fee_share(&self, fee_share: f64) {
+        unwrap_call_result(
+            &self.env,
+            desoroban_result(
+                self.client
+                    .try_set_deposit_fee_share(&percentage_to_fee_share(fee_share)),
+            ),
+        );
+    }
+
+    pub fn migrate(&self) {
+        unwrap_call_result(&self.env, desoroban_result(self.client.try_migrate()));
+    }
+
+    pub fn set_upgrader(&self, new_upgrader: &Address) {
+        unwrap_call_result(
+            &self.env,
+            desoroban_result(self.client.try_set_upgrader(new_upgrader)),
+        );
+    }
+
+    pub fn set_offpeg_fee_multiplier(&self, offpeg_fee_multiplier_bp: u128) {
+        unwrap_call_result(
+            &self.env,
+            desoroban_result(
+                self.client
+                    .try_set_offpeg_fee_multiplier(&offpeg_fee_multiplier_bp),
+            ),
+        );
+    }
+
+    pub fn set_rate_provider(
+        &self,
+        token: three_pool::Token,
+        rate_provider: Option<&RateProvider>,
+    ) {
+        unwrap_call_result(
+            &self.env,
+            desoroban_result(self.client.try_set_rate_provider(
+                &token,
+                &rate_provider.map(|rate_provider| rate_provider.id.clone()),
+            )),
+        );
+    }
+
+    pub fn set_oracle(
+        &self,
+        token: three_pool::Token,
+        price_feed: &PriceFeed,
+        max_price_age: u64,
+        max_price_deviation: f64,
+    ) {
+        unwrap_call_result(
+            &self.env,
+            desoroban_result(self.client.try_set_oracle(
+                &token,
+                &price_feed.id,
+                &max_price_age,
+                &percentage_to_bp(max_price_deviation),
+            )),
         );
     }
 
@@ -125,7 +217,22 @@ impl Pool {
         );
     }
 
-    pub fn withdraw_checked(&self, user: &User, withdraw_amount: f64) -> CallResult {
+    pub fn transfer_lp(&self, sender: &User, recipient: &Address, lp_amount: f64) {
+        unwrap_call_result(
+            &self.env,
+            desoroban_result(self.client.try_transfer_lp(
+                &sender.as_address(),
+                recipient,
+                &float_to_uint_sp(lp_amount),
+            )),
+        );
+    }
+
+    pub fn withdraw_checked(
+        &self,
+        user: &User,
+        withdraw_amount: f64,
+    ) -> CallResult<(u128, u128, u128)> {
         desoroban_result(
             self.client
                 .try_withdraw(&user.as_address(), &float_to_uint_sp(withdraw_amount)),
@@ -141,7 +248,7 @@ impl Pool {
         user: &Address,
         deposit_amounts: (f64, f64, f64),
         min_lp_amount: f64,
-    ) -> CallResult {
+    ) -> CallResult<u128> {
         desoroban_result(self.client.try_deposit(
             user,
             &(
@@ -170,7 +277,7 @@ impl Pool {
         user: &User,
         deposit_amounts: (f64, f64, f64),
         min_lp_amount: f64,
-    ) -> CallResult {
+    ) -> CallResult<u128> {
         self.deposit_with_address_checked(&user.as_address(), deposit_amounts, min_lp_amount)
     }
 
@@ -208,7 +315,14 @@ impl Pool {
     ) {
         unwrap_call_result(
             &self.env,
-            self.swap_checked(sender, recipient, amount, receive_amount_min, token_from, token_to),
+            self.swap_checked(
+                sender,
+                recipient,
+                amount,
+                receive_amount_min,
+                token_from,
+                token_to,
+            ),
         );
     }
 }
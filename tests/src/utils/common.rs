@@ -8,13 +8,17 @@ use color_print::cformat;
 use soroban_sdk::{
     testutils::Events,
     xdr::{ScError, ScVal},
-    Address, BytesN, ConversionError, Env, Error as SorobanError, FromVal, InvokeError, Symbol,
-    TryFromVal, Val,
+    Address, BytesN, ConversionError, Env, Error as SorobanError, FromVal, InvokeError, Map,
+    Symbol, TryFromVal, Val,
 };
 
 use soroban_sdk::xdr::ScAddress;
 
-use crate::contracts::pool::Direction;
+use crate::contracts::{
+    factory,
+    pool::{self, Direction},
+    stable_pool,
+};
 
 pub const SYSTEM_PRECISION: u32 = 3;
 
@@ -84,21 +88,78 @@ fn type_name_of_event<T: FromVal<Env, Val> + ?Sized>() -> String {
         .collect()
 }
 
-pub fn get_latest_event<T: FromVal<Env, Val>>(env: &Env) -> Option<T> {
+/// Struct fields that an event publishes as topics, in publishing order
+pub trait EventTopics {
+    const TOPICS: &'static [&'static str] = &[];
+}
+
+macro_rules! impl_event_topics {
+    ($($event:ty => [$($topic:literal),*]),* $(,)?) => {
+        $(
+            impl EventTopics for $event {
+                const TOPICS: &'static [&'static str] = &[$($topic),*];
+            }
+        )*
+    };
+}
+
+pub(crate) use impl_event_topics;
+
+impl_event_topics!(
+    pool::Swapped => ["sender", "from_token", "to_token"],
+    pool::Deposit => ["user"],
+    pool::Withdraw => ["user"],
+    pool::RewardsClaimed => ["user"],
+    pool::AdminFeeClaimed => ["admin"],
+    pool::AdminChanged => ["new_admin"],
+    pool::SwapFeeShareUpdated => [],
+    pool::WithdrawFeeShareUpdated => [],
+    pool::DepositFeeShareUpdated => [],
+    pool::AdminFeeShareUpdated => [],
+    pool::OffpegFeeMultiplierUpdated => [],
+    pool::RateSourceUpdated => ["token"],
+    pool::Upgraded => [],
+    pool::UpgraderChanged => ["new_upgrader"],
+    pool::Sync => [],
+    stable_pool::Swapped => ["sender", "from_token", "to_token"],
+    stable_pool::Deposit => ["user"],
+    stable_pool::Withdraw => ["user"],
+    factory::PairCreated => ["token0", "token1"],
+    factory::ThreePoolCreated => ["token0", "token1", "token2"],
+    factory::StablePoolCreated => ["tokens"],
+    factory::MetapoolCreated => ["token", "base_pool"],
+    factory::AdminChanged => ["new_admin"],
+    factory::TwoPoolWasmHashUpdated => [],
+    factory::ThreePoolWasmHashUpdated => [],
+    factory::StablePoolWasmHashUpdated => [],
+    factory::MetapoolWasmHashUpdated => [],
+    factory::PermissionlessConfigUpdated => [],
+    factory::CreationFeeUpdated => [],
+    factory::TokenAllowlistEnabled => [],
+    factory::TokenAllowlistUpdated => ["token"],
+    factory::PoolsUpgraded => ["pool_type"],
+    factory::PoolStatusUpdated => ["pool"],
+    factory::Upgraded => [],
+);
+
+pub fn get_latest_event<T: FromVal<Env, Val> + EventTopics>(env: &Env) -> Option<T> {
     env.events()
         .all()
         .iter()
         .rev()
-        .find_map(|(_, topic, event_data)| {
-            Symbol::try_from_val(env, &topic.last().unwrap())
-                .map(|symbol| {
-                    symbol
-                        .to_string()
-                        .eq(&type_name_of_event::<T>())
-                        .then(|| T::from_val(env, &event_data))
-                })
-                .ok()
-                .flatten()
+        .find_map(|(_, topics, event_data)| {
+            let symbol = Symbol::try_from_val(env, &topics.first()?).ok()?;
+            if symbol.to_string() != type_name_of_event::<T>() {
+                return None;
+            }
+
+            // Topic fields are published separately from the data map
+            let mut data = Map::<Symbol, Val>::from_val(env, &event_data);
+            for (field, topic) in T::TOPICS.iter().zip(topics.iter().skip(1)) {
+                data.set(Symbol::new(env, field), topic);
+            }
+
+            Some(T::from_val(env, &data.to_val()))
         })
 }
 
@@ -134,6 +195,12 @@ pub fn percentage_to_bp(percentage: f64) -> u128 {
     (percentage * 100.0) as u128
 }
 
+pub fn percentage_to_fee_share(percentage: f64) -> u128 {
+    assert!((0.0..=100.0).contains(&percentage));
+
+    (percentage * 10_000.0).round() as u128
+}
+
 impl Direction {
     pub fn reverse(&self) -> Self {
         match self {
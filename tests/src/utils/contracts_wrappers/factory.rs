@@ -1,7 +1,7 @@
-use soroban_sdk::{Address, BytesN, Env, vec};
+use soroban_sdk::{vec, Address, BytesN, Env, Vec};
 
 use crate::{
-    contracts::{factory, pool, three_pool},
+    contracts::{factory, metapool, pool, stable_pool, three_pool},
     utils::{desoroban_result, unwrap_call_result},
 };
 
@@ -15,10 +15,18 @@ impl PoolFactory {
     pub fn create(env: &Env, admin: &Address) -> PoolFactory {
         let three_pool_wasm_hash = env.deployer().upload_contract_wasm(three_pool::WASM);
         let two_pool_wasm_hash = env.deployer().upload_contract_wasm(pool::WASM);
+        let stable_pool_wasm_hash = env.deployer().upload_contract_wasm(stable_pool::WASM);
+        let metapool_wasm_hash = env.deployer().upload_contract_wasm(metapool::WASM);
         let id = env.register_contract_wasm(None, factory::WASM);
         let client = factory::Client::new(env, &id);
 
-        client.initialize(&two_pool_wasm_hash, &three_pool_wasm_hash, admin);
+        client.initialize(
+            &two_pool_wasm_hash,
+            &three_pool_wasm_hash,
+            &stable_pool_wasm_hash,
+            &metapool_wasm_hash,
+            admin,
+        );
 
         PoolFactory {
             id,
@@ -45,10 +53,40 @@ impl PoolFactory {
                 &soroban_sdk::vec![&self.env, token_a.clone(), token_b.clone()],
                 &fee_share_bp,
                 &admin_fee,
+                &0,
             )),
         )
     }
 
+    pub fn create_stable_pool(
+        &self,
+        admin: &Address,
+        a: u128,
+        tokens: &Vec<Address>,
+        fee_share_bp: u128,
+        admin_fee: u128,
+    ) -> Address {
+        unwrap_call_result(
+            &self.env,
+            desoroban_result(self.client.try_create_stable_pool(
+                admin,
+                admin,
+                &a,
+                tokens,
+                &fee_share_bp,
+                &admin_fee,
+                &0,
+            )),
+        )
+    }
+
+    pub fn update_stable_pool_wasm_hash(&self, new_wasm_hash: &BytesN<32>) {
+        unwrap_call_result(
+            &self.env,
+            desoroban_result(self.client.try_update_stable_pool_wasm_hash(new_wasm_hash)),
+        );
+    }
+
     pub fn update_wasm_hash(&self, new_wasm_hash: &BytesN<32>) {
         unwrap_call_result(
             &self.env,
@@ -56,6 +94,16 @@ impl PoolFactory {
         );
     }
 
+    pub fn migrate(&self, stable_pool_wasm_hash: &BytesN<32>, metapool_wasm_hash: &BytesN<32>) {
+        unwrap_call_result(
+            &self.env,
+            desoroban_result(
+                self.client
+                    .try_migrate(stable_pool_wasm_hash, metapool_wasm_hash),
+            ),
+        );
+    }
+
     pub fn set_admin(&self, admin: Address) {
         unwrap_call_result(
             &self.env,
@@ -66,8 +114,10 @@ impl PoolFactory {
     pub fn pool(&self, token_a: &Address, token_b: &Address) -> Address {
         unwrap_call_result(
             &self.env,
-            desoroban_result(self.client.try_pool(&vec![&self.env, token_a.clone(), token_b.clone()])),
-
+            desoroban_result(
+                self.client
+                    .try_pool(&vec![&self.env, token_a.clone(), token_b.clone()], &None),
+            ),
         )
     }
 }
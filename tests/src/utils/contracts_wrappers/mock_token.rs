@@ -0,0 +1,36 @@
+use soroban_sdk::{contract, contractimpl, symbol_short, Address, Env};
+
+/// Exposes only `decimals`, enough for the factory token checks
+#[contract]
+pub struct MockDecimalsToken;
+
+#[contractimpl]
+impl MockDecimalsToken {
+    pub fn set_decimals(env: Env, decimals: u32) {
+        env.storage()
+            .instance()
+            .set(&symbol_short!("decimals"), &decimals);
+    }
+
+    pub fn decimals(env: Env) -> u32 {
+        env.storage()
+            .instance()
+            .get(&symbol_short!("decimals"))
+            .unwrap_or(7)
+    }
+}
+
+pub struct MockToken {
+    pub id: Address,
+    pub client: MockDecimalsTokenClient<'static>,
+}
+
+impl MockToken {
+    pub fn create(env: &Env, decimals: u32) -> MockToken {
+        let id = env.register_contract(None, MockDecimalsToken);
+        let client = MockDecimalsTokenClient::new(env, &id);
+        client.set_decimals(&decimals);
+
+        MockToken { id, client }
+    }
+}
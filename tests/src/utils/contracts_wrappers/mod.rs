@@ -1,9 +1,15 @@
 mod factory;
+mod mock_token;
 mod pool;
+mod price_feed;
+mod rate_provider;
 mod token;
 mod user;
 
 pub use factory::*;
+pub use mock_token::MockToken;
 pub use pool::*;
+pub use price_feed::*;
+pub use rate_provider::*;
 pub use token::*;
 pub use user::*;
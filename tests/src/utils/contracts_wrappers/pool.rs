@@ -1,11 +1,11 @@
 use soroban_sdk::{Address, Env};
 
-use super::User;
+use super::{PriceFeed, RateProvider, User};
 use crate::{
-    contracts::pool::{self, Direction, UserDeposit},
+    contracts::pool::{self, Direction, Token, UserDeposit},
     utils::{
-        desoroban_result, float_to_uint, float_to_uint_sp, percentage_to_bp, uint_to_float_sp,
-        unwrap_call_result, CallResult,
+        desoroban_result, float_to_uint, float_to_uint_sp, percentage_to_bp,
+        percentage_to_fee_share, uint_to_float_sp, unwrap_call_result, CallResult,
     },
 };
 
@@ -56,7 +56,9 @@ impl Pool {
         let pool_info = self.client.get_pool();
 
         assert_eq!(pool_info.a, expected_a);
-        assert_eq!(pool_info.fee_share_bp, expected_fee_share_bp);
+        assert_eq!(pool_info.swap_fee_share, expected_fee_share_bp * 100);
+        assert_eq!(pool_info.withdraw_fee_share, expected_fee_share_bp * 100);
+        assert_eq!(pool_info.deposit_fee_share, 0);
         assert_eq!(pool_info.admin_fee_share_bp, expected_admin_fee_share_bp);
 
         assert_eq!(pool_info.total_lp_amount, 0);
@@ -77,8 +79,16 @@ impl Pool {
         uint_to_float_sp(self.user_deposit(user).lp_amount)
     }
 
-    pub fn fee_share_bp(&self) -> u128 {
-        self.client.get_pool().fee_share_bp
+    pub fn swap_fee_share(&self) -> u128 {
+        self.client.get_pool().swap_fee_share
+    }
+
+    pub fn withdraw_fee_share(&self) -> u128 {
+        self.client.get_pool().withdraw_fee_share
+    }
+
+    pub fn deposit_fee_share(&self) -> u128 {
+        self.client.get_pool().deposit_fee_share
     }
 
     pub fn admin_fee_share_bp(&self) -> u128 {
@@ -106,10 +116,82 @@ impl Pool {
         );
     }
 
-    pub fn set_fee_share(&self, fee_share: f64) {
+    pub fn set_swap_fee_share(&self, fee_share: f64) {
+        unwrap_call_result(
+            &self.env,
+            desoroban_result(
+                self.client
+                    .try_set_swap_fee_share(&percentage_to_fee_share(fee_share)),
+            ),
+        );
+    }
+
+    pub fn set_withdraw_fee_share(&self, fee_share: f64) {
+        unwrap_call_result(
+            &self.env,
+            desoroban_result(
+                self.client
+                    .try_set_withdraw_fee_share(&percentage_to_fee_share(fee_share)),
+            ),
+        );
+    }
+
+    pub fn set_deposit_fee_share(&self, fee_share: f64) {
+        unwrap_call_result(
+            &self.env,
+            desoroban_result(
+                self.client
+                    .try_set_deposit_fee_share(&percentage_to_fee_share(fee_share)),
+            ),
+        );
+    }
+
+    pub fn migrate(&self) {
+        unwrap_call_result(&self.env, desoroban_result(self.client.try_migrate()));
+    }
+
+    pub fn set_upgrader(&self, new_upgrader: &Address) {
+        unwrap_call_result(
+            &self.env,
+            desoroban_result(self.client.try_set_upgrader(new_upgrader)),
+        );
+    }
+
+    pub fn set_offpeg_fee_multiplier(&self, offpeg_fee_multiplier_bp: u128) {
+        unwrap_call_result(
+            &self.env,
+            desoroban_result(
+                self.client
+                    .try_set_offpeg_fee_multiplier(&offpeg_fee_multiplier_bp),
+            ),
+        );
+    }
+
+    pub fn set_rate_provider(&self, token: Token, rate_provider: Option<&RateProvider>) {
+        unwrap_call_result(
+            &self.env,
+            desoroban_result(self.client.try_set_rate_provider(
+                &token,
+                &rate_provider.map(|rate_provider| rate_provider.id.clone()),
+            )),
+        );
+    }
+
+    pub fn set_oracle(
+        &self,
+        token: Token,
+        price_feed: &PriceFeed,
+        max_price_age: u64,
+        max_price_deviation: f64,
+    ) {
         unwrap_call_result(
             &self.env,
-            desoroban_result(self.client.try_set_fee_share(&percentage_to_bp(fee_share))),
+            desoroban_result(self.client.try_set_oracle(
+                &token,
+                &price_feed.id,
+                &max_price_age,
+                &percentage_to_bp(max_price_deviation),
+            )),
         );
     }
 
@@ -0,0 +1,58 @@
+use shared::price_feed::{self, PriceData, PriceFeed as _};
+use soroban_sdk::{contract, contractimpl, symbol_short, Address, Env};
+
+use crate::utils::float_to_uint;
+
+#[contract]
+pub struct MockPriceFeed;
+
+#[contractimpl]
+impl MockPriceFeed {
+    pub fn set_price(env: Env, price: i128, timestamp: u64) {
+        env.storage()
+            .instance()
+            .set(&symbol_short!("price"), &PriceData { price, timestamp });
+    }
+}
+
+#[contractimpl]
+impl price_feed::PriceFeed for MockPriceFeed {
+    fn decimals(_env: Env) -> u32 {
+        PriceFeed::DECIMALS
+    }
+
+    fn lastprice(env: Env) -> Option<PriceData> {
+        env.storage().instance().get(&symbol_short!("price"))
+    }
+}
+
+pub struct PriceFeed {
+    pub id: Address,
+    pub client: MockPriceFeedClient<'static>,
+    pub env: Env,
+}
+
+impl PriceFeed {
+    pub const DECIMALS: u32 = 14;
+
+    pub fn create(env: &Env, price: f64) -> PriceFeed {
+        let id = env.register_contract(None, MockPriceFeed);
+        let client = MockPriceFeedClient::new(env, &id);
+        let price_feed = PriceFeed {
+            id,
+            client,
+            env: env.clone(),
+        };
+        price_feed.set_price(price);
+
+        price_feed
+    }
+
+    /// Publishes the price at the current ledger timestamp
+    pub fn set_price(&self, price: f64) {
+        self.client.set_price(
+            &(float_to_uint(price, Self::DECIMALS) as i128),
+            &self.env.ledger().timestamp(),
+        );
+    }
+}
@@ -0,0 +1,63 @@
+use shared::{rate_provider::RATE_PRECISION, Error};
+use soroban_sdk::{contract, contractimpl, symbol_short, Address, Env};
+
+use crate::utils::float_to_uint;
+
+#[contract]
+pub struct MockRateProvider;
+
+#[contractimpl]
+impl MockRateProvider {
+    pub fn set_rate(env: Env, rate: u128) {
+        env.storage().instance().set(&symbol_short!("rate"), &rate);
+    }
+
+    /// Makes `get_rate` revert
+    pub fn set_failing(env: Env, failing: bool) {
+        env.storage()
+            .instance()
+            .set(&symbol_short!("failing"), &failing);
+    }
+
+    /// `RateProvider::get_rate` that can revert, the error is returned instead of panicking
+    /// since a panic of a native contract aborts the test when it is called from wasm
+    pub fn get_rate(env: Env) -> Result<u128, Error> {
+        let failing = env
+            .storage()
+            .instance()
+            .get(&symbol_short!("failing"))
+            .unwrap_or(false);
+
+        if failing {
+            return Err(Error::Unimplemented);
+        }
+
+        Ok(env
+            .storage()
+            .instance()
+            .get(&symbol_short!("rate"))
+            .unwrap_or(RATE_PRECISION))
+    }
+}
+
+pub struct RateProvider {
+    pub id: Address,
+    pub client: MockRateProviderClient<'static>,
+}
+
+impl RateProvider {
+    pub const DECIMALS: u32 = 18;
+
+    pub fn create(env: &Env, rate: f64) -> RateProvider {
+        let id = env.register_contract(None, MockRateProvider);
+        let client = MockRateProviderClient::new(env, &id);
+        let rate_provider = RateProvider { id, client };
+        rate_provider.set_rate(rate);
+
+        rate_provider
+    }
+
+    pub fn set_rate(&self, rate: f64) {
+        self.client.set_rate(&float_to_uint(rate, Self::DECIMALS));
+    }
+}
@@ -1,7 +1,7 @@
 use soroban_sdk::{Address, Env};
 
 use crate::{
-    contracts::pool::{Deposit, Direction, RewardsClaimed, Swapped, Withdraw},
+    contracts::pool::{AdminFeeClaimed, Deposit, Direction, RewardsClaimed, Swapped, Withdraw},
     utils::{assert_rel_eq, float_to_uint, float_to_uint_sp, percentage_to_bp},
 };
 
@@ -165,6 +165,11 @@ impl TestingEnv {
         let pool = Pool::new(env, pool);
 
         pool.assert_initialization(a, fee_share_bp, admin_fee_bp);
+        // new pools start without a deposit fee, the scenarios charge the imbalanced part of
+        // deposits at the pool fee share
+        if fee_share_bp > 0 {
+            pool.set_deposit_fee_share(fee_share_percentage);
+        }
 
         token_a.airdrop(admin, admin_init_deposit * 2.0);
         token_b.airdrop(admin, admin_init_deposit * 2.0);
@@ -197,6 +202,26 @@ impl TestingEnv {
         );
     }
 
+    pub fn assert_admin_fee_claimed_event(
+        &self,
+        (expected_yusd_fee, expected_yaro_fee): (f64, f64),
+    ) {
+        let admin_fee_claimed =
+            get_latest_event::<AdminFeeClaimed>(&self.env).expect("Expected AdminFeeClaimed");
+
+        assert_eq!(admin_fee_claimed.admin, self.admin.as_address());
+        assert_rel_eq(
+            admin_fee_claimed.fees.0,
+            float_to_uint(expected_yusd_fee, 7),
+            1,
+        );
+        assert_rel_eq(
+            admin_fee_claimed.fees.1,
+            float_to_uint(expected_yaro_fee, 7),
+            1,
+        );
+    }
+
     pub fn assert_swapped_event(
         &self,
         sender: &User,
@@ -602,6 +627,10 @@ impl TestingEnv {
         let title = format!("Claim admin fee, expected {:?}", expected_rewards);
         snapshot_before.print_change_with(&snapshot_after, &title);
 
+        if expected_rewards != DOUBLE_ZERO {
+            self.assert_admin_fee_claimed_event(expected_rewards);
+        }
+
         TestingEnv::assert_claim_admin_fee(snapshot_before, snapshot_after, expected_rewards);
     }
 